@@ -0,0 +1,39 @@
+use crate::messages::inter::rr_classes::RRClasses;
+use crate::messages::wire::WireError;
+use crate::rr_data::inter::rr_data::RRData;
+use crate::rr_data::rrsig_rr_data::RRSigRRData;
+use crate::utils::fqdn_utils::labels;
+use crate::zone::rr_set::RRSet;
+
+pub fn rr_set_signing_data(fqdn: &str, class: RRClasses, set: &RRSet, rrsig: &RRSigRRData) -> Result<Vec<u8>, WireError> {
+    let mut rrsig = rrsig.clone();
+    rrsig.set_signature(&[]);
+
+    let mut buf = rrsig.to_canonical_bytes()?;
+    buf.extend_from_slice(&set.to_canonical_bytes(&canonical_owner(fqdn, rrsig.labels()), class, rrsig.original_ttl())?);
+
+    Ok(buf)
+}
+
+pub fn signature_labels(fqdn: &str) -> u8 {
+    if fqdn.is_empty() {
+        return 0;
+    }
+
+    labels(fqdn)
+        .enumerate()
+        .filter(|(i, l)| !(*i == 0 && *l == "*"))
+        .count() as u8
+}
+
+fn canonical_owner(fqdn: &str, rrsig_labels: u8) -> String {
+    if signature_labels(fqdn) <= rrsig_labels {
+        return fqdn.to_string();
+    }
+
+    let parts: Vec<&str> = labels(fqdn).collect();
+    match rrsig_labels {
+        0 => "*".to_string(),
+        _ => format!("*.{}", parts[parts.len() - rrsig_labels as usize..].join("."))
+    }
+}
@@ -0,0 +1,65 @@
+use crate::dnssec::inter::digest_types::DigestTypes;
+use crate::rr_data::dnskey_rr_data::DnsKeyRRData;
+use crate::rr_data::ds_rr_data::DsRRData;
+use crate::rr_data::inter::rr_data::RRData;
+use crate::utils::fqdn_utils::pack_fqdn;
+use crate::utils::hash::inter::hash::Hash;
+use crate::utils::hash::sha1::Sha1;
+use crate::utils::hash::sha256::Sha256;
+use crate::utils::hash::sha384::Sha384;
+
+pub fn dnskey_digest(fqdn: &str, dnskey: &DnsKeyRRData, digest_type: DigestTypes) -> Option<Vec<u8>> {
+    let mut buf = pack_fqdn(&fqdn.to_lowercase());
+    buf.extend_from_slice(&dnskey.to_bytes().ok()?);
+
+    match digest_type {
+        DigestTypes::Sha1 => Some(digest::<Sha1>(&buf)),
+        DigestTypes::Sha256 => Some(digest::<Sha256>(&buf)),
+        DigestTypes::Sha384 => Some(digest::<Sha384>(&buf)),
+        DigestTypes::Gost => None
+    }
+}
+
+pub fn ds_from_dnskey(fqdn: &str, dnskey: &DnsKeyRRData, digest_type: DigestTypes) -> Option<DsRRData> {
+    Some(DsRRData::new(dnskey.key_tag(), dnskey.algorithm(), digest_type.code(), &dnskey_digest(fqdn, dnskey, digest_type)?))
+}
+
+pub fn ds_matches_dnskey(fqdn: &str, dnskey: &DnsKeyRRData, ds: &DsRRData) -> bool {
+    if ds.key_tag() != dnskey.key_tag() || ds.algorithm() != dnskey.algorithm() {
+        return false;
+    }
+
+    match DigestTypes::try_from(ds.digest_type()) {
+        Ok(digest_type) => dnskey_digest(fqdn, dnskey, digest_type).map_or(false, |digest| digest.eq(ds.digest())),
+        Err(_) => false
+    }
+}
+
+fn digest<H: Hash>(buf: &[u8]) -> Vec<u8> {
+    let mut hasher = H::new();
+    hasher.update(buf, 0, buf.len());
+    hasher.get_value().as_ref().to_vec()
+}
+
+#[test]
+fn test() {
+    use crate::utils::{base64, hex};
+
+    let dnskey = DnsKeyRRData::new(256, 3, 5, base64::decode("AQOeiiR0GOMYkDshWoSKz9XzfwJr1AYtsmx3TGkJaNXVbfi/2pHm822aJ5iI9BMzNXxeYCmZDRD99WYwYqUSdjMmmAphXdvxegXd/M5+X7OrzKBaMbCVdFLUUh6DhweJBjEVv5f2wwjM9XzcnOf+EPbtG9DMBmADjFDc2w/rljwvFw==").unwrap());
+    assert_eq!(dnskey.key_tag(), 60485);
+
+    let ds = ds_from_dnskey("dskey.example.com", &dnskey, DigestTypes::Sha1).unwrap();
+    assert_eq!(ds.key_tag(), 60485);
+    assert_eq!(ds.algorithm(), 5);
+    assert_eq!(hex::encode(ds.digest()), "2bb183af5f22588179a53b0a98631fad1a292118");
+
+    let ds = ds_from_dnskey("dskey.example.com", &dnskey, DigestTypes::Sha256).unwrap();
+    assert_eq!(hex::encode(ds.digest()), "d4b7d520e7bb5f0f67674a0cceb1e3e0614b93c4f9e99b8383f6a1e4469da50a");
+    assert!(ds_matches_dnskey("DSKEY.example.com", &dnskey, &ds));
+    assert!(!ds_matches_dnskey("other.example.com", &dnskey, &ds));
+
+    let ds = ds_from_dnskey("dskey.example.com", &dnskey, DigestTypes::Sha384).unwrap();
+    assert_eq!(ds.digest().len(), 48);
+    assert!(ds_matches_dnskey("dskey.example.com", &dnskey, &ds));
+    assert!(ds_from_dnskey("dskey.example.com", &dnskey, DigestTypes::Gost).is_none());
+}
@@ -0,0 +1,85 @@
+use std::fmt;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum DigestTypes {
+    Sha1,
+    Sha256,
+    Gost,
+    Sha384
+}
+
+impl DigestTypes {
+
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::Sha1 => 1,
+            Self::Sha256 => 2,
+            Self::Gost => 3,
+            Self::Sha384 => 4
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DigestTypeParseError {
+    UnknownCode(u8),
+    UnknownName(String)
+}
+
+impl fmt::Display for DigestTypeParseError {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::UnknownCode(v) => format!("unknown digest type code: {}", v),
+            Self::UnknownName(s) => format!("unknown digest type name: {}", s)
+        })
+    }
+}
+
+impl TryFrom<u8> for DigestTypes {
+
+    type Error = DigestTypeParseError;
+
+    fn try_from(v: u8) -> Result<Self, Self::Error> {
+        Ok(match v {
+            1 => Self::Sha1,
+            2 => Self::Sha256,
+            3 => Self::Gost,
+            4 => Self::Sha384,
+            _  => return Err(DigestTypeParseError::UnknownCode(v))
+        })
+    }
+}
+
+impl FromStr for DigestTypes {
+
+    type Err = DigestTypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "SHA-1" => Self::Sha1,
+            "SHA-256" => Self::Sha256,
+            "GOST" => Self::Gost,
+            "SHA-384" => Self::Sha384,
+            _  => {
+                let code = s.parse::<u8>()
+                    .map_err(|_| DigestTypeParseError::UnknownName(s.to_string()))?;
+                return Self::try_from(code);
+            }
+        })
+    }
+}
+
+impl fmt::Display for DigestTypes {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::Sha1 => "SHA-1",
+            Self::Sha256 => "SHA-256",
+            Self::Gost => "GOST",
+            Self::Sha384 => "SHA-384"
+        })
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum DnsSecAlgorithms {
+    RsaMd5,
+    Dh,
+    Dsa,
+    RsaSha1,
+    DsaNsec3Sha1,
+    RsaSha1Nsec3Sha1,
+    RsaSha256,
+    RsaSha512,
+    EccGost,
+    EcdsaP256Sha256,
+    EcdsaP384Sha384,
+    Ed25519,
+    Ed448
+}
+
+impl DnsSecAlgorithms {
+
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::RsaMd5 => 1,
+            Self::Dh => 2,
+            Self::Dsa => 3,
+            Self::RsaSha1 => 5,
+            Self::DsaNsec3Sha1 => 6,
+            Self::RsaSha1Nsec3Sha1 => 7,
+            Self::RsaSha256 => 8,
+            Self::RsaSha512 => 10,
+            Self::EccGost => 12,
+            Self::EcdsaP256Sha256 => 13,
+            Self::EcdsaP384Sha384 => 14,
+            Self::Ed25519 => 15,
+            Self::Ed448 => 16
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DnsSecAlgorithmParseError {
+    UnknownCode(u8),
+    UnknownName(String)
+}
+
+impl fmt::Display for DnsSecAlgorithmParseError {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::UnknownCode(v) => format!("unknown dnssec algorithm code: {}", v),
+            Self::UnknownName(s) => format!("unknown dnssec algorithm name: {}", s)
+        })
+    }
+}
+
+impl TryFrom<u8> for DnsSecAlgorithms {
+
+    type Error = DnsSecAlgorithmParseError;
+
+    fn try_from(v: u8) -> Result<Self, Self::Error> {
+        Ok(match v {
+            1 => Self::RsaMd5,
+            2 => Self::Dh,
+            3 => Self::Dsa,
+            5 => Self::RsaSha1,
+            6 => Self::DsaNsec3Sha1,
+            7 => Self::RsaSha1Nsec3Sha1,
+            8 => Self::RsaSha256,
+            10 => Self::RsaSha512,
+            12 => Self::EccGost,
+            13 => Self::EcdsaP256Sha256,
+            14 => Self::EcdsaP384Sha384,
+            15 => Self::Ed25519,
+            16 => Self::Ed448,
+            _  => return Err(DnsSecAlgorithmParseError::UnknownCode(v))
+        })
+    }
+}
+
+impl FromStr for DnsSecAlgorithms {
+
+    type Err = DnsSecAlgorithmParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "RSAMD5" => Self::RsaMd5,
+            "DH" => Self::Dh,
+            "DSA" => Self::Dsa,
+            "RSASHA1" => Self::RsaSha1,
+            "DSA-NSEC3-SHA1" => Self::DsaNsec3Sha1,
+            "RSASHA1-NSEC3-SHA1" => Self::RsaSha1Nsec3Sha1,
+            "RSASHA256" => Self::RsaSha256,
+            "RSASHA512" => Self::RsaSha512,
+            "ECC-GOST" => Self::EccGost,
+            "ECDSAP256SHA256" => Self::EcdsaP256Sha256,
+            "ECDSAP384SHA384" => Self::EcdsaP384Sha384,
+            "ED25519" => Self::Ed25519,
+            "ED448" => Self::Ed448,
+            _  => {
+                let code = s.parse::<u8>()
+                    .map_err(|_| DnsSecAlgorithmParseError::UnknownName(s.to_string()))?;
+                return Self::try_from(code);
+            }
+        })
+    }
+}
+
+impl fmt::Display for DnsSecAlgorithms {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::RsaMd5 => "RSAMD5",
+            Self::Dh => "DH",
+            Self::Dsa => "DSA",
+            Self::RsaSha1 => "RSASHA1",
+            Self::DsaNsec3Sha1 => "DSA-NSEC3-SHA1",
+            Self::RsaSha1Nsec3Sha1 => "RSASHA1-NSEC3-SHA1",
+            Self::RsaSha256 => "RSASHA256",
+            Self::RsaSha512 => "RSASHA512",
+            Self::EccGost => "ECC-GOST",
+            Self::EcdsaP256Sha256 => "ECDSAP256SHA256",
+            Self::EcdsaP384Sha384 => "ECDSAP384SHA384",
+            Self::Ed25519 => "ED25519",
+            Self::Ed448 => "ED448"
+        })
+    }
+}
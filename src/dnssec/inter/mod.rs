@@ -0,0 +1,3 @@
+pub mod dnssec_algorithms;
+pub mod digest_types;
+pub mod validation_status;
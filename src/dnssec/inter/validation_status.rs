@@ -0,0 +1,22 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ValidationStatus {
+    Secure,
+    Insecure,
+    Bogus,
+    Indeterminate
+}
+
+impl fmt::Display for ValidationStatus {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::Secure => "SECURE",
+            Self::Insecure => "INSECURE",
+            Self::Bogus => "BOGUS",
+            Self::Indeterminate => "INDETERMINATE"
+        })
+    }
+}
@@ -0,0 +1,7 @@
+pub mod inter;
+pub mod signing_key;
+pub mod canonical;
+pub mod digest;
+pub mod nsec3;
+pub mod zone_signer;
+pub mod validator;
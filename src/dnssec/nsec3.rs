@@ -0,0 +1,37 @@
+use crate::utils::base32;
+use crate::utils::fqdn_utils::pack_fqdn;
+use crate::utils::hash::inter::hash::Hash;
+use crate::utils::hash::sha1::Sha1;
+
+pub const NSEC3_HASH_SHA1: u8 = 1;
+
+pub fn nsec3_hash(fqdn: &str, salt: &[u8], iterations: u16) -> Vec<u8> {
+    let mut hasher = Sha1::new();
+
+    let name = pack_fqdn(&fqdn.to_lowercase());
+    hasher.update(&name, 0, name.len());
+    hasher.update(salt, 0, salt.len());
+    let mut digest = hasher.get_value();
+
+    for _ in 0..iterations {
+        hasher.reset();
+        hasher.update(&digest, 0, digest.len());
+        hasher.update(salt, 0, salt.len());
+        digest = hasher.get_value();
+    }
+
+    digest.to_vec()
+}
+
+pub fn nsec3_label(fqdn: &str, salt: &[u8], iterations: u16) -> String {
+    base32::hex_encode_nopad(&nsec3_hash(fqdn, salt, iterations)).to_lowercase()
+}
+
+#[test]
+fn test() {
+    let salt = [0xaa, 0xbb, 0xcc, 0xdd];
+    assert_eq!(nsec3_label("example", &salt, 12), "0p9mhaveqvm6t7vbl5lop2u3t2rp3tom");
+    assert_eq!(nsec3_label("a.example", &salt, 12), "35mthgpgcu1qg68fab165klnsnk3dpvl");
+    assert_eq!(nsec3_label("*.w.example", &salt, 12), "r53bq7cc2uvmubfu5ocmm6pers9tk9en");
+    assert_eq!(nsec3_label("xx.example", &salt, 12), "t644ebqk9bibcna874givr6joj62mlhv");
+}
@@ -0,0 +1,56 @@
+use crate::dnssec::inter::dnssec_algorithms::DnsSecAlgorithms;
+use crate::dnssec::zone_signer::{ErrorKind, ZoneSignerError};
+use crate::rr_data::dnskey_rr_data::DnsKeyRRData;
+use crate::utils::crypto::{ed25519, p256};
+
+#[derive(Debug, Clone)]
+pub struct SigningKey {
+    algorithm: DnsSecAlgorithms,
+    private_key: Vec<u8>,
+    dnskey: DnsKeyRRData
+}
+
+impl SigningKey {
+
+    pub fn new(algorithm: DnsSecAlgorithms, flags: u16, private_key: &[u8]) -> Result<Self, ZoneSignerError> {
+        let public_key = match algorithm {
+            DnsSecAlgorithms::EcdsaP256Sha256 => p256::public_key(private_key).map(|k| k.to_vec()),
+            DnsSecAlgorithms::Ed25519 => ed25519::public_key(private_key).map(|k| k.to_vec()),
+            _ => return Err(ZoneSignerError::new(ErrorKind::UnsupportedAlgorithm, &format!("algorithm {} is not supported for signing", algorithm)))
+        }.ok_or_else(|| ZoneSignerError::new(ErrorKind::InvalidKey, &format!("invalid private key for algorithm {}", algorithm)))?;
+
+        Ok(Self {
+            algorithm,
+            private_key: private_key.to_vec(),
+            dnskey: DnsKeyRRData::new(flags, 3, algorithm.code(), public_key)
+        })
+    }
+
+    pub fn algorithm(&self) -> DnsSecAlgorithms {
+        self.algorithm
+    }
+
+    pub fn flags(&self) -> u16 {
+        self.dnskey.flags()
+    }
+
+    pub fn is_ksk(&self) -> bool {
+        self.dnskey.is_secure_entry_point()
+    }
+
+    pub fn key_tag(&self) -> u16 {
+        self.dnskey.key_tag()
+    }
+
+    pub fn dnskey(&self) -> &DnsKeyRRData {
+        &self.dnskey
+    }
+
+    pub fn sign(&self, data: &[u8]) -> Result<Vec<u8>, ZoneSignerError> {
+        match self.algorithm {
+            DnsSecAlgorithms::EcdsaP256Sha256 => p256::sign(&self.private_key, data).map(|s| s.to_vec()),
+            DnsSecAlgorithms::Ed25519 => ed25519::sign(&self.private_key, data).map(|s| s.to_vec()),
+            _ => None
+        }.ok_or_else(|| ZoneSignerError::new(ErrorKind::InvalidKey, &format!("unable to sign with algorithm {}", self.algorithm)))
+    }
+}
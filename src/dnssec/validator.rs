@@ -0,0 +1,292 @@
+use std::fmt;
+use std::fmt::Formatter;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::dnssec::canonical::{rr_set_signing_data, signature_labels};
+use crate::dnssec::digest::ds_matches_dnskey;
+use crate::dnssec::inter::digest_types::DigestTypes;
+use crate::dnssec::inter::dnssec_algorithms::DnsSecAlgorithms;
+use crate::dnssec::inter::validation_status::ValidationStatus;
+use crate::messages::inter::rr_classes::RRClasses;
+use crate::rr_data::dnskey_rr_data::DnsKeyRRData;
+use crate::rr_data::ds_rr_data::DsRRData;
+use crate::rr_data::rrsig_rr_data::RRSigRRData;
+use crate::utils::crypto::{ed25519, p256};
+use crate::zone::rr_set::RRSet;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ValidationResult {
+    status: ValidationStatus,
+    reason: String,
+    ttl: Option<u32>
+}
+
+impl ValidationResult {
+
+    pub fn new(status: ValidationStatus, reason: &str) -> Self {
+        Self {
+            status,
+            reason: reason.to_string(),
+            ttl: None
+        }
+    }
+
+    pub fn status(&self) -> ValidationStatus {
+        self.status
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+
+    pub fn ttl(&self) -> Option<u32> {
+        self.ttl
+    }
+
+    pub fn is_secure(&self) -> bool {
+        self.status.eq(&ValidationStatus::Secure)
+    }
+}
+
+impl fmt::Display for ValidationResult {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.status, self.reason)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Validator {
+    now: u32
+}
+
+impl Default for Validator {
+
+    fn default() -> Self {
+        Self {
+            now: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs() as u32).unwrap_or(0)
+        }
+    }
+}
+
+impl Validator {
+
+    pub fn new(now: u32) -> Self {
+        Self {
+            now
+        }
+    }
+
+    pub fn set_now(&mut self, now: u32) {
+        self.now = now;
+    }
+
+    pub fn now(&self) -> u32 {
+        self.now
+    }
+
+    pub fn validate_rr_set(&self, fqdn: &str, class: RRClasses, set: &RRSet, rrsigs: &[RRSigRRData], dnskeys: &[DnsKeyRRData]) -> ValidationResult {
+        let rrsigs: Vec<&RRSigRRData> = rrsigs.iter()
+            .filter(|rrsig| rrsig.type_covered() == Some(&set.rtype()))
+            .collect();
+
+        // Insecure needs a proof that the zone is unsigned (no DS at the parent), which this API never
+        // sees, so missing signatures or unsupported algorithms are Indeterminate rather than Insecure.
+        if rrsigs.is_empty() {
+            return match dnskeys.is_empty() {
+                true => ValidationResult::new(ValidationStatus::Indeterminate, &format!("no RRSIG or DNSKEY present for {} {}", fqdn, set.rtype())),
+                false => ValidationResult::new(ValidationStatus::Bogus, &format!("no RRSIG covers {} {}", fqdn, set.rtype()))
+            };
+        }
+
+        if dnskeys.is_empty() {
+            return ValidationResult::new(ValidationStatus::Indeterminate, &format!("no DNSKEY available to validate {} {}", fqdn, set.rtype()));
+        }
+
+        let fqdn = fqdn.to_lowercase();
+        let owner_labels = signature_labels(&fqdn);
+
+        let mut supported = false;
+        let mut reason = String::new();
+
+        for rrsig in rrsigs {
+            if !is_supported(rrsig.algorithm()) {
+                continue;
+            }
+            supported = true;
+
+            if rrsig.labels() > owner_labels {
+                reason = format!("RRSIG labels {} exceed owner labels {}", rrsig.labels(), owner_labels);
+                continue;
+            }
+
+            let signer_name = rrsig.signer_name().map(|s| s.to_lowercase()).unwrap_or_default();
+            if !(signer_name.is_empty() || fqdn.eq(&signer_name) || fqdn.ends_with(&format!(".{}", signer_name))) {
+                reason = format!("signer {} is not an ancestor of {}", signer_name, fqdn);
+                continue;
+            }
+
+            if (self.now.wrapping_sub(rrsig.inception()) as i32) < 0 {
+                reason = format!("RRSIG with key tag {} is not yet valid", rrsig.key_tag());
+                continue;
+            }
+
+            if (rrsig.expiration().wrapping_sub(self.now) as i32) < 0 {
+                reason = format!("RRSIG with key tag {} has expired", rrsig.key_tag());
+                continue;
+            }
+
+            let keys: Vec<&DnsKeyRRData> = dnskeys.iter()
+                .filter(|key| key.key_tag() == rrsig.key_tag() && key.algorithm() == rrsig.algorithm()
+                    && key.protocol() == 3 && key.is_zone_key() && !key.is_revoked())
+                .collect();
+
+            if keys.is_empty() {
+                reason = format!("no DNSKEY matches key tag {}", rrsig.key_tag());
+                continue;
+            }
+
+            let data = match rr_set_signing_data(&fqdn, class, set, rrsig) {
+                Ok(data) => data,
+                Err(e) => {
+                    reason = e.to_string();
+                    continue;
+                }
+            };
+
+            if keys.iter().any(|key| verify(key, &data, rrsig.signature())) {
+                let ttl = set.ttl()
+                    .min(rrsig.original_ttl())
+                    .min(rrsig.expiration().wrapping_sub(self.now));
+
+                return ValidationResult {
+                    status: ValidationStatus::Secure,
+                    reason: format!("verified with key tag {}", rrsig.key_tag()),
+                    ttl: Some(ttl)
+                };
+            }
+
+            reason = format!("signature verification failed for key tag {}", rrsig.key_tag());
+        }
+
+        match supported {
+            true => ValidationResult::new(ValidationStatus::Bogus, &reason),
+            false => ValidationResult::new(ValidationStatus::Indeterminate, "no RRSIG uses a supported algorithm")
+        }
+    }
+
+    pub fn validate_dnskeys(&self, apex: &str, class: RRClasses, dnskeys: &RRSet, rrsigs: &[RRSigRRData], ds: &[DsRRData]) -> ValidationResult {
+        if ds.is_empty() {
+            return ValidationResult::new(ValidationStatus::Indeterminate, &format!("no DS present for {}", apex));
+        }
+
+        let keys: Vec<DnsKeyRRData> = dnskeys.data().iter()
+            .filter_map(|data| data.as_any().downcast_ref::<DnsKeyRRData>())
+            .cloned()
+            .collect();
+
+        let mut supported = false;
+        let mut reason = String::new();
+
+        for ds in ds {
+            if DigestTypes::try_from(ds.digest_type()).map_or(true, |t| t.eq(&DigestTypes::Gost)) || !is_supported(ds.algorithm()) {
+                continue;
+            }
+            supported = true;
+
+            reason = format!("no DNSKEY matches DS key tag {}", ds.key_tag());
+
+            for key in keys.iter().filter(|key| key.key_tag() == ds.key_tag() && key.algorithm() == ds.algorithm()) {
+                if !ds_matches_dnskey(apex, key, ds) {
+                    reason = format!("DS digest mismatch for key tag {}", ds.key_tag());
+                    continue;
+                }
+
+                let result = self.validate_rr_set(apex, class, dnskeys, rrsigs, std::slice::from_ref(key));
+                if result.is_secure() {
+                    return result;
+                }
+
+                reason = result.reason;
+            }
+        }
+
+        match supported {
+            true => ValidationResult::new(ValidationStatus::Bogus, &reason),
+            false => ValidationResult::new(ValidationStatus::Indeterminate, "no DS uses a supported algorithm and digest type")
+        }
+    }
+}
+
+fn is_supported(algorithm: u8) -> bool {
+    matches!(DnsSecAlgorithms::try_from(algorithm), Ok(DnsSecAlgorithms::EcdsaP256Sha256) | Ok(DnsSecAlgorithms::Ed25519))
+}
+
+fn verify(dnskey: &DnsKeyRRData, data: &[u8], signature: &[u8]) -> bool {
+    match DnsSecAlgorithms::try_from(dnskey.algorithm()) {
+        Ok(DnsSecAlgorithms::EcdsaP256Sha256) => p256::verify(dnskey.public_key(), data, signature),
+        Ok(DnsSecAlgorithms::Ed25519) => ed25519::verify(dnskey.public_key(), data, signature),
+        _ => false
+    }
+}
+
+#[test]
+fn test() {
+    use std::net::Ipv4Addr;
+    use crate::dnssec::digest::ds_from_dnskey;
+    use crate::dnssec::signing_key::SigningKey;
+    use crate::dnssec::zone_signer::ZoneSigner;
+    use crate::messages::inter::rr_types::RRTypes;
+    use crate::rr_data::dnskey_rr_data::{DNSKEY_SECURE_ENTRY_POINT, DNSKEY_ZONE_KEY};
+    use crate::rr_data::in_a_rr_data::InARRData;
+    use crate::rr_data::inter::rr_data::RRData;
+    use crate::rr_data::soa_rr_data::SoaRRData;
+    use crate::zone::inter::zone_types::ZoneTypes;
+    use crate::zone::zone::Zone;
+
+    let mut zone = Zone::new(ZoneTypes::Master, RRClasses::In);
+    zone.add_record("", RRTypes::Soa, 3600, SoaRRData::new("ns1.find9.net", "admin.find9.net", 1, 3600, 600, 604800, 300).upcast());
+    zone.add_record("www", RRTypes::A, 300, InARRData::new(Ipv4Addr::new(127, 0, 0, 1)).upcast());
+
+    let ksk = SigningKey::new(DnsSecAlgorithms::Ed25519, DNSKEY_ZONE_KEY | DNSKEY_SECURE_ENTRY_POINT, &[0x11; 32]).unwrap();
+    let zsk = SigningKey::new(DnsSecAlgorithms::EcdsaP256Sha256, DNSKEY_ZONE_KEY, &[0x22; 32]).unwrap();
+
+    let mut signer = ZoneSigner::new(vec![ksk.clone(), zsk]);
+    signer.set_validity(1700000000, 1800000000);
+    signer.sign_zone(&mut zone, "find9.net").unwrap();
+
+    let rrsigs = |name: &str| -> Vec<RRSigRRData> {
+        zone.rr_set(name, &RRTypes::RRSig).unwrap().data().iter()
+            .filter_map(|d| d.as_any().downcast_ref::<RRSigRRData>())
+            .cloned()
+            .collect()
+    };
+
+    let dnskey_set = zone.rr_set("", &RRTypes::DnsKey).unwrap();
+    let dnskeys: Vec<DnsKeyRRData> = dnskey_set.data().iter()
+        .filter_map(|d| d.as_any().downcast_ref::<DnsKeyRRData>())
+        .cloned()
+        .collect();
+
+    let validator = Validator::new(1750000000);
+    let www = zone.rr_set("www", &RRTypes::A).unwrap();
+
+    let result = validator.validate_rr_set("www.find9.net", RRClasses::In, www, &rrsigs("www"), &dnskeys);
+    assert_eq!(result.status(), ValidationStatus::Secure);
+    assert_eq!(result.ttl(), Some(300));
+
+    let mut tampered = www.clone();
+    tampered.add_data(300, InARRData::new(Ipv4Addr::new(127, 0, 0, 2)).upcast());
+    assert_eq!(validator.validate_rr_set("www.find9.net", RRClasses::In, &tampered, &rrsigs("www"), &dnskeys).status(), ValidationStatus::Bogus);
+
+    assert_eq!(Validator::new(1900000000).validate_rr_set("www.find9.net", RRClasses::In, www, &rrsigs("www"), &dnskeys).status(), ValidationStatus::Bogus);
+    assert_eq!(validator.validate_rr_set("www.find9.net", RRClasses::In, www, &rrsigs("www"), &[]).status(), ValidationStatus::Indeterminate);
+    assert_eq!(validator.validate_rr_set("www.find9.net", RRClasses::In, www, &[], &[]).status(), ValidationStatus::Indeterminate);
+
+    let ds = ds_from_dnskey("find9.net", ksk.dnskey(), DigestTypes::Sha256).unwrap();
+    assert!(validator.validate_dnskeys("find9.net", RRClasses::In, dnskey_set, &rrsigs(""), std::slice::from_ref(&ds)).is_secure());
+    assert_eq!(validator.validate_dnskeys("find9.net", RRClasses::In, dnskey_set, &rrsigs(""), &[]).status(), ValidationStatus::Indeterminate);
+
+    let mut bad = ds.clone();
+    bad.set_digest(&[0; 32]);
+    assert_eq!(validator.validate_dnskeys("find9.net", RRClasses::In, dnskey_set, &rrsigs(""), &[bad]).status(), ValidationStatus::Bogus);
+}
@@ -0,0 +1,227 @@
+use std::fmt;
+use std::fmt::Formatter;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::dnssec::canonical::{rr_set_signing_data, signature_labels};
+use crate::dnssec::signing_key::SigningKey;
+use crate::messages::inter::rr_classes::RRClasses;
+use crate::messages::inter::rr_types::RRTypes;
+use crate::rr_data::inter::rr_data::RRData;
+use crate::rr_data::rrsig_rr_data::RRSigRRData;
+use crate::utils::fqdn_utils::to_fqdn;
+use crate::zone::rr_set::RRSet;
+use crate::zone::zone::Zone;
+
+const DEFAULT_INCEPTION_OFFSET: u32 = 3600;
+const DEFAULT_VALIDITY: u32 = 30 * 86400;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ZoneSignerError {
+    _type: ErrorKind,
+    message: String
+}
+
+impl fmt::Display for ZoneSignerError {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self._type, self.message)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ErrorKind {
+    UnsupportedAlgorithm,
+    InvalidKey,
+    NoKeys,
+    NoSoa,
+    Format
+}
+
+impl ZoneSignerError {
+
+    pub fn new(_type: ErrorKind, message: &str) -> Self {
+        Self {
+            _type,
+            message: message.to_string()
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ZoneSigner {
+    keys: Vec<SigningKey>,
+    inception: u32,
+    expiration: u32
+}
+
+impl Default for ZoneSigner {
+
+    fn default() -> Self {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs() as u32).unwrap_or(0);
+
+        Self {
+            keys: Vec::new(),
+            inception: now.wrapping_sub(DEFAULT_INCEPTION_OFFSET),
+            expiration: now.wrapping_add(DEFAULT_VALIDITY)
+        }
+    }
+}
+
+impl ZoneSigner {
+
+    pub fn new(keys: Vec<SigningKey>) -> Self {
+        Self {
+            keys,
+            ..Default::default()
+        }
+    }
+
+    pub fn add_key(&mut self, key: SigningKey) {
+        self.keys.push(key);
+    }
+
+    pub fn keys(&self) -> &Vec<SigningKey> {
+        &self.keys
+    }
+
+    pub fn set_inception(&mut self, inception: u32) {
+        self.inception = inception;
+    }
+
+    pub fn inception(&self) -> u32 {
+        self.inception
+    }
+
+    pub fn set_expiration(&mut self, expiration: u32) {
+        self.expiration = expiration;
+    }
+
+    pub fn expiration(&self) -> u32 {
+        self.expiration
+    }
+
+    pub fn set_validity(&mut self, inception: u32, expiration: u32) {
+        self.inception = inception;
+        self.expiration = expiration;
+    }
+
+    pub fn sign_zone(&self, zone: &mut Zone, apex: &str) -> Result<(), ZoneSignerError> {
+        if self.keys.is_empty() {
+            return Err(ZoneSignerError::new(ErrorKind::NoKeys, "no signing keys were provided"));
+        }
+
+        let dnskey_ttl = zone.rr_set("", &RRTypes::Soa)
+            .ok_or_else(|| ZoneSignerError::new(ErrorKind::NoSoa, &format!("zone {} has no SOA", apex)))?.ttl();
+
+        for key in &self.keys {
+            let dnskey = key.dnskey().clone().upcast();
+            if !zone.rr_set("", &RRTypes::DnsKey).map_or(false, |set| set.data().iter().any(|d| d.eq(&dnskey))) {
+                zone.add_record("", RRTypes::DnsKey, dnskey_ttl, dnskey);
+            }
+        }
+
+        let signed: Vec<String> = zone.all_rr_sets_recursive()
+            .filter(|(_, sets)| sets.iter().any(|s| s.rtype().eq(&RRTypes::RRSig)))
+            .map(|(name, _)| name)
+            .collect();
+
+        for name in signed {
+            zone.remove_rr_set(&name, &RRTypes::RRSig);
+        }
+
+        let mut signatures = Vec::new();
+
+        for (name, sets) in zone.all_rr_sets_recursive() {
+            let at_cut = match zone.delegation_point(&name) {
+                Some((cut, _)) if cut.eq(&name) => true,
+                Some(_) => continue,
+                None => false
+            };
+
+            let fqdn = to_fqdn(apex, &name);
+
+            for set in sets {
+                if at_cut && !set.rtype().eq(&RRTypes::Ds) && !set.rtype().eq(&RRTypes::NSec) {
+                    continue;
+                }
+
+                for key in self.keys_for(&set.rtype()) {
+                    signatures.push((name.clone(), set.ttl(), self.sign_rr_set(key, apex, &fqdn, zone.class(), set)?));
+                }
+            }
+        }
+
+        for (name, ttl, rrsig) in signatures {
+            zone.add_record(&name, RRTypes::RRSig, ttl, rrsig.upcast());
+        }
+
+        Ok(())
+    }
+
+    pub fn sign_rr_set(&self, key: &SigningKey, apex: &str, fqdn: &str, class: RRClasses, set: &RRSet) -> Result<RRSigRRData, ZoneSignerError> {
+        let mut rrsig = RRSigRRData::new(set.rtype(), key.algorithm().code(), signature_labels(fqdn), set.ttl(),
+            self.expiration, self.inception, key.key_tag(), &apex.to_lowercase(), &[]);
+
+        let data = rr_set_signing_data(fqdn, class, set, &rrsig)
+            .map_err(|e| ZoneSignerError::new(ErrorKind::Format, &e.to_string()))?;
+        rrsig.set_signature(&key.sign(&data)?);
+
+        Ok(rrsig)
+    }
+
+    fn keys_for(&self, rtype: &RRTypes) -> Vec<&SigningKey> {
+        let (ksks, zsks): (Vec<&SigningKey>, Vec<&SigningKey>) = self.keys.iter().partition(|k| k.is_ksk());
+
+        match rtype {
+            RRTypes::DnsKey if !ksks.is_empty() => ksks,
+            _ if !zsks.is_empty() => zsks,
+            _ => ksks
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use std::net::Ipv4Addr;
+    use crate::dnssec::inter::dnssec_algorithms::DnsSecAlgorithms;
+    use crate::rr_data::dnskey_rr_data::{DNSKEY_SECURE_ENTRY_POINT, DNSKEY_ZONE_KEY};
+    use crate::rr_data::in_a_rr_data::InARRData;
+    use crate::rr_data::ns_rr_data::NsRRData;
+    use crate::rr_data::soa_rr_data::SoaRRData;
+    use crate::utils::crypto::{ed25519, p256};
+    use crate::zone::inter::zone_types::ZoneTypes;
+
+    let mut zone = Zone::new(ZoneTypes::Master, RRClasses::In);
+    zone.add_record("", RRTypes::Soa, 3600, SoaRRData::new("ns1.find9.net", "admin.find9.net", 1, 3600, 600, 604800, 300).upcast());
+    zone.add_record("www", RRTypes::A, 300, InARRData::new(Ipv4Addr::new(127, 0, 0, 1)).upcast());
+    zone.add_record("*.wild", RRTypes::A, 300, InARRData::new(Ipv4Addr::new(127, 0, 0, 2)).upcast());
+    zone.add_record("sub", RRTypes::Ns, 300, NsRRData::new("ns.sub.find9.net").upcast());
+    zone.add_record("ns.sub", RRTypes::A, 300, InARRData::new(Ipv4Addr::new(127, 0, 0, 3)).upcast());
+
+    let ksk = SigningKey::new(DnsSecAlgorithms::Ed25519, DNSKEY_ZONE_KEY | DNSKEY_SECURE_ENTRY_POINT, &[0x11; 32]).unwrap();
+    let zsk = SigningKey::new(DnsSecAlgorithms::EcdsaP256Sha256, DNSKEY_ZONE_KEY, &[0x22; 32]).unwrap();
+    assert!(SigningKey::new(DnsSecAlgorithms::RsaSha256, DNSKEY_ZONE_KEY, &[0x22; 32]).is_err());
+
+    let mut signer = ZoneSigner::new(vec![ksk.clone(), zsk.clone()]);
+    signer.set_validity(1700000000, 1800000000);
+    signer.sign_zone(&mut zone, "find9.net").unwrap();
+
+    assert_eq!(zone.rr_set("", &RRTypes::DnsKey).unwrap().data().len(), 2);
+    assert!(zone.rr_set("sub", &RRTypes::RRSig).is_none());
+    assert!(zone.rr_set("ns.sub", &RRTypes::RRSig).is_none());
+
+    let rrsig = zone.rr_set("", &RRTypes::RRSig).unwrap().data().iter()
+        .filter_map(|d| d.as_any().downcast_ref::<RRSigRRData>())
+        .find(|d| d.type_covered() == Some(&RRTypes::DnsKey))
+        .unwrap();
+    assert_eq!(rrsig.key_tag(), ksk.key_tag());
+    let data = rr_set_signing_data("find9.net", RRClasses::In, zone.rr_set("", &RRTypes::DnsKey).unwrap(), rrsig).unwrap();
+    assert!(ed25519::verify(ksk.dnskey().public_key(), &data, rrsig.signature()));
+
+    let rrsig = zone.rr_set("*.wild", &RRTypes::RRSig).unwrap().data()[0].as_any().downcast_ref::<RRSigRRData>().unwrap().clone();
+    assert_eq!(rrsig.labels(), 3);
+    assert_eq!(rrsig.key_tag(), zsk.key_tag());
+    assert_eq!(rrsig.inception(), 1700000000);
+    let data = rr_set_signing_data("*.wild.find9.net", RRClasses::In, zone.rr_set("*.wild", &RRTypes::A).unwrap(), &rrsig).unwrap();
+    assert!(p256::verify(zsk.dnskey().public_key(), &data, rrsig.signature()));
+    assert_eq!(rr_set_signing_data("x.wild.find9.net", RRClasses::In, zone.rr_set("*.wild", &RRTypes::A).unwrap(), &rrsig).unwrap(), data);
+}
@@ -1,47 +1,152 @@
-use std::io;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Formatter;
+use std::path::PathBuf;
+use crate::journal::journal_reader::JournalReader;
 use crate::journal::txn::Txn;
+use crate::utils::serial_utils::SerialUtils;
 
-#[derive(Debug, Clone)]
+#[derive(Default, Debug, Clone)]
 pub struct Journal {
-    //txns: IndexMap<u32, Txn>
+    txns: Vec<Txn>,
+    index: HashMap<u32, usize>
+}
+
+#[derive(Debug, Clone)]
+pub struct JournalError {
+    _type: ErrorKind,
+    message: String
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ErrorKind {
+    ReadErr,
+    SerialMismatch
+}
+
+impl JournalError {
+
+    pub fn new(_type: ErrorKind, message: &str) -> Self {
+        Self {
+            _type,
+            message: message.to_string()
+        }
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self._type
+    }
+}
+
+impl fmt::Display for JournalError {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self._type, self.message)
+    }
 }
 
 impl Journal {
 
     pub fn new() -> Self {
         Self {
-            //txns: IndexMap::new()
+            ..Default::default()
         }
     }
 
-    pub fn open(file_path: &str) -> io::Result<Self> {
-        /*
-        let mut txns = IndexMap::new();
+    pub fn open<P: Into<PathBuf>>(file_path: P) -> Result<Self, JournalError> {
+        let mut journal = Self::new();
 
-        let mut reader = JournalReader::open(file_path)?;
+        let mut reader = JournalReader::open(file_path)
+            .map_err(|e| JournalError::new(ErrorKind::ReadErr, &e.to_string()))?;
         for txn in reader.txns() {
-            txns.insert(txn.serial_0(), txn);
+            journal.add_txn(txn.map_err(|e| JournalError::new(ErrorKind::ReadErr, &e.to_string()))?)?;
         }
 
-        Ok(Self {
-            txns
-        })
-        */
-        todo!()
+        Ok(journal)
+    }
+
+    pub fn add_txn(&mut self, txn: Txn) -> Result<(), JournalError> {
+        if let Some(end_serial) = self.end_serial() {
+            if end_serial != txn.serial_0() {
+                return Err(JournalError::new(ErrorKind::SerialMismatch,
+                    &format!("transaction starts at serial {} but journal ends at serial {}", txn.serial_0(), end_serial)));
+            }
+        }
+
+        if !txn.serial_1().serial_gt(txn.serial_0()) {
+            return Err(JournalError::new(ErrorKind::SerialMismatch,
+                &format!("transaction serial {} does not advance past {}", txn.serial_1(), txn.serial_0())));
+        }
+
+        self.index.insert(txn.serial_0(), self.txns.len());
+        self.txns.push(txn);
+        Ok(())
+    }
+
+    pub fn begin_serial(&self) -> Option<u32> {
+        self.txns.first().map(|t| t.serial_0())
+    }
+
+    pub fn end_serial(&self) -> Option<u32> {
+        self.txns.last().map(|t| t.serial_1())
     }
-/*
-    pub fn txns(&self) -> &IndexMap<u32, Txn> {
+
+    pub fn txns(&self) -> &Vec<Txn> {
         self.txns.as_ref()
     }
 
-    pub fn txn(&self, index: u32) -> Option<&Txn> {
-        self.txns.get(&index)
+    pub fn txn(&self, serial: u32) -> Option<&Txn> {
+        self.txns.get(*self.index.get(&serial)?)
+    }
+
+    pub fn txns_from(&self, start: u32) -> Option<&[Txn]> {
+        if self.end_serial()? == start {
+            return Some(&self.txns[self.txns.len()..]);
+        }
+
+        Some(&self.txns[*self.index.get(&start)?..])
+    }
+
+    pub fn txns_range(&self, start: u32, end: u32) -> Option<&[Txn]> {
+        let first = match self.end_serial()? == start {
+            true => self.txns.len(),
+            false => *self.index.get(&start)?
+        };
+
+        let last = match self.end_serial()? == end {
+            true => self.txns.len(),
+            false => *self.index.get(&end)?
+        };
+
+        self.txns.get(first..last)
+    }
+
+    pub fn compact(&mut self, max_txns: usize) -> usize {
+        if self.txns.len() <= max_txns {
+            return 0;
+        }
+
+        let removed = self.txns.len() - max_txns;
+        self.drain(removed);
+        removed
     }
 
-    pub fn txns_from(&self, start: u32) -> impl Iterator<Item = (&u32, &Txn)> {
-        self.txns.range(start..)
+    pub fn compact_before(&mut self, serial: u32) -> usize {
+        let removed = self.txns.iter()
+            .take_while(|t| t.serial_0().serial_lt(serial))
+            .count();
+        self.drain(removed);
+        removed
     }
-*/
+
+    pub fn len(&self) -> usize {
+        self.txns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.txns.is_empty()
+    }
+
     pub fn as_ref(&self) -> &Self {
         self
     }
@@ -49,4 +154,44 @@ impl Journal {
     pub fn as_mut(&mut self) -> &mut Self {
         self
     }
+
+    fn drain(&mut self, count: usize) {
+        if count == 0 {
+            return;
+        }
+
+        self.txns.drain(..count);
+        self.index = self.txns.iter()
+            .enumerate()
+            .map(|(i, t)| (t.serial_0(), i))
+            .collect();
+    }
+}
+
+#[test]
+fn test() {
+    let mut journal = Journal::new();
+    journal.add_txn(Txn::new(u32::MAX - 1, u32::MAX)).unwrap();
+    journal.add_txn(Txn::new(u32::MAX, 0)).unwrap();
+    journal.add_txn(Txn::new(0, 5)).unwrap();
+    assert_eq!(journal.add_txn(Txn::new(6, 7)).unwrap_err().kind(), &ErrorKind::SerialMismatch);
+    assert!(journal.add_txn(Txn::new(5, 4)).is_err());
+
+    assert_eq!(journal.begin_serial(), Some(u32::MAX - 1));
+    assert_eq!(journal.end_serial(), Some(5));
+    assert_eq!(journal.txns_from(u32::MAX).unwrap().len(), 2);
+    assert_eq!(journal.txns_from(5).unwrap().len(), 0);
+    assert!(journal.txns_from(3).is_none());
+    assert_eq!(journal.txns_range(u32::MAX - 1, 0).unwrap().len(), 2);
+    assert_eq!(journal.txns_range(0, 5).unwrap().len(), 1);
+    assert_eq!(journal.txns_range(5, 5).unwrap().len(), 0);
+    assert!(journal.txns_range(0, u32::MAX).is_none());
+    assert_eq!(journal.txn(u32::MAX).unwrap().serial_1(), 0);
+
+    assert_eq!(journal.compact_before(0), 2);
+    assert_eq!(journal.begin_serial(), Some(0));
+    assert_eq!(journal.txn(0).unwrap().serial_1(), 5);
+    assert!(journal.txn(u32::MAX).is_none());
+    assert_eq!(journal.compact(0), 1);
+    assert!(journal.is_empty());
 }
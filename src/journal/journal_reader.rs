@@ -10,7 +10,11 @@ use crate::messages::inter::rr_types::RRTypes;
 use crate::rr_data::inter::rr_data::RRData;
 use crate::utils::fqdn_utils::unpack_fqdn;
 
-#[derive(Default)]
+pub const JOURNAL_HEADER_SIZE: usize = 64;
+pub const JOURNAL_MAGIC_V9: &[u8; 13] = b";BIND LOG V9\n";
+pub const JOURNAL_MAGIC_V92: &[u8; 15] = b";BIND LOG V9.2\n";
+
+#[derive(Default, Debug, Clone)]
 pub struct JournalHeader {
     begin_serial: u32,
     begin_offset: u32,
@@ -23,18 +27,73 @@ pub struct JournalHeader {
 
 impl JournalHeader {
 
+    pub fn new(begin_serial: u32, begin_offset: u32, end_serial: u32, end_offset: u32, index_size: u32, source_serial: u32, flags: u8) -> Self {
+        Self {
+            begin_serial,
+            begin_offset,
+            end_serial,
+            end_offset,
+            index_size,
+            source_serial,
+            flags
+        }
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> Self {
+        Self {
+            begin_serial: u32::from_be_bytes([buf[16], buf[17], buf[18], buf[19]]),
+            begin_offset: u32::from_be_bytes([buf[20], buf[21], buf[22], buf[23]]),
+            end_serial: u32::from_be_bytes([buf[24], buf[25], buf[26], buf[27]]),
+            end_offset: u32::from_be_bytes([buf[28], buf[29], buf[30], buf[31]]),
+            index_size: u32::from_be_bytes([buf[32], buf[33], buf[34], buf[35]]),
+            source_serial: u32::from_be_bytes([buf[36], buf[37], buf[38], buf[39]]),
+            flags: buf[40]
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = vec![0u8; JOURNAL_HEADER_SIZE];
+
+        buf[0..JOURNAL_MAGIC_V92.len()].copy_from_slice(JOURNAL_MAGIC_V92);
+        buf[16..20].copy_from_slice(&self.begin_serial.to_be_bytes());
+        buf[20..24].copy_from_slice(&self.begin_offset.to_be_bytes());
+        buf[24..28].copy_from_slice(&self.end_serial.to_be_bytes());
+        buf[28..32].copy_from_slice(&self.end_offset.to_be_bytes());
+        buf[32..36].copy_from_slice(&self.index_size.to_be_bytes());
+        buf[36..40].copy_from_slice(&self.source_serial.to_be_bytes());
+        buf[40] = self.flags;
+
+        buf
+    }
+
+    pub fn set_begin_serial(&mut self, begin_serial: u32) {
+        self.begin_serial = begin_serial;
+    }
+
     pub fn begin_serial(&self) -> u32 {
         self.begin_serial
     }
 
+    pub fn set_begin_offset(&mut self, begin_offset: u32) {
+        self.begin_offset = begin_offset;
+    }
+
     pub fn begin_offset(&self) -> u32 {
         self.begin_offset
     }
 
+    pub fn set_end_serial(&mut self, end_serial: u32) {
+        self.end_serial = end_serial;
+    }
+
     pub fn end_serial(&self) -> u32 {
         self.end_serial
     }
 
+    pub fn set_end_offset(&mut self, end_offset: u32) {
+        self.end_offset = end_offset;
+    }
+
     pub fn end_offset(&self) -> u32 {
         self.end_offset
     }
@@ -77,6 +136,7 @@ pub enum ErrorKind {
     ReadErr,
     ClassNotFound,
     TypeNotFound,
+    SerialMismatch,
     UnexpectedEof
 }
 
@@ -110,30 +170,19 @@ impl JournalReader {
     }
 
     fn read_headers(&mut self) -> Result<&JournalHeader, JournalReaderError> {
-        let mut buf = vec![0u8; 64];
+        let mut buf = vec![0u8; JOURNAL_HEADER_SIZE];
         self.reader.read_exact(&mut buf)
             .map_err(|_| JournalReaderError::new(ErrorKind::ReadErr, &format!("unable to read next {} bytes", buf.len())))?;
 
         // Magic (first 16 bytes): ";BIND LOG V9\n" or ";BIND LOG V9.2\n"
         let magic = true;//&buf[0..16];
-        let v9 = b";BIND LOG V9\n";
-        let v92 = b";BIND LOG V9.2\n";
-        //if !(magic.starts_with(v9) || magic.starts_with(v92)) {
+        //if !(magic.starts_with(JOURNAL_MAGIC_V9) || magic.starts_with(JOURNAL_MAGIC_V92)) {
         //    //return Err(io::Error::new(io::ErrorKind::InvalidData, "bad .jnl magic"));
         //}
 
-        //let is_v92 = magic.starts_with(v92);
-
-        let begin_serial = u32::from_be_bytes([buf[16], buf[17], buf[18], buf[19]]);
-        let begin_offset = u32::from_be_bytes([buf[20], buf[21], buf[22], buf[23]]);
-        let end_serial = u32::from_be_bytes([buf[24], buf[25], buf[26], buf[27]]);
-        let end_offset = u32::from_be_bytes([buf[28], buf[29], buf[30], buf[31]]);
-        let index_size = u32::from_be_bytes([buf[32], buf[33], buf[34], buf[35]]);
-        let source_serial = u32::from_be_bytes([buf[36], buf[37], buf[38], buf[39]]);
-        let flags = buf[40];
-
-
+        //let is_v92 = magic.starts_with(JOURNAL_MAGIC_V92);
 
+        let headers = JournalHeader::from_bytes(&buf);
 
         // ===== 2) OPTIONAL INDEX =====
         // Each index entry is 8 bytes: [serial(4) | offset(4)]
@@ -141,18 +190,10 @@ impl JournalReader {
         //    .map_err(|e| JournalReaderError::new(ErrorKind::ReadErr, "unable to seek to position"))?;
 
         // ===== 3) POSITION TO FIRST TRANSACTION =====
-        self.reader.seek(SeekFrom::Start(begin_offset as u64))
+        self.reader.seek(SeekFrom::Start(headers.begin_offset as u64))
             .map_err(|_| JournalReaderError::new(ErrorKind::ReadErr, "unable to seek to position"))?;
 
-        self.headers = Some(JournalHeader {
-            begin_serial,
-            begin_offset,
-            end_serial,
-            end_offset,
-            index_size,
-            source_serial,
-            flags
-        });
+        self.headers = Some(headers);
 
         self.headers.as_ref().ok_or(JournalReaderError::new(ErrorKind::ReadErr, "header not found"))
     }
@@ -221,8 +262,6 @@ impl JournalReader {
                 if seen_soa == 2 {
                     phase = TxnOpCodes::Add;
                 }
-
-                continue;
             }
 
             let class = RRClasses::try_from(u16::from_be_bytes([buf[off+2], buf[off+3]]))
@@ -0,0 +1,225 @@
+use std::fmt;
+use std::fmt::Formatter;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use crate::journal::inter::txn_op_codes::TxnOpCodes;
+use crate::journal::journal_reader::{JournalHeader, JOURNAL_HEADER_SIZE};
+use crate::journal::txn::Txn;
+use crate::messages::inter::rr_types::RRTypes;
+use crate::messages::record::Record;
+use crate::utils::fqdn_utils::pack_fqdn;
+
+pub struct JournalWriter {
+    file: File,
+    headers: JournalHeader
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct JournalWriterError {
+    _type: ErrorKind,
+    message: String
+}
+
+impl fmt::Display for JournalWriterError {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self._type, self.message)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ErrorKind {
+    PathNotFound,
+    ReadErr,
+    WriteErr,
+    Format,
+    SerialMismatch
+}
+
+impl JournalWriterError {
+
+    pub fn new(_type: ErrorKind, message: &str) -> Self {
+        Self {
+            _type,
+            message: message.to_string()
+        }
+    }
+}
+
+impl JournalWriter {
+
+    pub fn create<P: Into<PathBuf>>(file_path: P) -> Result<Self, JournalWriterError> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(file_path.into())
+            .map_err(|e| JournalWriterError::new(ErrorKind::PathNotFound, &e.to_string()))?;
+
+        let offset = JOURNAL_HEADER_SIZE as u32;
+
+        let mut writer = Self {
+            file,
+            headers: JournalHeader::new(0, offset, 0, offset, 0, 0, 0)
+        };
+
+        writer.write_headers()?;
+        Ok(writer)
+    }
+
+    pub fn open<P: Into<PathBuf>>(file_path: P) -> Result<Self, JournalWriterError> {
+        let path = file_path.into();
+
+        if !path.exists() {
+            return Self::create(path);
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+            .map_err(|e| JournalWriterError::new(ErrorKind::PathNotFound, &e.to_string()))?;
+
+        let mut buf = vec![0u8; JOURNAL_HEADER_SIZE];
+        file.read_exact(&mut buf)
+            .map_err(|_| JournalWriterError::new(ErrorKind::ReadErr, &format!("unable to read next {} bytes", buf.len())))?;
+
+        Ok(Self {
+            file,
+            headers: JournalHeader::from_bytes(&buf)
+        })
+    }
+
+    pub fn headers(&self) -> &JournalHeader {
+        &self.headers
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.headers.begin_offset() == self.headers.end_offset()
+    }
+
+    pub fn write_txn(&mut self, txn: &Txn) -> Result<(), JournalWriterError> {
+        if !self.is_empty() && self.headers.end_serial() != txn.serial_0() {
+            return Err(JournalWriterError::new(ErrorKind::SerialMismatch,
+                &format!("transaction starts at serial {} but journal ends at serial {}", txn.serial_0(), self.headers.end_serial())));
+        }
+
+        let deletes = txn.records(TxnOpCodes::Delete);
+        let adds = txn.records(TxnOpCodes::Add);
+
+        if !deletes.first().map_or(false, |r| r.rtype().eq(&RRTypes::Soa)) ||
+                !adds.first().map_or(false, |r| r.rtype().eq(&RRTypes::Soa)) {
+            return Err(JournalWriterError::new(ErrorKind::Format, "transaction must begin each phase with an SOA record"));
+        }
+
+        let mut body = Vec::new();
+        for record in deletes.iter().chain(adds.iter()) {
+            let buf = Self::encode_record(record)?;
+            body.extend_from_slice(&(buf.len() as u32).to_be_bytes());
+            body.extend_from_slice(&buf);
+        }
+
+        let mut buf = Vec::with_capacity(16+body.len());
+        buf.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        buf.extend_from_slice(&((deletes.len()+adds.len()) as u32).to_be_bytes());
+        buf.extend_from_slice(&txn.serial_0().to_be_bytes());
+        buf.extend_from_slice(&txn.serial_1().to_be_bytes());
+        buf.extend_from_slice(&body);
+
+        self.file.seek(SeekFrom::Start(self.headers.end_offset() as u64))
+            .map_err(|_| JournalWriterError::new(ErrorKind::WriteErr, "unable to seek to position"))?;
+        self.file.write_all(&buf)
+            .map_err(|e| JournalWriterError::new(ErrorKind::WriteErr, &e.to_string()))?;
+
+        if self.is_empty() {
+            self.headers.set_begin_serial(txn.serial_0());
+        }
+
+        self.headers.set_end_serial(txn.serial_1());
+        self.headers.set_end_offset(self.headers.end_offset() + buf.len() as u32);
+
+        self.write_headers()
+    }
+
+    pub fn write_txns(&mut self, txns: &[Txn]) -> Result<(), JournalWriterError> {
+        for txn in txns {
+            self.write_txn(txn)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_headers(&mut self) -> Result<(), JournalWriterError> {
+        self.file.seek(SeekFrom::Start(0))
+            .map_err(|_| JournalWriterError::new(ErrorKind::WriteErr, "unable to seek to position"))?;
+
+        let mut writer = BufWriter::new(&self.file);
+        writer.write_all(&self.headers.to_bytes())
+            .map_err(|e| JournalWriterError::new(ErrorKind::WriteErr, &e.to_string()))?;
+        writer.flush()
+            .map_err(|e| JournalWriterError::new(ErrorKind::WriteErr, &e.to_string()))
+    }
+
+    fn encode_record(record: &Record) -> Result<Vec<u8>, JournalWriterError> {
+        let mut buf = pack_fqdn(record.fqdn());
+
+        buf.extend_from_slice(&record.rtype().code().to_be_bytes());
+        buf.extend_from_slice(&record.class().code().to_be_bytes());
+        buf.extend_from_slice(&record.ttl().to_be_bytes());
+
+        match record.data() {
+            Some(data) => {
+                let data = data.to_bytes()
+                    .map_err(|e| JournalWriterError::new(ErrorKind::Format, &e.to_string()))?;
+                buf.extend_from_slice(&(data.len() as u16).to_be_bytes());
+                buf.extend_from_slice(&data);
+            }
+            None => buf.extend_from_slice(&0u16.to_be_bytes())
+        }
+
+        Ok(buf)
+    }
+}
+
+#[test]
+fn test() {
+    use std::net::Ipv4Addr;
+    use crate::journal::journal_reader::JournalReader;
+    use crate::messages::inter::rr_classes::RRClasses;
+    use crate::rr_data::in_a_rr_data::InARRData;
+    use crate::rr_data::inter::rr_data::RRData;
+    use crate::rr_data::soa_rr_data::SoaRRData;
+
+    let soa = |serial| Some(SoaRRData::new("ns1.find9.net", "admin.find9.net", serial, 3600, 600, 604800, 300).upcast());
+
+    let mut txn_0 = Txn::new(1, 2);
+    txn_0.add_record(TxnOpCodes::Delete, "find9.net", RRClasses::In, RRTypes::Soa, 3600, soa(1));
+    txn_0.add_record(TxnOpCodes::Add, "find9.net", RRClasses::In, RRTypes::Soa, 3600, soa(2));
+    txn_0.add_record(TxnOpCodes::Add, "www.find9.net", RRClasses::In, RRTypes::A, 300, Some(InARRData::new(Ipv4Addr::new(127, 0, 0, 1)).upcast()));
+
+    let mut txn_1 = Txn::new(2, 3);
+    txn_1.add_record(TxnOpCodes::Delete, "find9.net", RRClasses::In, RRTypes::Soa, 3600, soa(2));
+    txn_1.add_record(TxnOpCodes::Delete, "www.find9.net", RRClasses::In, RRTypes::A, 300, Some(InARRData::new(Ipv4Addr::new(127, 0, 0, 1)).upcast()));
+    txn_1.add_record(TxnOpCodes::Add, "find9.net", RRClasses::In, RRTypes::Soa, 3600, soa(3));
+
+    let path = std::env::temp_dir().join("rlibdns_journal_writer_test.jnl");
+    let mut writer = JournalWriter::create(&path).unwrap();
+    writer.write_txn(&txn_0).unwrap();
+    assert!(writer.write_txn(&txn_0).is_err());
+
+    let mut writer = JournalWriter::open(&path).unwrap();
+    writer.write_txn(&txn_1).unwrap();
+    assert_eq!(writer.headers().begin_serial(), 1);
+    assert_eq!(writer.headers().end_serial(), 3);
+
+    let mut reader = JournalReader::open(&path).unwrap();
+    let txns = reader.txns().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(txns.len(), 2);
+    assert_eq!(txns[0].serial_0(), 1);
+    assert_eq!(txns[0].records(TxnOpCodes::Add).len(), 2);
+    assert_eq!(txns[1].serial_1(), 3);
+    assert_eq!(txns[1].records(TxnOpCodes::Delete).len(), 2);
+    assert_eq!(txns[1].records(TxnOpCodes::Delete)[1].data(), txn_1.records(TxnOpCodes::Delete)[1].data());
+}
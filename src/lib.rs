@@ -4,6 +4,7 @@ pub mod utils;
 pub mod zone;
 pub mod keyring;
 pub mod journal;
+pub mod dnssec;
 
 #[cfg(test)]
 mod tests {
@@ -20,16 +21,9 @@ mod tests {
     //CLEAN UP OCTALS, HEX, BASE64, BASE32 parsing
     //CLEAN UP COORD UTILS
 
-    //ADD JNL AND ZONE WRITERS...
-
     //CLEAN UP JNL & ZONER READERS
 
 
-    //COMPLETE NSEC3 WIRING
-    //COMPLETE NSEC WIRING
-    //COMPLETE SSHFP WIRING
-
-
     //NEVER PANIC
 
     //DONT USE UNWRAPS - MODIFY ALL ENUMS DOING IT - THEN CHANGE io::Result for from_bytes on Message to our own Result
@@ -0,0 +1,215 @@
+use std::net::IpAddr;
+use crate::messages::edns::{Edns, EdnsOption};
+use crate::messages::inter::response_codes::ResponseCodes;
+use crate::messages::message::Message;
+use crate::utils::hash::siphash::siphash24;
+use crate::utils::serial_utils::SerialUtils;
+
+pub const COOKIE_VERSION: u8 = 1;
+pub const CLIENT_COOKIE_LEN: usize = 8;
+pub const SERVER_COOKIE_LEN: usize = 16;
+pub const MAX_COOKIE_AGE: u32 = 3600;
+pub const MAX_COOKIE_SKEW: u32 = 300;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CookieStatus {
+    Missing,
+    ClientOnly,
+    Valid,
+    Invalid
+}
+
+pub fn client_cookie(secret: &[u8; 16], server_ip: IpAddr) -> [u8; CLIENT_COOKIE_LEN] {
+    siphash24(secret, &ip_octets(server_ip))
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerCookies {
+    secret: [u8; 16],
+    previous_secret: Option<([u8; 16], u32)>,
+    rotation_window: u32
+}
+
+impl ServerCookies {
+
+    pub fn new(secret: [u8; 16]) -> Self {
+        Self {
+            secret,
+            previous_secret: None,
+            rotation_window: MAX_COOKIE_AGE
+        }
+    }
+
+    pub fn set_rotation_window(&mut self, rotation_window: u32) -> bool {
+        if 0u32.serial_add(rotation_window).is_none() {
+            return false;
+        }
+
+        self.rotation_window = rotation_window;
+        true
+    }
+
+    pub fn rotation_window(&self) -> u32 {
+        self.rotation_window
+    }
+
+    pub fn rotate(&mut self, secret: [u8; 16], now: u32) {
+        self.previous_secret = now.serial_add(self.rotation_window).map(|expires| (self.secret, expires));
+        self.secret = secret;
+    }
+
+    pub fn server_cookie(&self, client: &[u8; CLIENT_COOKIE_LEN], client_ip: IpAddr, now: u32) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(SERVER_COOKIE_LEN);
+        buf.push(COOKIE_VERSION);
+        buf.extend_from_slice(&[0; 3]);
+        buf.extend_from_slice(&now.to_be_bytes());
+        buf.extend_from_slice(&Self::hash(&self.secret, client, &buf, client_ip));
+        buf
+    }
+
+    pub fn verify(&self, client: &[u8; CLIENT_COOKIE_LEN], server: &[u8], client_ip: IpAddr, now: u32) -> bool {
+        if server.len() != SERVER_COOKIE_LEN || server[0] != COOKIE_VERSION {
+            return false;
+        }
+
+        let timestamp = u32::from_be_bytes([server[4], server[5], server[6], server[7]]);
+        if timestamp.serial_lt(now.wrapping_sub(MAX_COOKIE_AGE)) || timestamp.serial_gt(now.wrapping_add(MAX_COOKIE_SKEW)) {
+            return false;
+        }
+
+        let matches = |secret: &[u8; 16]| {
+            Self::hash(secret, client, &server[..8], client_ip).iter()
+                .zip(&server[8..])
+                .fold(0u8, |d, (a, b)| d | (a ^ b)) == 0
+        };
+
+        matches(&self.secret) || self.previous_secret.as_ref()
+            .is_some_and(|(secret, expires)| now.serial_lt(*expires) && matches(secret))
+    }
+
+    pub fn check(&self, query: &Message, now: u32) -> CookieStatus {
+        let (client, server) = match Self::cookie(query) {
+            Some(cookie) => cookie,
+            None => return CookieStatus::Missing
+        };
+
+        match (server, query.origin()) {
+            (None, _) => CookieStatus::ClientOnly,
+            (Some(server), Some(origin)) if self.verify(&client, &server, origin.ip(), now) => CookieStatus::Valid,
+            _ => CookieStatus::Invalid
+        }
+    }
+
+    pub fn set_cookie(&self, query: &Message, response: &mut Message, now: u32) -> bool {
+        let (client, origin) = match (Self::cookie(query), query.origin()) {
+            (Some((client, _)), Some(origin)) => (client, origin),
+            _ => return false
+        };
+
+        if response.edns().is_none() {
+            let edns = query.edns().map(|edns| Edns::new(edns.payload_size(), 0, 0, edns.do_bit(), 0, Vec::new()));
+            if let Some(edns) = edns {
+                response.set_edns(edns);
+            }
+        }
+
+        let server = self.server_cookie(&client, origin.ip(), now);
+
+        match response.edns_mut() {
+            Some(edns) => {
+                edns.options_mut().retain(|option| !matches!(option, EdnsOption::Cookie { .. }));
+                edns.add_option(EdnsOption::Cookie {
+                    client,
+                    server: Some(server)
+                });
+                true
+            }
+            None => false
+        }
+    }
+
+    pub fn bad_cookie(&self, query: &Message, now: u32) -> Message {
+        let mut response = Message::new(query.id());
+        response.set_qr(true);
+        response.set_op_code(query.op_code());
+        response.set_recursion_desired(query.is_recursion_desired());
+
+        for q in query.queries() {
+            response.add_query(q.clone());
+        }
+
+        self.set_cookie(query, &mut response, now);
+        response.set_response_code(ResponseCodes::BadCookie);
+
+        response
+    }
+
+    fn cookie(query: &Message) -> Option<([u8; CLIENT_COOKIE_LEN], Option<Vec<u8>>)> {
+        query.edns()?.options().iter().find_map(|option| match option {
+            EdnsOption::Cookie { client, server } => Some((*client, server.clone())),
+            _ => None
+        })
+    }
+
+    fn hash(secret: &[u8; 16], client: &[u8; CLIENT_COOKIE_LEN], header: &[u8], client_ip: IpAddr) -> [u8; 8] {
+        let mut buf = Vec::with_capacity(36);
+        buf.extend_from_slice(client);
+        buf.extend_from_slice(header);
+        buf.extend_from_slice(&ip_octets(client_ip));
+        siphash24(secret, &buf)
+    }
+}
+
+fn ip_octets(ip: IpAddr) -> Vec<u8> {
+    match ip {
+        IpAddr::V4(ip) => ip.octets().to_vec(),
+        IpAddr::V6(ip) => ip.octets().to_vec()
+    }
+}
+
+#[test]
+fn test() {
+    use std::net::{Ipv4Addr, SocketAddr};
+    use crate::messages::inter::rr_classes::RRClasses;
+    use crate::messages::inter::rr_types::RRTypes;
+    use crate::messages::rr_query::RRQuery;
+    use crate::utils::hex;
+
+    let secret = <[u8; 16]>::try_from(hex::decode("e5e973e5a6b2a43f48e7dc849e37bfcf").unwrap()).unwrap();
+    let client = [0x24, 0x64, 0xc4, 0xab, 0xcf, 0x10, 0xc9, 0x57];
+    let client_ip = IpAddr::V4(Ipv4Addr::new(198, 51, 100, 100));
+
+    let mut cookies = ServerCookies::new(secret);
+    let server = cookies.server_cookie(&client, client_ip, 1559731985);
+    assert_eq!(hex::encode(&server), "010000005cf79f111f8130c3eee29480");
+    assert!(cookies.verify(&client, &server, client_ip, 1559731985 + 60));
+    assert!(!cookies.verify(&client, &server, IpAddr::V4(Ipv4Addr::new(198, 51, 100, 101)), 1559731985));
+    assert!(!cookies.verify(&client, &server, client_ip, 1559731985 + MAX_COOKIE_AGE + 1));
+    assert!(!cookies.verify(&client, &server, client_ip, 1559731985 - MAX_COOKIE_SKEW - 1));
+
+    cookies.rotate([7; 16], 1559731985);
+    assert!(cookies.verify(&client, &server, client_ip, 1559731985 + 60));
+    assert!(cookies.set_rotation_window(30));
+    assert!(!cookies.set_rotation_window(u32::MAX));
+    cookies.rotate([8; 16], 1559731985);
+    assert!(!cookies.verify(&client, &server, client_ip, 1559731985 + 60));
+
+    let mut query = Message::new(0x55aa);
+    query.add_query(RRQuery::new("find9.net", RRTypes::A, RRClasses::In));
+    query.set_origin(SocketAddr::new(client_ip, 53000));
+    assert_eq!(cookies.check(&query, 1559731985), CookieStatus::Missing);
+
+    query.set_edns(Edns::new(1232, 0, 0, false, 0, vec![EdnsOption::Cookie { client, server: None }]));
+    assert_eq!(cookies.check(&query, 1559731985), CookieStatus::ClientOnly);
+
+    query.set_edns(Edns::new(1232, 0, 0, false, 0, vec![EdnsOption::Cookie { client, server: Some(server.clone()) }]));
+    assert_eq!(cookies.check(&query, 1559731985), CookieStatus::Invalid);
+
+    let response = Message::from_bytes(cookies.bad_cookie(&query, 1559731985).to_bytes(512)).unwrap();
+    assert_eq!(response.id(), 0x55aa);
+    assert_eq!(response.response_code(), ResponseCodes::BadCookie);
+
+    let mut query = query.clone();
+    query.set_edns(response.edns().unwrap().clone());
+    assert_eq!(cookies.check(&query, 1559731985 + 10), CookieStatus::Valid);
+}
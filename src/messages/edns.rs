@@ -1,9 +1,11 @@
+use std::borrow::Cow;
 use std::fmt;
 use std::fmt::Formatter;
-use std::net::{Ipv4Addr, Ipv6Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use crate::messages::wire::{FromWire, FromWireContext, ToWire, ToWireContext, WireError};
 use crate::rr_data::inter::opt_codes::OptCodes;
 use crate::rr_data::inter::rr_data::RRDataError;
+use crate::utils::fqdn_utils::{pack_fqdn, unpack_fqdn};
 use crate::utils::hex;
 
 #[derive(Debug, Clone)]
@@ -16,62 +18,279 @@ pub struct Edns {
     options: Vec<EdnsOption>
 }
 
-#[derive(Debug, Clone)]
-pub struct EdnsOption {
-    code: OptCodes,
-    data: Vec<u8>
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum EdnsOption {
+    Nsid(Vec<u8>),
+    Ecs {
+        address: IpAddr,
+        source_prefix: u8,
+        scope_prefix: u8
+    },
+    Expire(Option<u32>),
+    Cookie {
+        client: [u8; 8],
+        server: Option<Vec<u8>>
+    },
+    TcpKeepalive(Option<u16>),
+    Padding(u16),
+    Chain(String),
+    KeyTag(Vec<u16>),
+    Ede {
+        info_code: u16,
+        extra_text: String
+    },
+    Other(OptCodes, Vec<u8>)
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EdnsOptionParseError(pub String);
+
+impl fmt::Display for EdnsOptionParseError {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
 }
 
 impl EdnsOption {
 
     pub fn new(code: OptCodes, data: &[u8]) -> Self {
-        Self {
-            code,
-            data: data.to_vec()
+        Self::from_bytes(code, data).unwrap_or_else(|_| Self::Other(code, data.to_vec()))
+    }
+
+    pub fn ecs(address: IpAddr, source_prefix: u8, scope_prefix: u8) -> Self {
+        let (mut octets, max_prefix) = match address {
+            IpAddr::V4(address) => (address.octets().to_vec(), 32),
+            IpAddr::V6(address) => (address.octets().to_vec(), 128)
+        };
+
+        let source_prefix = source_prefix.min(max_prefix);
+        for (i, octet) in octets.iter_mut().enumerate() {
+            let bits = (source_prefix as usize).saturating_sub(i * 8).min(8);
+            *octet &= (0xFF00u16 >> bits) as u8;
+        }
+
+        let address = match address {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(octets).unwrap())),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(octets).unwrap()))
+        };
+
+        Self::Ecs {
+            address,
+            source_prefix,
+            scope_prefix
         }
     }
 
-    pub fn set_code(&mut self, code: OptCodes) {
-        self.code = code;
+    pub fn from_bytes(code: OptCodes, buf: &[u8]) -> Result<Self, EdnsOptionParseError> {
+        Ok(match code {
+            OptCodes::Nsid => Self::Nsid(buf.to_vec()),
+            OptCodes::Ecs => {
+                if buf.len() < 4 {
+                    return Err(EdnsOptionParseError("truncated ECS option".to_string()));
+                }
+
+                let family = u16::from_be_bytes([buf[0], buf[1]]);
+                let source_prefix = buf[2];
+                let scope_prefix = buf[3];
+                let addr = &buf[4..];
+
+                if addr.len() != (source_prefix as usize).div_ceil(8) {
+                    return Err(EdnsOptionParseError(format!("invalid ECS address length {} for source prefix {}", addr.len(), source_prefix)));
+                }
+
+                // RFC 7871 §6: address bits past SOURCE PREFIX-LENGTH must be zero
+                if !source_prefix.is_multiple_of(8) && addr.last().is_some_and(|octet| octet & (0xFF >> (source_prefix % 8)) != 0) {
+                    return Err(EdnsOptionParseError(format!("ECS address has bits set beyond source prefix {}", source_prefix)));
+                }
+
+                let address = match family {
+                    1 if source_prefix <= 32 => {
+                        let mut octets = [0u8; 4];
+                        octets[..addr.len()].copy_from_slice(addr);
+                        IpAddr::V4(Ipv4Addr::from(octets))
+                    }
+                    2 if source_prefix <= 128 => {
+                        let mut octets = [0u8; 16];
+                        octets[..addr.len()].copy_from_slice(addr);
+                        IpAddr::V6(Ipv6Addr::from(octets))
+                    }
+                    _ => return Err(EdnsOptionParseError(format!("invalid ECS family {} with source prefix {}", family, source_prefix)))
+                };
+
+                Self::Ecs {
+                    address,
+                    source_prefix,
+                    scope_prefix
+                }
+            }
+            OptCodes::Expire => Self::Expire(match buf.len() {
+                0 => None,
+                4 => Some(u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]])),
+                _ => return Err(EdnsOptionParseError(format!("invalid EXPIRE length: {}", buf.len())))
+            }),
+            OptCodes::Cookie => {
+                if buf.len() != 8 && !(16..=40).contains(&buf.len()) {
+                    return Err(EdnsOptionParseError(format!("invalid COOKIE length: {}", buf.len())));
+                }
+
+                let mut client = [0u8; 8];
+                client.copy_from_slice(&buf[..8]);
+
+                Self::Cookie {
+                    client,
+                    server: match buf.len() {
+                        8 => None,
+                        _ => Some(buf[8..].to_vec())
+                    }
+                }
+            }
+            OptCodes::TcpKeepalive => Self::TcpKeepalive(match buf.len() {
+                0 => None,
+                2 => Some(u16::from_be_bytes([buf[0], buf[1]])),
+                _ => return Err(EdnsOptionParseError(format!("invalid TCP_KEEP_ALIVE length: {}", buf.len())))
+            }),
+            OptCodes::Padding => Self::Padding(buf.len() as u16),
+            OptCodes::Chain => {
+                let (fqdn, length) = unpack_fqdn(buf, 0);
+                if length != buf.len() {
+                    return Err(EdnsOptionParseError("invalid CHAIN name".to_string()));
+                }
+
+                Self::Chain(fqdn)
+            }
+            OptCodes::KeyTag => {
+                if !buf.len().is_multiple_of(2) {
+                    return Err(EdnsOptionParseError(format!("invalid KEYTAG length: {}", buf.len())));
+                }
+
+                Self::KeyTag(buf.chunks_exact(2)
+                    .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                    .collect())
+            }
+            OptCodes::Ede => {
+                if buf.len() < 2 {
+                    return Err(EdnsOptionParseError("truncated EDE option".to_string()));
+                }
+
+                Self::Ede {
+                    info_code: u16::from_be_bytes([buf[0], buf[1]]),
+                    extra_text: String::from_utf8(buf[2..].to_vec())
+                        .map_err(|_| EdnsOptionParseError("invalid EDE extra text".to_string()))?
+                }
+            }
+            _ => Self::Other(code, buf.to_vec())
+        })
     }
 
-    pub fn code(&self) -> OptCodes {
-        self.code
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Nsid(data) => data.clone(),
+            Self::Ecs { address, source_prefix, scope_prefix } => {
+                let (family, octets, max_prefix) = match address {
+                    IpAddr::V4(address) => (1u16, address.octets().to_vec(), 32),
+                    IpAddr::V6(address) => (2u16, address.octets().to_vec(), 128)
+                };
+
+                let source_prefix = (*source_prefix).min(max_prefix);
+                let addr = &octets[..(source_prefix as usize).div_ceil(8)];
+
+                let mut out = Vec::with_capacity(4 + addr.len());
+                out.extend_from_slice(&family.to_be_bytes());
+                out.push(source_prefix);
+                out.push(*scope_prefix);
+                out.extend_from_slice(addr);
+                out
+            }
+            Self::Expire(expire) => expire.map(|v| v.to_be_bytes().to_vec()).unwrap_or_default(),
+            Self::Cookie { client, server } => {
+                let mut out = client.to_vec();
+                if let Some(server) = server {
+                    out.extend_from_slice(server);
+                }
+                out
+            }
+            Self::TcpKeepalive(timeout) => timeout.map(|v| v.to_be_bytes().to_vec()).unwrap_or_default(),
+            Self::Padding(length) => vec![0; *length as usize],
+            Self::Chain(fqdn) => pack_fqdn(fqdn),
+            Self::KeyTag(tags) => {
+                let mut out = Vec::with_capacity(tags.len() * 2);
+                for tag in tags {
+                    out.extend_from_slice(&tag.to_be_bytes());
+                }
+                out
+            }
+            Self::Ede { info_code, extra_text } => {
+                let mut out = Vec::with_capacity(2 + extra_text.len());
+                out.extend_from_slice(&info_code.to_be_bytes());
+                out.extend_from_slice(extra_text.as_bytes());
+                out
+            }
+            Self::Other(_, data) => data.clone()
+        }
+    }
+
+    pub fn set_code(&mut self, code: OptCodes) {
+        *self = Self::new(code, &self.to_bytes());
     }
 
     pub fn set_data(&mut self, data: &[u8]) {
-        self.data = data.to_vec();
+        *self = Self::new(self.code(), data);
     }
 
-    pub fn data(&self) -> &[u8] {
-        &self.data
+    // Borrows the payload of opaque options, use to_bytes for an owned copy
+    pub fn data(&self) -> Cow<'_, [u8]> {
+        match self {
+            Self::Nsid(data) | Self::Other(_, data) => Cow::Borrowed(data),
+            _ => Cow::Owned(self.to_bytes())
+        }
+    }
+
+    pub fn code(&self) -> OptCodes {
+        match self {
+            Self::Nsid(_)            => OptCodes::Nsid,
+            Self::Ecs { .. }         => OptCodes::Ecs,
+            Self::Expire(_)          => OptCodes::Expire,
+            Self::Cookie { .. }      => OptCodes::Cookie,
+            Self::TcpKeepalive(_)    => OptCodes::TcpKeepalive,
+            Self::Padding(_)         => OptCodes::Padding,
+            Self::Chain(_)           => OptCodes::Chain,
+            Self::KeyTag(_)          => OptCodes::KeyTag,
+            Self::Ede { .. }         => OptCodes::Ede,
+            Self::Other(code, _)     => *code
+        }
     }
 }
 
 impl fmt::Display for EdnsOption {
 
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self.code {
-            OptCodes::Ecs => {
-                if self.data.len() >= 4 {
-                    let family = u16::from_be_bytes([self.data[0], self.data[1]]);
-                    let src_prefix = self.data[2];
-                    let scope_prefix = self.data[3];
-                    let addr = &self.data[4..];
-
-                    let ip_str = match family {
-                        1 => format!("{}", Ipv4Addr::new(addr[0], addr[1], addr[2], addr[3])),
-                        2 => format!("{}", Ipv6Addr::from(<[u8; 16]>::try_from(addr).unwrap_or_default())),
-                        _ => format!("unknown family {}", family),
-                    };
-
-                    write!(f, "{}: {ip_str}/{src_prefix}/{scope_prefix}", self.code)
-
-                } else {
-                    write!(f, "{}: (invalid)", self.code)
+        write!(f, "{}: ", self.code())?;
+
+        match self {
+            Self::Nsid(data) => write!(f, "{} (\"{}\")", hex::encode(data), String::from_utf8_lossy(data)),
+            Self::Ecs { address, source_prefix, scope_prefix } => write!(f, "{address}/{source_prefix}/{scope_prefix}"),
+            Self::Expire(Some(expire)) => write!(f, "{} secs", expire),
+            Self::Cookie { client, server } => {
+                write!(f, "{}", hex::encode(client))?;
+                match server {
+                    Some(server) => write!(f, " {}", hex::encode(server)),
+                    None => Ok(())
                 }
             }
-            _ => write!(f, "{}: {}", self.code, hex::encode(&self.data))
+            Self::TcpKeepalive(Some(timeout)) => write!(f, "{}.{} secs", timeout / 10, timeout % 10),
+            Self::Expire(None) | Self::TcpKeepalive(None) => Ok(()),
+            Self::Padding(length) => write!(f, "({} bytes)", length),
+            Self::Chain(fqdn) => write!(f, "{}.", fqdn),
+            Self::KeyTag(tags) => write!(f, "{}", tags.iter()
+                .map(|t| t.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")),
+            Self::Ede { info_code, extra_text } => match extra_text.is_empty() {
+                true => write!(f, "{}", info_code),
+                false => write!(f, "{} (\"{}\")", info_code, extra_text)
+            },
+            Self::Other(_, data) => write!(f, "{}", hex::encode(data))
         }
     }
 }
@@ -136,10 +355,11 @@ impl Edns {
 
         let mut opt_length = 0u16;
         for option in self.options.iter() {
-            buf.extend_from_slice(&option.code.code().to_be_bytes());
-            buf.extend_from_slice(&(option.data.len() as u16).to_be_bytes());
-            buf.extend_from_slice(&option.data);
-            opt_length += 4+option.data.len() as u16;
+            let data = option.to_bytes();
+            buf.extend_from_slice(&option.code().code().to_be_bytes());
+            buf.extend_from_slice(&(data.len() as u16).to_be_bytes());
+            buf.extend_from_slice(&data);
+            opt_length += 4+data.len() as u16;
         }
 
         buf[6..8].copy_from_slice(&opt_length.to_be_bytes());
@@ -250,10 +470,11 @@ impl ToWire for Edns {
 
         let mut opt_length = 0u16;
         for option in self.options.iter() {
-            option.code.code().to_wire(context)?;
-            (option.data.len() as u16).to_wire(context)?;
-            context.write(&option.data)?;
-            opt_length += 4+option.data.len() as u16;
+            let data = option.to_bytes();
+            option.code().code().to_wire(context)?;
+            (data.len() as u16).to_wire(context)?;
+            context.write(&data)?;
+            opt_length += 4+data.len() as u16;
         }
 
         context.patch(checkpoint..checkpoint+2, &opt_length.to_be_bytes())?;
@@ -280,4 +501,52 @@ fn test() {
     let buf = vec![ 0x4, 0xd0, 0x0, 0x0, 0x0, 0x0, 0x0, 0xc, 0x0, 0xa, 0x0, 0x8, 0x3c, 0x79, 0xda, 0xbb, 0x15, 0xdc, 0x64, 0x77 ];
     let record = Edns::from_bytes(&buf).unwrap();
     assert_eq!(buf, record.to_bytes().unwrap());
+    assert_eq!(record.options()[0], EdnsOption::Cookie { client: [0x3c, 0x79, 0xda, 0xbb, 0x15, 0xdc, 0x64, 0x77], server: None });
+
+    let mut edns = Edns::new(1232, 0, 0, true, 0, Vec::new());
+    edns.add_option(EdnsOption::Nsid(b"ns1".to_vec()));
+    edns.add_option(EdnsOption::ecs(IpAddr::V4(Ipv4Addr::new(192, 0, 3, 130)), 23, 0));
+    edns.add_option(EdnsOption::Expire(Some(3600)));
+    edns.add_option(EdnsOption::Cookie { client: [1; 8], server: Some(vec![2; 16]) });
+    edns.add_option(EdnsOption::TcpKeepalive(Some(300)));
+    edns.add_option(EdnsOption::Padding(12));
+    edns.add_option(EdnsOption::Chain("find9.net".to_string()));
+    edns.add_option(EdnsOption::KeyTag(vec![20326, 38696]));
+    edns.add_option(EdnsOption::Ede { info_code: 18, extra_text: "prohibited".to_string() });
+
+    let buf = edns.to_bytes().unwrap();
+    assert_eq!(&buf[19..26], &[0x0, 0x1, 23, 0, 192, 0, 2]);
+
+    let mut context = ToWireContext::with_capacity(512);
+    edns.to_wire(&mut context).unwrap();
+    assert_eq!(buf, context.to_bytes());
+
+    let record = Edns::from_wire(&mut FromWireContext::new(&buf)).unwrap();
+    assert_eq!(buf, record.to_bytes().unwrap());
+    assert_eq!(record.options().len(), 9);
+    assert_eq!(record.options()[1], EdnsOption::Ecs { address: IpAddr::V4(Ipv4Addr::new(192, 0, 2, 0)), source_prefix: 23, scope_prefix: 0 });
+    assert_eq!(record.options()[1].to_string(), "ECS: 192.0.2.0/23/0");
+    assert_eq!(record.options()[8].to_string(), "EDE: 18 (\"prohibited\")");
+    assert_eq!(&record.options()[2..], &edns.options()[2..]);
+
+    let buf = vec![ 0x4, 0xd0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x12,
+        0x0, 0x8, 0x0, 0x7, 0x0, 0x1, 0x17, 0x0, 0xc0, 0x0, 0x3,
+        0x0, 0xe, 0x0, 0x3, 0x4e, 0x66, 0x1 ];
+    let record = Edns::from_bytes(&buf).unwrap();
+    assert_eq!(buf, record.to_bytes().unwrap());
+    assert_eq!(record.options()[0], EdnsOption::Other(OptCodes::Ecs, vec![0x0, 0x1, 0x17, 0x0, 0xc0, 0x0, 0x3]));
+    assert_eq!(record.options()[1], EdnsOption::Other(OptCodes::KeyTag, vec![0x4e, 0x66, 0x1]));
+
+    assert_eq!(EdnsOption::new(OptCodes::Ede, &[0x0, 0x12, 0xff, 0x6f, 0x6b]), EdnsOption::Other(OptCodes::Ede, vec![0x0, 0x12, 0xff, 0x6f, 0x6b]));
+    assert!(EdnsOption::from_bytes(OptCodes::Ecs, &[0x0, 0x1, 0x17, 0x0, 0xc0, 0x0, 0x3]).is_err());
+    assert!(EdnsOption::from_bytes(OptCodes::Ecs, &[0x0, 0x1, 0x17, 0x0, 0xc0, 0x0, 0x2]).is_ok());
+    assert!(EdnsOption::from_bytes(OptCodes::Ecs, &[0x0, 0x2, 0x37, 0x0, 0x20, 0x1, 0xd, 0xb8, 0x0, 0x0, 0x1]).is_err());
+    assert_eq!(EdnsOption::new(OptCodes::Ecs, &[0x0, 0x1, 0x18, 0x0, 0xc0]), EdnsOption::Other(OptCodes::Ecs, vec![0x0, 0x1, 0x18, 0x0, 0xc0]));
+
+    let mut option = EdnsOption::new(OptCodes::Ecs, &[0x0, 0x1, 0x18, 0x0, 0xc0, 0x0]);
+    assert_eq!(option.code(), OptCodes::Ecs);
+    assert_eq!(option.data(), &[0x0, 0x1, 0x18, 0x0, 0xc0, 0x0][..]);
+    assert!(matches!(option.data(), Cow::Borrowed(_)));
+    option.set_data(&[0x0, 0x1, 0x10, 0x0, 0xc0, 0xa8]);
+    assert_eq!(option, EdnsOption::Ecs { address: IpAddr::V4(Ipv4Addr::new(192, 168, 0, 0)), source_prefix: 16, scope_prefix: 0 });
 }
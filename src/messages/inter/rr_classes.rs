@@ -9,7 +9,9 @@ pub enum RRClasses {
     Ch,
     Hs,
     None,
-    Any
+    Any,
+    // Only codes without a named variant, build it with try_from so Unknown(1) never stands in for IN
+    Unknown(u16)
 }
 
 impl RRClasses {
@@ -20,14 +22,14 @@ impl RRClasses {
             Self::Ch => 3,
             Self::Hs => 4,
             Self::None => 254,
-            Self::Any => 255
+            Self::Any => 255,
+            Self::Unknown(v) => *v
         }
     }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum RRClassParseError {
-    UnknownCode(u16),
     UnknownName(String)
 }
 
@@ -35,7 +37,6 @@ impl fmt::Display for RRClassParseError {
 
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{}", match self {
-            Self::UnknownCode(v) => format!("unknown class code: {}", v),
             Self::UnknownName(s) => format!("unknown class name: {}", s)
         })
     }
@@ -52,7 +53,7 @@ impl TryFrom<u16> for RRClasses {
             4 => Self::Hs,
             254 => Self::None,
             255 => Self::Any,
-            _  => return Err(RRClassParseError::UnknownCode(v))
+            _  => Self::Unknown(v)
         })
     }
 }
@@ -68,7 +69,12 @@ impl FromStr for RRClasses {
             "HS" => Self::Hs,
             "NONE" => Self::None,
             "ANY" => Self::Any,
-            _  => return Err(RRClassParseError::UnknownName(s.to_string()))
+            _  => {
+                let code = s.strip_prefix("CLASS")
+                    .and_then(|v| v.parse::<u16>().ok())
+                    .ok_or_else(|| RRClassParseError::UnknownName(s.to_string()))?;
+                return Self::try_from(code);
+            }
         })
     }
 }
@@ -81,7 +87,8 @@ impl fmt::Display for RRClasses {
             Self::Ch => "CH",
             Self::Hs => "HS",
             Self::None => "NONE",
-            Self::Any => "ANY"
+            Self::Any => "ANY",
+            Self::Unknown(v) => return write!(f, "CLASS{}", v)
         })
     }
 }
@@ -19,6 +19,7 @@ pub enum RRTypes {
     Srv,
     Naptr,
     Cert,
+    DName,
     Opt,
     Ds,
     SshFp,
@@ -33,6 +34,7 @@ pub enum RRTypes {
     Cds,
     CdnsKey,
     OpenPGPKey,
+    ZoneMd,
     Svcb,
     Https,
     Spf,
@@ -42,7 +44,9 @@ pub enum RRTypes {
     Ixfr,
     Axfr,
     Uri,
-    Caa
+    Caa,
+    // Only codes without a named variant, build it with try_from so Unknown(1) never stands in for A
+    Unknown(u16)
 }
 
 impl RRTypes {
@@ -62,6 +66,7 @@ impl RRTypes {
             Self::Srv => 33,
             Self::Naptr => 35,
             Self::Cert => 37,
+            Self::DName => 39,
             Self::Opt => 41,
             Self::Ds => 43,
             Self::SshFp => 44,
@@ -76,6 +81,7 @@ impl RRTypes {
             Self::Cds => 59,
             Self::CdnsKey => 60,
             Self::OpenPGPKey => 61,
+            Self::ZoneMd => 63,
             Self::Svcb => 64,
             Self::Https => 65,
             Self::Spf => 99,
@@ -85,14 +91,14 @@ impl RRTypes {
             Self::Axfr => 252,
             Self::Any => 255,
             Self::Uri => 256,
-            Self::Caa => 257
+            Self::Caa => 257,
+            Self::Unknown(v) => *v
         }
     }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum RRTypeParseError {
-    UnknownCode(u16),
     UnknownName(String)
 }
 
@@ -100,7 +106,6 @@ impl fmt::Display for RRTypeParseError {
 
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{}", match self {
-            Self::UnknownCode(v) => format!("unknown type code: {}", v),
             Self::UnknownName(s) => format!("unknown type name: {}", s)
         })
     }
@@ -125,6 +130,7 @@ impl TryFrom<u16> for RRTypes {
             33 => Self::Srv,
             35 => Self::Naptr,
             37 => Self::Cert,
+            39 => Self::DName,
             41 => Self::Opt,
             43 => Self::Ds,
             44 => Self::SshFp,
@@ -139,6 +145,7 @@ impl TryFrom<u16> for RRTypes {
             59 => Self::Cds,
             60 => Self::CdnsKey,
             61 => Self::OpenPGPKey,
+            63 => Self::ZoneMd,
             64 => Self::Svcb,
             65 => Self::Https,
             99 => Self::Spf,
@@ -149,7 +156,7 @@ impl TryFrom<u16> for RRTypes {
             255 => Self::Any,
             256 => Self::Uri,
             257 => Self::Caa,
-            _  => return Err(RRTypeParseError::UnknownCode(v))
+            _  => Self::Unknown(v)
         })
     }
 }
@@ -173,6 +180,7 @@ impl FromStr for RRTypes {
             "SRV" => Self::Srv,
             "NAPTR" => Self::Naptr,
             "CERT" => Self::Cert,
+            "DNAME" => Self::DName,
             "OPT" => Self::Opt,
             "DS" => Self::Ds,
             "SSHFP" => Self::SshFp,
@@ -187,6 +195,7 @@ impl FromStr for RRTypes {
             "CDS" => Self::Cds,
             "CDNSKEY" => Self::CdnsKey,
             "OPENPGPKEY" => Self::OpenPGPKey,
+            "ZONEMD" => Self::ZoneMd,
             "SVCB" => Self::Svcb,
             "HTTPS" => Self::Https,
             "SPF" => Self::Spf,
@@ -197,7 +206,12 @@ impl FromStr for RRTypes {
             "ANY" => Self::Any,
             "URI" => Self::Uri,
             "CAA" => Self::Caa,
-            _  => return Err(RRTypeParseError::UnknownName(s.to_string()))
+            _  => {
+                let code = s.strip_prefix("TYPE")
+                    .and_then(|v| v.parse::<u16>().ok())
+                    .ok_or_else(|| RRTypeParseError::UnknownName(s.to_string()))?;
+                return Self::try_from(code);
+            }
         })
     }
 }
@@ -219,6 +233,7 @@ impl fmt::Display for RRTypes {
             Self::Srv => "SRV",
             Self::Naptr => "NAPTR",
             Self::Cert => "CERT",
+            Self::DName => "DNAME",
             Self::Opt => "OPT",
             Self::Ds => "DS",
             Self::SshFp => "SSHFP",
@@ -233,6 +248,7 @@ impl fmt::Display for RRTypes {
             Self::Cds => "CDS",
             Self::CdnsKey => "CDNSKEY",
             Self::OpenPGPKey => "OPENPGPKEY",
+            Self::ZoneMd => "ZONEMD",
             Self::Svcb => "SVCB",
             Self::Https => "HTTPS",
             Self::Spf => "SPF",
@@ -242,7 +258,8 @@ impl fmt::Display for RRTypes {
             Self::Axfr => "AXFR",
             Self::Any => "ANY",
             Self::Uri => "URI",
-            Self::Caa => "CAA"
+            Self::Caa => "CAA",
+            Self::Unknown(v) => return write!(f, "TYPE{}", v)
         })
     }
 }
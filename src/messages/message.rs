@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fmt;
 use std::fmt::Formatter;
 use std::net::SocketAddr;
@@ -16,6 +17,8 @@ use crate::rr_data::tsig_rr_data::TSigRRData;
 use crate::utils::fqdn_utils::pack_fqdn;
 use crate::utils::hash::hmac::hmac;
 use crate::utils::hash::sha256::Sha256;
+pub const DEFAULT_EDNS_PAYLOAD_SIZE: u16 = 1232;
+
 /*
                                1  1  1  1  1  1
  0  1  2  3  4  5  6  7  8  9  0  1  2  3  4  5
@@ -103,7 +106,7 @@ impl Message {
         //let z = (flags & 0x0040) != 0;
         let authenticated_data = (flags & 0x0020) != 0;
         let checking_disabled = (flags & 0x0010) != 0;
-        let response_code = ResponseCodes::try_from((flags & 0x000F) as u8).map_err(|e| WireError::Format(e.to_string()))?;
+        let response_code = flags & 0x000F;
 
         let qd_count = u16::from_wire(&mut context)?;
         let an_count = u16::from_wire(&mut context)?;
@@ -129,6 +132,7 @@ impl Message {
         let mut edns = None;
         let mut tsig = None;
         for _ in 0..ar_count {
+            let record_start = context.pos();
             let fqdn = context.name()?;
             let checkpoint = context.pos();
 
@@ -167,13 +171,17 @@ impl Message {
                                     ]);
                                     signed_payload.extend_from_slice(&data.fudge().to_be_bytes());
 
-                                    signed_payload.extend_from_slice(&data.error().to_be_bytes());
+                                    signed_payload.extend_from_slice(&data.error().code().to_be_bytes());
 
                                     signed_payload.extend_from_slice(&(data.data().len() as u16).to_be_bytes());
                                     signed_payload.extend_from_slice(&data.data());
 
+                                    let mut unsigned_message = context.range(0..record_start)?.to_vec();
+                                    unsigned_message[10..12].copy_from_slice(&(ar_count - 1).to_be_bytes());
+
                                     let mut ts = TSig::new(&fqdn, data);
                                     ts.set_signed_payload(&signed_payload);
+                                    ts.set_unsigned_message(&unsigned_message);
 
                                     tsig = Some(ts);
                                 }
@@ -199,6 +207,12 @@ impl Message {
             }
         }
 
+        let response_code = match edns.as_ref() {
+            Some(edns) => ((edns.ext_rcode() as u16) << 4) | response_code,
+            None => response_code
+        };
+        let response_code = ResponseCodes::try_from(response_code).map_err(|e| WireError::Format(e.to_string()))?;
+
         Ok(Self {
             id,
             op_code,
@@ -264,7 +278,7 @@ impl Message {
         'ar: {
             if !truncated {
                 count = 0;
-                if let Some(edns) = self.edns.as_ref() {
+                if let Some(edns) = self.wire_edns() {
                     let checkpoint = context.pos();
                     if let Err(_) = {
                         0u8.to_wire(&mut context).unwrap();
@@ -275,6 +289,7 @@ impl Message {
                         context.rollback(checkpoint);
                         break 'ar;
                     }
+                    context.patch(checkpoint+5..checkpoint+6, &[(self.response_code.code() >> 4) as u8]).unwrap();
                     count += 1;
                 }
 
@@ -351,7 +366,7 @@ impl Message {
         'ar: {
             if !truncated {
                 count = 0;
-                if let Some(edns) = self.edns.as_ref() {
+                if let Some(edns) = self.wire_edns() {
                     let checkpoint = context.pos();
                     if let Err(_) = {
                         0u8.to_wire(&mut context).unwrap();
@@ -362,6 +377,7 @@ impl Message {
                         context.rollback(checkpoint);
                         break 'ar;
                     }
+                    context.patch(checkpoint+5..checkpoint+6, &[(self.response_code.code() >> 4) as u8]).unwrap();
                     count += 1;
                 }
 
@@ -413,22 +429,21 @@ impl Message {
                 ]);
                 signed_payload.extend_from_slice(&tsig.data().fudge().to_be_bytes());
 
-                signed_payload.extend_from_slice(&tsig.data().error().to_be_bytes());
+                signed_payload.extend_from_slice(&tsig.data().error().code().to_be_bytes());
 
                 signed_payload.extend_from_slice(&(tsig.data().data().len() as u16).to_be_bytes());
                 signed_payload.extend_from_slice(&tsig.data().data());
 
                 tsig.set_signed_payload(&signed_payload);
-                tsig.sign(key);
-
+                if tsig.sign(key) {
+                    if let Err(_) = tsig.to_wire(&mut context) {
+                        truncated = true;
+                        context.rollback(checkpoint);
+                    }
+                    count += 1;
 
-                if let Err(_) = tsig.to_wire(&mut context) {
-                    truncated = true;
-                    context.rollback(checkpoint);
+                    context.patch(10..12, &count.to_be_bytes()).unwrap();
                 }
-                count += 1;
-
-                context.patch(10..12, &count.to_be_bytes()).unwrap();
             }
         }
 
@@ -457,7 +472,7 @@ impl Message {
         flags.to_wire(&mut context).unwrap();  // RCODE
 
         let mut total = self.sections.iter().map(|r| r.len()).sum();
-        if self.edns.is_some() {
+        if self.wire_edns().is_some() {
             total += 1;
         }
         if self.tsig.is_some() {
@@ -495,7 +510,7 @@ impl Message {
         flags.to_wire(&mut context).unwrap();  // RCODE
 
         let mut total = self.sections.iter().map(|r| r.len()).sum();
-        if self.edns.is_some() {
+        if self.wire_edns().is_some() {
             total += 1;
         }
         if self.tsig.is_some() {
@@ -651,6 +666,18 @@ impl Message {
         self.edns.as_ref()
     }
 
+    pub fn edns_mut(&mut self) -> Option<&mut Edns> {
+        self.edns.as_mut()
+    }
+
+    fn wire_edns(&self) -> Option<Cow<'_, Edns>> {
+        match self.edns.as_ref() {
+            Some(edns) => Some(Cow::Borrowed(edns)),
+            None if self.response_code.code() > 0x0F => Some(Cow::Owned(Edns::new(DEFAULT_EDNS_PAYLOAD_SIZE, 0, 0, false, 0, Vec::new()))),
+            None => None
+        }
+    }
+
     pub fn set_tsig(&mut self, tsig: TSig) {
         self.tsig = Some(tsig);
     }
@@ -816,7 +843,7 @@ impl<'a> Iterator for WireIter<'a> {
                     count = 0;
                     let start = self.position - before;
 
-                    if let Some(edns) = self.message.edns.as_ref() {
+                    if let Some(edns) = self.message.wire_edns() {
                         let checkpoint = self.context.pos();
                         rollbacks.push(checkpoint);
                         if let Err(_) = {
@@ -830,6 +857,7 @@ impl<'a> Iterator for WireIter<'a> {
                             println!("QUICK BREAK - EDNS");
                             break 'sections;
                         }
+                        self.context.patch(checkpoint+5..checkpoint+6, &[(self.message.response_code.code() >> 4) as u8]).unwrap();
                         count += 1;
                     }
 
@@ -873,7 +901,7 @@ impl<'a> Iterator for WireIter<'a> {
                         ]);
                         signed_payload.extend_from_slice(&tsig.data().fudge().to_be_bytes());
 
-                        signed_payload.extend_from_slice(&tsig.data().error().to_be_bytes());
+                        signed_payload.extend_from_slice(&tsig.data().error().code().to_be_bytes());
 
                         signed_payload.extend_from_slice(&(tsig.data().data().len() as u16).to_be_bytes());
                         signed_payload.extend_from_slice(&tsig.data().data());
@@ -921,22 +949,22 @@ impl<'a> Iterator for WireIter<'a> {
             ]);
             signed_payload.extend_from_slice(&tsig.data().fudge().to_be_bytes());
 
-            signed_payload.extend_from_slice(&tsig.data().error().to_be_bytes());
+            signed_payload.extend_from_slice(&tsig.data().error().code().to_be_bytes());
 
             signed_payload.extend_from_slice(&(tsig.data().data().len() as u16).to_be_bytes());
             signed_payload.extend_from_slice(&tsig.data().data());
 
             tsig.set_signed_payload(&signed_payload);
-            tsig.sign(self.key.as_ref().unwrap());
+            if tsig.sign(self.key.as_ref().unwrap()) {
+                if let Err(_) = tsig.to_wire(&mut self.context) {
+                    truncated = true;
+                    self.context.rollback(checkpoint);
+                }
+                count += 1;
 
-            if let Err(_) = tsig.to_wire(&mut self.context) {
-                truncated = true;
-                self.context.rollback(checkpoint);
+                self.context.patch(10..12, &count.to_be_bytes()).unwrap();
+                println!("COMPLETE - TSIG");
             }
-            count += 1;
-
-            self.context.patch(10..12, &count.to_be_bytes()).unwrap();
-            println!("COMPLETE - TSIG");
         }
 
         println!("PUSHING NOW  {:?}", rollbacks);
@@ -944,3 +972,57 @@ impl<'a> Iterator for WireIter<'a> {
         Some(self.context.to_bytes())
     }
 }
+
+#[test]
+fn test() {
+    let mut message = Message::new(0x1234);
+    message.set_qr(true);
+    message.set_response_code(ResponseCodes::BadVers);
+    message.set_edns(Edns::new(1232, 0, 0, false, 0, Vec::new()));
+
+    let buf = message.to_bytes(512);
+    assert_eq!(buf[3] & 0x0F, 0);
+    assert_eq!(buf[12 + 5], 1);
+    assert_eq!(Message::from_bytes(&buf).unwrap().response_code(), ResponseCodes::BadVers);
+
+    message.set_response_code(ResponseCodes::NxRrSet);
+    let buf = message.to_bytes(512);
+    assert_eq!(buf[12 + 5], 0);
+    assert_eq!(Message::from_bytes(&buf).unwrap().response_code(), ResponseCodes::NxRrSet);
+
+    let mut message = Message::new(0x1234);
+    message.set_qr(true);
+    message.set_response_code(ResponseCodes::BadCookie);
+    let buf = message.to_bytes(512);
+    assert_eq!(buf[3] & 0x0F, 7);
+    assert_eq!(u16::from_be_bytes([buf[10], buf[11]]), 1);
+    assert_eq!(Message::from_bytes(&buf).unwrap().response_code(), ResponseCodes::BadCookie);
+
+    message.set_response_code(ResponseCodes::Unknown(12));
+    let buf = message.to_bytes(512);
+    assert_eq!(u16::from_be_bytes([buf[10], buf[11]]), 0);
+    assert_eq!(Message::from_bytes(&buf).unwrap().response_code(), ResponseCodes::Unknown(12));
+
+    message.set_response_code(ResponseCodes::Unknown(3841));
+    let buf = message.to_bytes(512);
+    assert_eq!(Message::from_bytes(&buf).unwrap().response_code(), ResponseCodes::Unknown(3841));
+    assert_eq!(ResponseCodes::from_tsig_error(0xFF00).unwrap(), ResponseCodes::Unknown(0xFF00));
+}
+
+#[test]
+fn test_unknown_round_trip() {
+    use crate::rr_data::raw_rr_data::RawRRData;
+
+    let mut message = Message::new(0x4321);
+    message.set_qr(true);
+    message.add_query(RRQuery::new("find9.net", RRTypes::Unknown(65280), RRClasses::Unknown(32)));
+    message.add_section(0, "find9.net", RRClasses::Unknown(32), RRTypes::Unknown(65280), 300, Some(RawRRData::new(&[ 0xde, 0xad, 0xbe, 0xef ]).upcast()));
+    message.add_section(0, "find9.net", RRClasses::In, RRTypes::Unknown(65281), 300, Some(RawRRData::new(&[ 0x3, 0x77, 0x77, 0x77, 0x0 ]).upcast()));
+
+    let buf = message.to_bytes(512);
+    let parsed = Message::from_bytes(&buf).unwrap();
+    assert_eq!(parsed.queries()[0].rtype(), RRTypes::Unknown(65280));
+    assert_eq!(parsed.section(0)[0].class(), RRClasses::Unknown(32));
+    assert_eq!(parsed.section(0)[1].data().unwrap().as_any().downcast_ref::<RawRRData>().unwrap().data(), &[ 0x3, 0x77, 0x77, 0x77, 0x0 ]);
+    assert_eq!(parsed.to_bytes(512), buf);
+}
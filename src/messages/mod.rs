@@ -4,4 +4,6 @@ pub mod rr_query;
 pub mod record;
 pub mod edns;
 pub mod tsig;
+pub mod cookie;
+pub mod update_builder;
 pub mod wire;
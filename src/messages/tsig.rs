@@ -1,19 +1,29 @@
 use std::fmt;
 use std::fmt::Formatter;
 use crate::keyring::key::Key;
+use crate::messages::inter::response_codes::ResponseCodes;
 use crate::messages::inter::rr_classes::RRClasses;
 use crate::messages::inter::rr_types::RRTypes;
 use crate::messages::wire::{FromWire, FromWireContext, FromWireLen, ToWire, ToWireContext, WireError};
 use crate::rr_data::tsig_rr_data::TSigRRData;
 use crate::utils::fqdn_utils::pack_fqdn;
+use crate::keyring::inter::algorithms::Algorithms;
 use crate::utils::hash::hmac::hmac;
+use crate::utils::hash::md5::Md5;
+use crate::utils::hash::sha1::Sha1;
+use crate::utils::hash::sha224::Sha224;
 use crate::utils::hash::sha256::Sha256;
+use crate::utils::hash::sha384::Sha384;
+use crate::utils::hash::sha512::Sha512;
+
+pub const DEFAULT_TSIG_FUDGE: u16 = 300;
 
 #[derive(Debug, Clone)]
 pub struct TSig {
     owner: String,
     data: TSigRRData,
-    signed_payload: Vec<u8>
+    signed_payload: Vec<u8>,
+    unsigned_message: Vec<u8>
 }
 
 impl TSig {
@@ -22,7 +32,8 @@ impl TSig {
         Self {
             owner: owner.to_string(),
             data,
-            signed_payload: Vec::new()
+            signed_payload: Vec::new(),
+            unsigned_message: Vec::new()
         }
     }
 
@@ -58,15 +69,81 @@ impl TSig {
         self.signed_payload.extend_from_slice(&signed_payload);
     }
 
-    pub fn verify(&self, key: &Key) -> bool {
-        let calc = hmac::<Sha256>(key.secret(), &self.signed_payload);
-        self.data.mac().as_ref().unwrap().len() == calc.len() &&
-            self.data.mac().as_ref().unwrap().iter().zip(calc).fold(0u8, |d,(a,b)| d | (a^b)) == 0
+    pub fn set_unsigned_message(&mut self, unsigned_message: &[u8]) {
+        self.unsigned_message = unsigned_message.to_vec();
+    }
+
+    pub fn unsigned_message(&self) -> &[u8] {
+        &self.unsigned_message
+    }
+
+    pub fn set_chained_payload(&mut self, prior_mac: &[u8], message: &[u8], timers_only: bool) {
+        let mut signed_payload = Vec::with_capacity(prior_mac.len() + message.len() + 64);
+        signed_payload.extend_from_slice(&(prior_mac.len() as u16).to_be_bytes());
+        signed_payload.extend_from_slice(prior_mac);
+        signed_payload.extend_from_slice(message);
+
+        if !timers_only {
+            signed_payload.extend_from_slice(&pack_fqdn(&self.owner.to_lowercase()));
+            signed_payload.extend_from_slice(&RRClasses::Any.code().to_be_bytes());
+            signed_payload.extend_from_slice(&0u32.to_be_bytes());
+
+            if let Some(algorithm) = self.data.algorithm() {
+                signed_payload.extend_from_slice(&pack_fqdn(&algorithm.to_string()));
+            }
+        }
+
+        signed_payload.extend_from_slice(&self.data.time_signed().to_be_bytes()[2..]);
+        signed_payload.extend_from_slice(&self.data.fudge().to_be_bytes());
+
+        if !timers_only {
+            signed_payload.extend_from_slice(&self.data.error().code().to_be_bytes());
+            signed_payload.extend_from_slice(&(self.data.data().len() as u16).to_be_bytes());
+            signed_payload.extend_from_slice(self.data.data());
+        }
+
+        self.signed_payload = signed_payload;
+    }
+
+    pub fn verify(&self, key: &Key) -> Result<(), ResponseCodes> {
+        if self.data.algorithm() != Some(&key.algorithm()) {
+            return Err(ResponseCodes::BadKey);
+        }
+
+        let calc = Self::calculate_mac(key, &self.signed_payload).ok_or(ResponseCodes::BadKey)?;
+
+        match self.data.mac() {
+            Some(mac) if mac.len() == calc.len() &&
+                mac.iter().zip(calc).fold(0u8, |d,(a,b)| d | (a^b)) == 0 => Ok(()),
+            _ => Err(ResponseCodes::BadSig)
+        }
+    }
+
+    pub fn sign(&mut self, key: &Key) -> bool {
+        match Self::calculate_mac(key, &self.signed_payload) {
+            Some(mac) => {
+                self.data.set_mac(&mac);
+                true
+            }
+            None => false
+        }
     }
 
-    pub fn sign(&mut self, key: &Key) {
-        let hmac = hmac::<Sha256>(key.secret(), &self.signed_payload);
-        self.data.set_mac(hmac.as_slice());
+    fn calculate_mac(key: &Key, payload: &[u8]) -> Option<Vec<u8>> {
+        let secret = key.secret();
+
+        Some(match key.algorithm() {
+            Algorithms::HmacMd5SigAlgRegInt => hmac::<Md5>(secret, payload).to_vec(),
+            Algorithms::HmacSha1 => hmac::<Sha1>(secret, payload).to_vec(),
+            Algorithms::HmacSha224 => hmac::<Sha224>(secret, payload).to_vec(),
+            Algorithms::HmacSha256 => hmac::<Sha256>(secret, payload).to_vec(),
+            Algorithms::HmacSha384 => hmac::<Sha384>(secret, payload).to_vec(),
+            Algorithms::HmacSha512 => hmac::<Sha512>(secret, payload).to_vec(),
+            Algorithms::HmacSha256_128 => hmac::<Sha256>(secret, payload)[..16].to_vec(),
+            Algorithms::HmacSha384_192 => hmac::<Sha384>(secret, payload)[..24].to_vec(),
+            Algorithms::HmacSha512_256 => hmac::<Sha512>(secret, payload)[..32].to_vec(),
+            Algorithms::GssTSig => return None
+        })
     }
 }
 
@@ -109,7 +186,7 @@ impl FromWireLen for TSig {
                 ]);
                 signed_payload.extend_from_slice(&data.fudge().to_be_bytes());
 
-                signed_payload.extend_from_slice(&data.error().to_be_bytes());
+                signed_payload.extend_from_slice(&data.error().code().to_be_bytes());
 
                 signed_payload.extend_from_slice(&(data.data().len() as u16).to_be_bytes());
                 signed_payload.extend_from_slice(&data.data());
@@ -160,3 +237,43 @@ impl fmt::Display for TSig {
                self.data)
     }
 }
+
+#[test]
+fn test() {
+    let algorithms = [
+        (Algorithms::HmacMd5SigAlgRegInt, 16),
+        (Algorithms::HmacSha1, 20),
+        (Algorithms::HmacSha224, 28),
+        (Algorithms::HmacSha256, 32),
+        (Algorithms::HmacSha384, 48),
+        (Algorithms::HmacSha512, 64),
+        (Algorithms::HmacSha256_128, 16),
+        (Algorithms::HmacSha384_192, 24),
+        (Algorithms::HmacSha512_256, 32)
+    ];
+
+    for (algorithm, mac_len) in algorithms {
+        let key = Key::new(vec![0x2a; 32], algorithm);
+        let mut tsig = TSig::new("key.find9.net", TSigRRData::new(algorithm, 1700000000, 300, 0x1234, ResponseCodes::NoError, &[]));
+        tsig.set_signed_payload(b"signed payload");
+
+        assert!(tsig.sign(&key));
+        assert_eq!(tsig.data().mac().unwrap().len(), mac_len);
+        assert_eq!(tsig.verify(&key), Ok(()));
+
+        tsig.set_signed_payload(b"tampered payload");
+        assert_eq!(tsig.verify(&key), Err(ResponseCodes::BadSig));
+    }
+
+    let key = Key::new(vec![0x2a; 32], Algorithms::HmacSha256);
+    let mut tsig = TSig::new("key.find9.net", TSigRRData::new(Algorithms::HmacSha512, 1700000000, 300, 0x1234, ResponseCodes::NoError, &[]));
+    tsig.set_signed_payload(b"signed payload");
+    assert!(tsig.sign(&key));
+    assert_eq!(tsig.verify(&key), Err(ResponseCodes::BadKey));
+
+    let key = Key::new(vec![0x2a; 32], Algorithms::GssTSig);
+    let mut tsig = TSig::new("key.find9.net", TSigRRData::new(Algorithms::GssTSig, 1700000000, 300, 0x1234, ResponseCodes::NoError, &[]));
+    assert!(!tsig.sign(&key));
+    assert!(tsig.data().mac().is_none());
+    assert_eq!(tsig.verify(&key), Err(ResponseCodes::BadKey));
+}
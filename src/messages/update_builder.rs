@@ -0,0 +1,154 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::keyring::key::Key;
+use crate::messages::inter::op_codes::OpCodes;
+use crate::messages::inter::response_codes::ResponseCodes;
+use crate::messages::inter::rr_classes::RRClasses;
+use crate::messages::inter::rr_types::RRTypes;
+use crate::messages::message::Message;
+use crate::messages::record::Record;
+use crate::messages::rr_query::RRQuery;
+use crate::messages::tsig::{TSig, DEFAULT_TSIG_FUDGE};
+use crate::rr_data::inter::rr_data::RRData;
+use crate::rr_data::tsig_rr_data::TSigRRData;
+use crate::utils::random;
+
+#[derive(Debug, Clone)]
+pub struct UpdateBuilder {
+    id: u16,
+    zone: String,
+    class: RRClasses,
+    prerequisites: Vec<Record>,
+    updates: Vec<Record>,
+    additional: Vec<Record>,
+    tsig: Option<(String, Key)>
+}
+
+impl UpdateBuilder {
+
+    pub fn new(zone: &str, class: RRClasses) -> Self {
+        Self {
+            id: random::gen(),
+            zone: zone.to_string(),
+            class,
+            prerequisites: Vec::new(),
+            updates: Vec::new(),
+            additional: Vec::new(),
+            tsig: None
+        }
+    }
+
+    pub fn id(mut self, id: u16) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn require_exists(mut self, fqdn: &str, rtype: RRTypes) -> Self {
+        self.prerequisites.push(Record::new(fqdn, RRClasses::Any, rtype, 0, None));
+        self
+    }
+
+    pub fn require_absent(mut self, fqdn: &str, rtype: RRTypes) -> Self {
+        self.prerequisites.push(Record::new(fqdn, RRClasses::None, rtype, 0, None));
+        self
+    }
+
+    pub fn require_rrset_equals(mut self, fqdn: &str, rtype: RRTypes, data: Vec<Box<dyn RRData>>) -> Self {
+        for data in data {
+            self.prerequisites.push(Record::new(fqdn, self.class, rtype, 0, Some(data)));
+        }
+        self
+    }
+
+    pub fn add_rr(mut self, fqdn: &str, rtype: RRTypes, ttl: u32, data: Box<dyn RRData>) -> Self {
+        self.updates.push(Record::new(fqdn, self.class, rtype, ttl, Some(data)));
+        self
+    }
+
+    pub fn delete_rrset(mut self, fqdn: &str, rtype: RRTypes) -> Self {
+        self.updates.push(Record::new(fqdn, RRClasses::Any, rtype, 0, None));
+        self
+    }
+
+    pub fn delete_rr(mut self, fqdn: &str, rtype: RRTypes, data: Box<dyn RRData>) -> Self {
+        self.updates.push(Record::new(fqdn, RRClasses::None, rtype, 0, Some(data)));
+        self
+    }
+
+    pub fn delete_name(mut self, fqdn: &str) -> Self {
+        self.updates.push(Record::new(fqdn, RRClasses::Any, RRTypes::Any, 0, None));
+        self
+    }
+
+    pub fn add_additional(mut self, record: Record) -> Self {
+        self.additional.push(record);
+        self
+    }
+
+    pub fn set_key(mut self, key_name: &str, key: &Key) -> Self {
+        self.tsig = Some((key_name.to_string(), key.clone()));
+        self
+    }
+
+    pub fn build(self) -> Message {
+        let mut message = Message::new(self.id);
+        message.set_op_code(OpCodes::Update);
+        message.add_query(RRQuery::new(&self.zone, RRTypes::Soa, self.class));
+        message.set_sections([self.prerequisites, self.updates, self.additional]);
+
+        if let Some((key_name, key)) = self.tsig {
+            let time_signed = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+            message.set_tsig(TSig::new(&key_name, TSigRRData::new(key.algorithm(), time_signed, DEFAULT_TSIG_FUDGE, self.id, ResponseCodes::NoError, &[])));
+        }
+
+        message
+    }
+}
+
+#[test]
+fn test() {
+    use std::net::Ipv4Addr;
+    use crate::keyring::inter::algorithms::Algorithms;
+    use crate::rr_data::in_a_rr_data::InARRData;
+    use crate::rr_data::soa_rr_data::SoaRRData;
+    use crate::zone::inter::zone_types::ZoneTypes;
+    use crate::zone::update::UpdateMessage;
+    use crate::zone::zone::Zone;
+
+    let a = |i| InARRData::new(Ipv4Addr::new(10, 0, 0, i)).upcast();
+
+    let mut zone = Zone::new(ZoneTypes::Master, RRClasses::In);
+    zone.add_record("", RRTypes::Soa, 3600, SoaRRData::new("ns1.find9.net", "admin.find9.net", 1, 3600, 600, 604800, 300).upcast());
+    zone.add_record("www", RRTypes::A, 300, a(1));
+    zone.add_record("old", RRTypes::A, 300, a(2));
+
+    let key = Key::new(b"update-secret".to_vec(), Algorithms::HmacSha256);
+    let mut message = UpdateBuilder::new("find9.net", RRClasses::In)
+        .id(0x4242)
+        .require_exists("www.find9.net", RRTypes::A)
+        .require_absent("api.find9.net", RRTypes::Any)
+        .require_rrset_equals("www.find9.net", RRTypes::A, vec![a(1)])
+        .add_rr("api.find9.net", RRTypes::A, 300, a(3))
+        .delete_rr("www.find9.net", RRTypes::A, a(1))
+        .add_rr("www.find9.net", RRTypes::A, 300, a(4))
+        .delete_rrset("mail.find9.net", RRTypes::Mx)
+        .delete_name("old.find9.net")
+        .set_key("update-key", &key)
+        .build();
+
+    let buf = message.to_bytes_with_sig(4096, &key);
+    assert_eq!(&buf[4..12], &[0, 1, 0, 3, 0, 5, 0, 1]);
+
+    let parsed = Message::from_bytes(&buf).unwrap();
+    assert_eq!(parsed.id(), 0x4242);
+    assert_eq!(parsed.op_code(), OpCodes::Update);
+    assert!(parsed.tsig().unwrap().verify(&key).is_ok());
+
+    let update = UpdateMessage::from_message(&parsed).unwrap();
+    assert_eq!(update.zone().rtype(), RRTypes::Soa);
+    update.apply("find9.net", &mut zone).unwrap().unwrap();
+
+    assert_eq!(zone.soa().unwrap().serial(), 2);
+    assert_eq!(zone.rr_set("api", &RRTypes::A).unwrap().data().len(), 1);
+    assert!(zone.rr_set("www", &RRTypes::A).unwrap().data()[0].eq(&a(4)));
+    assert!(zone.all_rr_sets("old").is_none());
+}
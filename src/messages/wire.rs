@@ -26,7 +26,8 @@ impl fmt::Display for WireError {
 pub struct ToWireContext {
     buf: Vec<u8>,
     capacity: usize,
-    compression_map: HashMap<Vec<u8>, u16>
+    compression_map: HashMap<Vec<u8>, u16>,
+    canonical: bool
 }
 
 impl ToWireContext {
@@ -35,10 +36,26 @@ impl ToWireContext {
         Self {
             buf: Vec::with_capacity(capacity),
             capacity,
-            compression_map: HashMap::new()
+            compression_map: HashMap::new(),
+            canonical: false
         }
     }
 
+    pub fn canonical_with_capacity(capacity: usize) -> Self {
+        Self {
+            canonical: true,
+            ..Self::with_capacity(capacity)
+        }
+    }
+
+    pub fn set_canonical(&mut self, canonical: bool) {
+        self.canonical = canonical;
+    }
+
+    pub fn is_canonical(&self) -> bool {
+        self.canonical
+    }
+
     pub fn pos(&self) -> usize {
         self.buf.len()
     }
@@ -90,6 +107,17 @@ impl ToWireContext {
     }
 
     pub fn write_name(&mut self, fqdn: &str, emit_pointers: bool) -> Result<(), WireError> {
+        match self.canonical {
+            true => self.write_labels(&fqdn.to_lowercase(), false),
+            false => self.write_labels(fqdn, emit_pointers)
+        }
+    }
+
+    pub fn write_name_preserve_case(&mut self, fqdn: &str, emit_pointers: bool) -> Result<(), WireError> {
+        self.write_labels(fqdn, emit_pointers && !self.canonical)
+    }
+
+    fn write_labels(&mut self, fqdn: &str, emit_pointers: bool) -> Result<(), WireError> {
         if fqdn.is_empty() {
             self.ensure_space(1)?;
             0u8.to_wire(self)?;
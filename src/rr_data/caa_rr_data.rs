@@ -0,0 +1,322 @@
+use std::any::Any;
+use std::fmt;
+use std::fmt::Formatter;
+use crate::messages::wire::{FromWire, FromWireContext, FromWireLen, ToWire, ToWireContext, WireError};
+use crate::rr_data::inter::rr_data::{RRData, RRDataError};
+use crate::zone::inter::zone_rr_data::ZoneRRData;
+use crate::zone::zone_reader::{ErrorKind, ZoneReaderError};
+
+pub const CAA_ISSUER_CRITICAL: u8 = 0x80;
+
+#[derive(Clone, Debug, Eq)]
+pub struct CaaRRData {
+    flags: u8,
+    tag: Option<String>,
+    value: Vec<u8>
+}
+
+impl Default for CaaRRData {
+
+    fn default() -> Self {
+        Self {
+            flags: 0,
+            tag: None,
+            value: Vec::new()
+        }
+    }
+}
+
+impl PartialEq for CaaRRData {
+
+    fn eq(&self, other: &Self) -> bool {
+        self.flags == other.flags &&
+            self.value == other.value &&
+            match (&self.tag, &other.tag) {
+                (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+                (None, None) => true,
+                _ => false
+            }
+    }
+}
+
+impl RRData for CaaRRData {
+
+    fn from_bytes(buf: &[u8]) -> Result<Self, RRDataError> {
+        if buf.len() < 2 {
+            return Err(RRDataError("truncated CAA record".to_string()));
+        }
+
+        let flags = buf[0];
+
+        let tag_length = buf[1] as usize;
+        if buf.len() < 2+tag_length {
+            return Err(RRDataError("truncated CAA tag".to_string()));
+        }
+
+        let tag = String::from_utf8(buf[2..2+tag_length].to_vec())
+            .map_err(|e| RRDataError(e.to_string()))?;
+        validate_tag(&tag).map_err(RRDataError)?;
+
+        let value = buf[2+tag_length..].to_vec();
+
+        Ok(Self {
+            flags,
+            tag: Some(tag),
+            value
+        })
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, RRDataError> {
+        let tag = self.tag.as_ref().ok_or_else(|| RRDataError("tag param was not set".to_string()))?;
+        validate_tag(tag).map_err(RRDataError)?;
+
+        let mut buf = Vec::with_capacity(2+tag.len()+self.value.len());
+
+        buf.push(self.flags);
+        buf.push(tag.len() as u8);
+        buf.extend_from_slice(tag.as_bytes());
+        buf.extend_from_slice(&self.value);
+
+        Ok(buf)
+    }
+
+    fn upcast(self) -> Box<dyn RRData> {
+        Box::new(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn RRData> {
+        Box::new(self.clone())
+    }
+
+    fn eq_box(&self, other: &dyn RRData) -> bool {
+        other.as_any().downcast_ref::<Self>().map_or(false, |o| self == o)
+    }
+}
+
+impl CaaRRData {
+
+    pub fn new(flags: u8, tag: &str, value: &[u8]) -> Self {
+        Self {
+            flags,
+            tag: Some(tag.to_string()),
+            value: value.to_vec()
+        }
+    }
+
+    pub fn set_flags(&mut self, flags: u8) {
+        self.flags = flags;
+    }
+
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    pub fn set_issuer_critical(&mut self, issuer_critical: bool) {
+        match issuer_critical {
+            true => self.flags |= CAA_ISSUER_CRITICAL,
+            false => self.flags &= !CAA_ISSUER_CRITICAL
+        }
+    }
+
+    pub fn is_issuer_critical(&self) -> bool {
+        self.flags & CAA_ISSUER_CRITICAL != 0
+    }
+
+    pub fn set_tag(&mut self, tag: &str) {
+        self.tag = Some(tag.to_string());
+    }
+
+    pub fn tag(&self) -> Option<&String> {
+        self.tag.as_ref()
+    }
+
+    pub fn set_value(&mut self, value: &[u8]) {
+        self.value = value.to_vec();
+    }
+
+    pub fn value(&self) -> &[u8] {
+        self.value.as_ref()
+    }
+
+    pub fn is_issue(&self) -> bool {
+        self.tag.as_ref().map_or(false, |t| t.eq_ignore_ascii_case("issue"))
+    }
+
+    pub fn is_issuewild(&self) -> bool {
+        self.tag.as_ref().map_or(false, |t| t.eq_ignore_ascii_case("issuewild"))
+    }
+
+    pub fn issuer(&self) -> Result<Option<String>, RRDataError> {
+        Ok(self.parse_issue_value()?.0)
+    }
+
+    pub fn parameters(&self) -> Result<Vec<(String, String)>, RRDataError> {
+        Ok(self.parse_issue_value()?.1)
+    }
+
+    pub fn iodef(&self) -> Result<String, RRDataError> {
+        if !self.tag.as_ref().map_or(false, |t| t.eq_ignore_ascii_case("iodef")) {
+            return Err(RRDataError("tag is not iodef".to_string()));
+        }
+
+        let url = std::str::from_utf8(&self.value).map_err(|e| RRDataError(e.to_string()))?;
+
+        if !(url.starts_with("mailto:") || url.starts_with("http:") || url.starts_with("https:")) {
+            return Err(RRDataError(format!("unsupported iodef url {}", url)));
+        }
+
+        Ok(url.to_string())
+    }
+
+    fn parse_issue_value(&self) -> Result<(Option<String>, Vec<(String, String)>), RRDataError> {
+        if !self.is_issue() && !self.is_issuewild() {
+            return Err(RRDataError("tag is not issue or issuewild".to_string()));
+        }
+
+        let value = std::str::from_utf8(&self.value).map_err(|e| RRDataError(e.to_string()))?;
+
+        let mut parts = value.split(';');
+
+        let issuer = parts.next().unwrap_or("").trim();
+        let issuer = match issuer.is_empty() {
+            true => None,
+            false => {
+                if !issuer.split('.').all(|l| !l.is_empty() && l.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')) {
+                    return Err(RRDataError(format!("invalid issuer domain {}", issuer)));
+                }
+
+                Some(issuer.to_string())
+            }
+        };
+
+        let mut parameters = Vec::new();
+
+        for part in parts {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+
+            let (key, val) = part.split_once('=')
+                .ok_or_else(|| RRDataError(format!("invalid parameter {}", part)))?;
+            let (key, val) = (key.trim(), val.trim());
+
+            if key.is_empty() || !key.bytes().all(|b| b.is_ascii_alphanumeric()) {
+                return Err(RRDataError(format!("invalid parameter tag {}", key)));
+            }
+
+            if val.bytes().any(|b| b.is_ascii_whitespace() || b == b';' || !(0x21..=0x7e).contains(&b)) {
+                return Err(RRDataError(format!("invalid parameter value {}", val)));
+            }
+
+            parameters.push((key.to_string(), val.to_string()));
+        }
+
+        Ok((issuer, parameters))
+    }
+}
+
+fn validate_tag(tag: &str) -> Result<(), String> {
+    if tag.is_empty() || tag.len() > 15 || !tag.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        return Err(format!("invalid CAA tag {}", tag));
+    }
+
+    Ok(())
+}
+
+impl FromWireLen for CaaRRData {
+
+    fn from_wire_len(context: &mut FromWireContext, len: u16) -> Result<Self, WireError> {
+        let flags = u8::from_wire(context)?;
+
+        let tag_length = u8::from_wire(context)? as usize;
+        let tag = String::from_utf8(context.take(tag_length)?.to_vec())
+            .map_err(|e| WireError::Format(e.to_string()))?;
+        validate_tag(&tag).map_err(WireError::Format)?;
+
+        let value = context.take((len as usize).checked_sub(2+tag_length)
+            .ok_or_else(|| WireError::Format("truncated CAA record".to_string()))?)?.to_vec();
+
+        Ok(Self {
+            flags,
+            tag: Some(tag),
+            value
+        })
+    }
+}
+
+impl ToWire for CaaRRData {
+
+    fn to_wire(&self, context: &mut ToWireContext) -> Result<(), WireError> {
+        let tag = self.tag.as_ref().ok_or_else(|| WireError::Format("tag param was not set".to_string()))?;
+        validate_tag(tag).map_err(WireError::Format)?;
+
+        self.flags.to_wire(context)?;
+        (tag.len() as u8).to_wire(context)?;
+        context.write(tag.as_bytes())?;
+
+        context.write(&self.value)
+    }
+}
+
+impl ZoneRRData for CaaRRData {
+
+    fn set_data(&mut self, index: usize, value: &str) -> Result<(), ZoneReaderError> {
+        Ok(match index {
+            0 => self.flags = value.parse().map_err(|_| ZoneReaderError::new(ErrorKind::Format, "unable to parse flags param for record type CAA"))?,
+            1 => {
+                validate_tag(value).map_err(|e| ZoneReaderError::new(ErrorKind::Format, &format!("{} for record type CAA", e)))?;
+                self.tag = Some(value.to_string());
+            }
+            2 => self.value = value.as_bytes().to_vec(),
+            _ => return Err(ZoneReaderError::new(ErrorKind::ExtraRRData, "extra record data found for record type CAA"))
+        })
+    }
+
+    fn upcast(self) -> Box<dyn ZoneRRData> {
+        Box::new(self)
+    }
+}
+
+impl fmt::Display for CaaRRData {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} \"{}\"", self.flags,
+               self.tag.as_ref().unwrap_or(&String::new()),
+               String::from_utf8_lossy(&self.value))
+    }
+}
+
+#[test]
+fn test() {
+    let buf = vec![ 0x80, 0x5, 0x69, 0x73, 0x73, 0x75, 0x65, 0x6c, 0x65, 0x74, 0x73, 0x65, 0x6e, 0x63, 0x72, 0x79, 0x70, 0x74, 0x2e, 0x6f, 0x72, 0x67, 0x3b, 0x20, 0x76, 0x61, 0x6c, 0x69, 0x64, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x6d, 0x65, 0x74, 0x68, 0x6f, 0x64, 0x73, 0x3d, 0x64, 0x6e, 0x73, 0x2d, 0x30, 0x31 ];
+    let record = CaaRRData::from_bytes(&buf).unwrap();
+    assert_eq!(buf, record.to_bytes().unwrap());
+    assert!(record.is_issuer_critical());
+    assert_eq!(record.issuer().unwrap(), Some("letsencrypt.org".to_string()));
+    assert_eq!(record.parameters().unwrap(), vec![("validationmethods".to_string(), "dns-01".to_string())]);
+
+    assert!(record.is_issue());
+    assert!(!record.is_issuewild());
+
+    let mut upper = buf.clone();
+    upper[2..7].copy_from_slice(b"ISSUE");
+    let upper_record = CaaRRData::from_bytes(&upper).unwrap();
+    assert_eq!(upper, upper_record.to_bytes().unwrap());
+    assert_eq!(record, upper_record);
+
+    let record = CaaRRData::new(0, "ISSUEWILD", b";");
+    assert!(record.is_issuewild());
+    assert!(!record.is_issue());
+    assert_eq!(record, CaaRRData::new(0, "issuewild", b";"));
+    assert_eq!(record.issuer().unwrap(), None);
+    assert!(CaaRRData::new(0, "bad-tag", b"").to_bytes().is_err());
+}
@@ -0,0 +1,142 @@
+use std::any::Any;
+use std::fmt;
+use std::fmt::Formatter;
+use crate::messages::wire::{FromWireContext, FromWireLen, ToWire, ToWireContext, WireError};
+use crate::rr_data::dnskey_rr_data::DnsKeyRRData;
+use crate::rr_data::inter::rr_data::{RRData, RRDataError};
+use crate::zone::inter::zone_rr_data::ZoneRRData;
+use crate::zone::zone_reader::ZoneReaderError;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CdnsKeyRRData {
+    dnskey: DnsKeyRRData
+}
+
+impl Default for CdnsKeyRRData {
+
+    fn default() -> Self {
+        Self {
+            dnskey: DnsKeyRRData::default()
+        }
+    }
+}
+
+impl RRData for CdnsKeyRRData {
+
+    fn from_bytes(buf: &[u8]) -> Result<Self, RRDataError> {
+        Ok(Self {
+            dnskey: DnsKeyRRData::from_bytes(buf)?
+        })
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, RRDataError> {
+        self.dnskey.to_bytes()
+    }
+
+    fn upcast(self) -> Box<dyn RRData> {
+        Box::new(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn RRData> {
+        Box::new(self.clone())
+    }
+
+    fn eq_box(&self, other: &dyn RRData) -> bool {
+        other.as_any().downcast_ref::<Self>().map_or(false, |o| self == o)
+    }
+}
+
+impl CdnsKeyRRData {
+
+    pub fn new(flags: u16, protocol: u8, algorithm: u8, public_key: Vec<u8>) -> Self {
+        Self {
+            dnskey: DnsKeyRRData::new(flags, protocol, algorithm, public_key)
+        }
+    }
+
+    pub fn set_flags(&mut self, flags: u16) {
+        self.dnskey.set_flags(flags);
+    }
+
+    pub fn flags(&self) -> u16 {
+        self.dnskey.flags()
+    }
+
+    pub fn set_protocol(&mut self, protocol: u8) {
+        self.dnskey.set_protocol(protocol);
+    }
+
+    pub fn protocol(&self) -> u8 {
+        self.dnskey.protocol()
+    }
+
+    pub fn set_algorithm(&mut self, algorithm: u8) {
+        self.dnskey.set_algorithm(algorithm);
+    }
+
+    pub fn algorithm(&self) -> u8 {
+        self.dnskey.algorithm()
+    }
+
+    pub fn set_public_key(&mut self, public_key: &[u8]) {
+        self.dnskey.set_public_key(public_key);
+    }
+
+    pub fn public_key(&self) -> &[u8] {
+        self.dnskey.public_key()
+    }
+
+    pub fn as_dnskey(&self) -> &DnsKeyRRData {
+        &self.dnskey
+    }
+}
+
+impl FromWireLen for CdnsKeyRRData {
+
+    fn from_wire_len(context: &mut FromWireContext, len: u16) -> Result<Self, WireError> {
+        Ok(Self {
+            dnskey: DnsKeyRRData::from_wire_len(context, len)?
+        })
+    }
+}
+
+impl ToWire for CdnsKeyRRData {
+
+    fn to_wire(&self, context: &mut ToWireContext) -> Result<(), WireError> {
+        self.dnskey.to_wire(context)
+    }
+}
+
+impl ZoneRRData for CdnsKeyRRData {
+
+    fn set_data(&mut self, index: usize, value: &str) -> Result<(), ZoneReaderError> {
+        ZoneRRData::set_data(&mut self.dnskey, index, value)
+    }
+
+    fn upcast(self) -> Box<dyn ZoneRRData> {
+        Box::new(self)
+    }
+}
+
+impl fmt::Display for CdnsKeyRRData {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.dnskey)
+    }
+}
+
+#[test]
+fn test() {
+    let buf = vec![ 0x1, 0x1, 0x3, 0xd, 0x99, 0xdb, 0x2c, 0xc1, 0x4c, 0xab, 0xdc, 0x33, 0xd6, 0xd7, 0x7d, 0xa6, 0x3a, 0x2f, 0x15, 0xf7, 0x11, 0x12, 0x58, 0x4f, 0x23, 0x4e, 0x8d, 0x1d, 0xc4, 0x28, 0xe3, 0x9e, 0x8a, 0x4a, 0x97, 0xe1 ];
+    let record = CdnsKeyRRData::from_bytes(&buf).unwrap();
+    assert_eq!(buf, record.to_bytes().unwrap());
+    assert!(CdnsKeyRRData::from_bytes(&buf[..2]).is_err());
+}
@@ -0,0 +1,142 @@
+use std::any::Any;
+use std::fmt;
+use std::fmt::Formatter;
+use crate::messages::wire::{FromWireContext, FromWireLen, ToWire, ToWireContext, WireError};
+use crate::rr_data::ds_rr_data::DsRRData;
+use crate::rr_data::inter::rr_data::{RRData, RRDataError};
+use crate::zone::inter::zone_rr_data::ZoneRRData;
+use crate::zone::zone_reader::ZoneReaderError;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CdsRRData {
+    ds: DsRRData
+}
+
+impl Default for CdsRRData {
+
+    fn default() -> Self {
+        Self {
+            ds: DsRRData::default()
+        }
+    }
+}
+
+impl RRData for CdsRRData {
+
+    fn from_bytes(buf: &[u8]) -> Result<Self, RRDataError> {
+        Ok(Self {
+            ds: DsRRData::from_bytes(buf)?
+        })
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, RRDataError> {
+        self.ds.to_bytes()
+    }
+
+    fn upcast(self) -> Box<dyn RRData> {
+        Box::new(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn RRData> {
+        Box::new(self.clone())
+    }
+
+    fn eq_box(&self, other: &dyn RRData) -> bool {
+        other.as_any().downcast_ref::<Self>().map_or(false, |o| self == o)
+    }
+}
+
+impl CdsRRData {
+
+    pub fn new(key_tag: u16, algorithm: u8, digest_type: u8, digest: &[u8]) -> Self {
+        Self {
+            ds: DsRRData::new(key_tag, algorithm, digest_type, digest)
+        }
+    }
+
+    pub fn set_key_tag(&mut self, key_tag: u16) {
+        self.ds.set_key_tag(key_tag);
+    }
+
+    pub fn key_tag(&self) -> u16 {
+        self.ds.key_tag()
+    }
+
+    pub fn set_algorithm(&mut self, algorithm: u8) {
+        self.ds.set_algorithm(algorithm);
+    }
+
+    pub fn algorithm(&self) -> u8 {
+        self.ds.algorithm()
+    }
+
+    pub fn set_digest_type(&mut self, digest_type: u8) {
+        self.ds.set_digest_type(digest_type);
+    }
+
+    pub fn digest_type(&self) -> u8 {
+        self.ds.digest_type()
+    }
+
+    pub fn set_digest(&mut self, digest: &[u8]) {
+        self.ds.set_digest(digest);
+    }
+
+    pub fn digest(&self) -> &[u8] {
+        self.ds.digest()
+    }
+
+    pub fn as_ds(&self) -> &DsRRData {
+        &self.ds
+    }
+}
+
+impl FromWireLen for CdsRRData {
+
+    fn from_wire_len(context: &mut FromWireContext, len: u16) -> Result<Self, WireError> {
+        Ok(Self {
+            ds: DsRRData::from_wire_len(context, len)?
+        })
+    }
+}
+
+impl ToWire for CdsRRData {
+
+    fn to_wire(&self, context: &mut ToWireContext) -> Result<(), WireError> {
+        self.ds.to_wire(context)
+    }
+}
+
+impl ZoneRRData for CdsRRData {
+
+    fn set_data(&mut self, index: usize, value: &str) -> Result<(), ZoneReaderError> {
+        ZoneRRData::set_data(&mut self.ds, index, value)
+    }
+
+    fn upcast(self) -> Box<dyn ZoneRRData> {
+        Box::new(self)
+    }
+}
+
+impl fmt::Display for CdsRRData {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ds)
+    }
+}
+
+#[test]
+fn test() {
+    let buf = vec![ 0x4f, 0x66, 0xd, 0x2, 0x8a, 0xc0, 0x71, 0x3d, 0x1b, 0x5c, 0xd1, 0x7a, 0x2c, 0x6d, 0x95, 0x3c, 0xb1, 0xd5, 0x56, 0x3a, 0x2b, 0x3f, 0x4e, 0x11, 0x8c, 0x17, 0x6a, 0xce, 0x7f, 0x9a, 0x1d, 0x4b, 0x52, 0x6e, 0x8, 0x93 ];
+    let record = CdsRRData::from_bytes(&buf).unwrap();
+    assert_eq!(buf, record.to_bytes().unwrap());
+    assert!(CdsRRData::from_bytes(&buf[..3]).is_err());
+}
@@ -0,0 +1,213 @@
+use std::any::Any;
+use std::fmt;
+use std::fmt::Formatter;
+use crate::messages::wire::{FromWire, FromWireContext, FromWireLen, ToWire, ToWireContext, WireError};
+use crate::rr_data::inter::rr_data::{RRData, RRDataError};
+use crate::utils::base64;
+use crate::zone::inter::zone_rr_data::ZoneRRData;
+use crate::zone::zone_reader::{ErrorKind, ZoneReaderError};
+
+const CERT_TYPES: [(u16, &str); 10] = [
+    (1, "PKIX"),
+    (2, "SPKI"),
+    (3, "PGP"),
+    (4, "IPKIX"),
+    (5, "ISPKI"),
+    (6, "IPGP"),
+    (7, "ACPKIX"),
+    (8, "IACPKIX"),
+    (253, "URI"),
+    (254, "OID")
+];
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CertRRData {
+    cert_type: u16,
+    key_tag: u16,
+    algorithm: u8,
+    certificate: Vec<u8>
+}
+
+impl Default for CertRRData {
+
+    fn default() -> Self {
+        Self {
+            cert_type: 0,
+            key_tag: 0,
+            algorithm: 0,
+            certificate: Vec::new()
+        }
+    }
+}
+
+impl RRData for CertRRData {
+
+    fn from_bytes(buf: &[u8]) -> Result<Self, RRDataError> {
+        if buf.len() < 5 {
+            return Err(RRDataError("truncated CERT record".to_string()));
+        }
+
+        let cert_type = u16::from_be_bytes([buf[0], buf[1]]);
+        let key_tag = u16::from_be_bytes([buf[2], buf[3]]);
+        let algorithm = buf[4];
+
+        let certificate = buf[5..buf.len()].to_vec();
+
+        Ok(Self {
+            cert_type,
+            key_tag,
+            algorithm,
+            certificate
+        })
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, RRDataError> {
+        let mut buf = Vec::with_capacity(5+self.certificate.len());
+
+        buf.extend_from_slice(&self.cert_type.to_be_bytes());
+        buf.extend_from_slice(&self.key_tag.to_be_bytes());
+        buf.push(self.algorithm);
+
+        buf.extend_from_slice(&self.certificate);
+
+        Ok(buf)
+    }
+
+    fn upcast(self) -> Box<dyn RRData> {
+        Box::new(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn RRData> {
+        Box::new(self.clone())
+    }
+
+    fn eq_box(&self, other: &dyn RRData) -> bool {
+        other.as_any().downcast_ref::<Self>().map_or(false, |o| self == o)
+    }
+}
+
+impl CertRRData {
+
+    pub fn new(cert_type: u16, key_tag: u16, algorithm: u8, certificate: &[u8]) -> Self {
+        Self {
+            cert_type,
+            key_tag,
+            algorithm,
+            certificate: certificate.to_vec()
+        }
+    }
+
+    pub fn set_cert_type(&mut self, cert_type: u16) {
+        self.cert_type = cert_type;
+    }
+
+    pub fn cert_type(&self) -> u16 {
+        self.cert_type
+    }
+
+    pub fn set_key_tag(&mut self, key_tag: u16) {
+        self.key_tag = key_tag;
+    }
+
+    pub fn key_tag(&self) -> u16 {
+        self.key_tag
+    }
+
+    pub fn set_algorithm(&mut self, algorithm: u8) {
+        self.algorithm = algorithm;
+    }
+
+    pub fn algorithm(&self) -> u8 {
+        self.algorithm
+    }
+
+    pub fn set_certificate(&mut self, certificate: &[u8]) {
+        self.certificate = certificate.to_vec();
+    }
+
+    pub fn certificate(&self) -> &[u8] {
+        self.certificate.as_ref()
+    }
+}
+
+impl FromWireLen for CertRRData {
+
+    fn from_wire_len(context: &mut FromWireContext, len: u16) -> Result<Self, WireError> {
+        let cert_type = u16::from_wire(context)?;
+        let key_tag = u16::from_wire(context)?;
+        let algorithm = u8::from_wire(context)?;
+
+        let certificate = context.take((len as usize).checked_sub(5)
+            .ok_or_else(|| WireError::Format("truncated CERT record".to_string()))?)?.to_vec();
+
+        Ok(Self {
+            cert_type,
+            key_tag,
+            algorithm,
+            certificate
+        })
+    }
+}
+
+impl ToWire for CertRRData {
+
+    fn to_wire(&self, context: &mut ToWireContext) -> Result<(), WireError> {
+        self.cert_type.to_wire(context)?;
+        self.key_tag.to_wire(context)?;
+        self.algorithm.to_wire(context)?;
+
+        context.write(&self.certificate)
+    }
+}
+
+impl ZoneRRData for CertRRData {
+
+    fn set_data(&mut self, index: usize, value: &str) -> Result<(), ZoneReaderError> {
+        Ok(match index {
+            0 => {
+                self.cert_type = match CERT_TYPES.iter().find(|(_, name)| name.eq_ignore_ascii_case(value)) {
+                    Some((code, _)) => *code,
+                    None => value.parse().map_err(|_| ZoneReaderError::new(ErrorKind::Format, "unable to parse cert_type param for record type CERT"))?
+                };
+            }
+            1 => self.key_tag = value.parse().map_err(|_| ZoneReaderError::new(ErrorKind::Format, "unable to parse key_tag param for record type CERT"))?,
+            2 => self.algorithm = value.parse().map_err(|_| ZoneReaderError::new(ErrorKind::Format, "unable to parse algorithm param for record type CERT"))?,
+            3 => self.certificate = base64::decode(value).map_err(|_| ZoneReaderError::new(ErrorKind::Format, "unable to parse certificate param for record type CERT"))?,
+            _ => return Err(ZoneReaderError::new(ErrorKind::ExtraRRData, "extra record data found for record type CERT"))
+        })
+    }
+
+    fn upcast(self) -> Box<dyn ZoneRRData> {
+        Box::new(self)
+    }
+}
+
+impl fmt::Display for CertRRData {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let cert_type = match CERT_TYPES.iter().find(|(code, _)| *code == self.cert_type) {
+            Some((_, name)) => name.to_string(),
+            None => self.cert_type.to_string()
+        };
+
+        write!(f, "{} {} {} {}", cert_type,
+               self.key_tag,
+               self.algorithm,
+               base64::encode(&self.certificate))
+    }
+}
+
+#[test]
+fn test() {
+    let buf = vec![ 0x0, 0x3, 0x0, 0x0, 0x0, 0x99, 0x1, 0xd, 0x4, 0x5c, 0x2f, 0x13, 0x8a, 0x1, 0x8, 0x0, 0xc3, 0x6a, 0x91, 0x2e, 0x7f ];
+    let record = CertRRData::from_bytes(&buf).unwrap();
+    assert_eq!(buf, record.to_bytes().unwrap());
+}
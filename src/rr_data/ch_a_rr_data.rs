@@ -111,7 +111,7 @@ impl FromWireLen for ChARRData {
 impl ToWire for ChARRData {
 
     fn to_wire(&self, context: &mut ToWireContext) -> Result<(), WireError> {
-        context.write_name(self.network.as_ref()
+        context.write_name_preserve_case(self.network.as_ref()
             .ok_or_else(|| WireError::Format("network param was not set".to_string()))?, true)?;
         self.address.to_wire(context)
     }
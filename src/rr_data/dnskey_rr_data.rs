@@ -8,6 +8,10 @@ use crate::utils::base64;
 use crate::zone::inter::zone_rr_data::ZoneRRData;
 use crate::zone::zone_reader::{ErrorKind, ZoneReaderError};
 
+pub const DNSKEY_ZONE_KEY: u16 = 0x0100;
+pub const DNSKEY_REVOKE: u16 = 0x0080;
+pub const DNSKEY_SECURE_ENTRY_POINT: u16 = 0x0001;
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DnsKeyRRData {
     flags: u16,
@@ -31,6 +35,10 @@ impl Default for DnsKeyRRData {
 impl RRData for DnsKeyRRData {
 
     fn from_bytes(buf: &[u8]) -> Result<Self, RRDataError> {
+        if buf.len() < 4 {
+            return Err(RRDataError("truncated DNSKEY record".to_string()));
+        }
+
         let flags = u16::from_be_bytes([buf[0], buf[1]]);
         /*
         Flags: 0x0100
@@ -128,6 +136,46 @@ impl DnsKeyRRData {
     pub fn public_key(&self) -> &[u8] {
         &self.public_key
     }
+
+    pub fn is_zone_key(&self) -> bool {
+        self.flags & DNSKEY_ZONE_KEY != 0
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.flags & DNSKEY_REVOKE != 0
+    }
+
+    pub fn is_secure_entry_point(&self) -> bool {
+        self.flags & DNSKEY_SECURE_ENTRY_POINT != 0
+    }
+
+    pub fn key_tag(&self) -> u16 {
+        if self.algorithm == 1 {
+            let len = self.public_key.len();
+            if len < 3 {
+                return 0;
+            }
+
+            return u16::from_be_bytes([self.public_key[len-3], self.public_key[len-2]]);
+        }
+
+        let mut buf = Vec::with_capacity(4+self.public_key.len());
+        buf.extend_from_slice(&self.flags.to_be_bytes());
+        buf.push(self.protocol);
+        buf.push(self.algorithm);
+        buf.extend_from_slice(&self.public_key);
+
+        let mut ac = 0u32;
+        for (i, b) in buf.iter().enumerate() {
+            ac += match i & 1 {
+                0 => (*b as u32) << 8,
+                _ => *b as u32
+            };
+        }
+
+        ac += (ac >> 16) & 0xffff;
+        (ac & 0xffff) as u16
+    }
 }
 
 impl FromWireLen for DnsKeyRRData {
@@ -145,7 +193,8 @@ impl FromWireLen for DnsKeyRRData {
         let protocol = u8::from_wire(context)?;
         let algorithm = u8::from_wire(context)?;
 
-        let public_key = context.take(len as usize - 4)?.to_vec();
+        let public_key = context.take((len as usize).checked_sub(4)
+            .ok_or_else(|| WireError::Format("truncated DNSKEY record".to_string()))?)?.to_vec();
 
         Ok(Self {
             flags,
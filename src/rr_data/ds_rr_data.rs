@@ -1,7 +1,7 @@
 use std::any::Any;
 use std::fmt;
 use std::fmt::Formatter;
-use crate::messages::wire::{FromWireContext, FromWireLen, ToWire, ToWireContext, WireError};
+use crate::messages::wire::{FromWire, FromWireContext, FromWireLen, ToWire, ToWireContext, WireError};
 use crate::rr_data::inter::rr_data::{RRData, RRDataError};
 use crate::utils::hex;
 use crate::zone::inter::zone_rr_data::ZoneRRData;
@@ -30,10 +30,15 @@ impl Default for DsRRData {
 impl RRData for DsRRData {
 
     fn from_bytes(buf: &[u8]) -> Result<Self, RRDataError> {
-        let key_tag = 0;
-        let algorithm = 0;
-        let digest_type = 0;
-        let digest = Vec::new();
+        if buf.len() < 4 {
+            return Err(RRDataError("truncated DS record".to_string()));
+        }
+
+        let key_tag = u16::from_be_bytes([buf[0], buf[1]]);
+        let algorithm = buf[2];
+        let digest_type = buf[3];
+
+        let digest = buf[4..buf.len()].to_vec();
 
         Ok(Self {
             key_tag,
@@ -44,7 +49,13 @@ impl RRData for DsRRData {
     }
 
     fn to_bytes(&self) -> Result<Vec<u8>, RRDataError> {
-        let mut buf = Vec::with_capacity(62);
+        let mut buf = Vec::with_capacity(36);
+
+        buf.extend_from_slice(&self.key_tag.to_be_bytes());
+        buf.push(self.algorithm);
+        buf.push(self.digest_type);
+
+        buf.extend_from_slice(&self.digest);
 
         Ok(buf)
     }
@@ -80,19 +91,67 @@ impl DsRRData {
             digest: digest.to_vec()
         }
     }
+
+    pub fn set_key_tag(&mut self, key_tag: u16) {
+        self.key_tag = key_tag;
+    }
+
+    pub fn key_tag(&self) -> u16 {
+        self.key_tag
+    }
+
+    pub fn set_algorithm(&mut self, algorithm: u8) {
+        self.algorithm = algorithm;
+    }
+
+    pub fn algorithm(&self) -> u8 {
+        self.algorithm
+    }
+
+    pub fn set_digest_type(&mut self, digest_type: u8) {
+        self.digest_type = digest_type;
+    }
+
+    pub fn digest_type(&self) -> u8 {
+        self.digest_type
+    }
+
+    pub fn set_digest(&mut self, digest: &[u8]) {
+        self.digest = digest.to_vec();
+    }
+
+    pub fn digest(&self) -> &[u8] {
+        self.digest.as_ref()
+    }
 }
 
 impl FromWireLen for DsRRData {
 
-    fn from_wire_len(context: &mut FromWireContext, _len: u16) -> Result<Self, WireError> {
-        todo!()
+    fn from_wire_len(context: &mut FromWireContext, len: u16) -> Result<Self, WireError> {
+        let key_tag = u16::from_wire(context)?;
+        let algorithm = u8::from_wire(context)?;
+        let digest_type = u8::from_wire(context)?;
+
+        let digest = context.take((len as usize).checked_sub(4)
+            .ok_or_else(|| WireError::Format("truncated DS record".to_string()))?)?.to_vec();
+
+        Ok(Self {
+            key_tag,
+            algorithm,
+            digest_type,
+            digest
+        })
     }
 }
 
 impl ToWire for DsRRData {
 
     fn to_wire(&self, context: &mut ToWireContext) -> Result<(), WireError> {
-        todo!()
+        self.key_tag.to_wire(context)?;
+        self.algorithm.to_wire(context)?;
+        self.digest_type.to_wire(context)?;
+
+        context.write(&self.digest)
     }
 }
 
@@ -125,7 +184,10 @@ impl fmt::Display for DsRRData {
 
 #[test]
 fn test() {
-    let buf = vec![ ];
+    let buf = vec![ 0x4f, 0x66, 0xd, 0x2, 0x8a, 0xc0, 0x71, 0x3d, 0x1b, 0x5c, 0xd1, 0x7a, 0x2c, 0x6d, 0x95, 0x3c, 0xb1, 0xd5, 0x56, 0x3a, 0x2b, 0x3f, 0x4e, 0x11, 0x8c, 0x17, 0x6a, 0xce, 0x7f, 0x9a, 0x1d, 0x4b, 0x52, 0x6e, 0x8, 0x93 ];
     let record = DsRRData::from_bytes(&buf).unwrap();
     assert_eq!(buf, record.to_bytes().unwrap());
+
+    assert!(DsRRData::from_bytes(&buf[..3]).is_err());
+    assert!(DsRRData::from_wire_len(&mut FromWireContext::new(&buf[..3]), 3).is_err());
 }
@@ -2,7 +2,14 @@ use crate::rr_data::{
     in_a_rr_data::InARRData,
     ch_a_rr_data::ChARRData,
     aaaa_rr_data::AaaaRRData,
+    caa_rr_data::CaaRRData,
+    cert_rr_data::CertRRData,
+    tlsa_rr_data::TlsaRRData,
+    cds_rr_data::CdsRRData,
+    cdnskey_rr_data::CdnsKeyRRData,
+    openpgpkey_rr_data::OpenPgpKeyRRData,
     cname_rr_data::CNameRRData,
+    dname_rr_data::DNameRRData,
     dnskey_rr_data::DnsKeyRRData,
     ds_rr_data::DsRRData,
     hinfo_rr_data::HInfoRRData,
@@ -15,6 +22,7 @@ use crate::rr_data::{
     nsec3_rr_data::NSec3RRData,
     nsec3param_rr_data::NSec3ParamRRData,
     ptr_rr_data::PtrRRData,
+    raw_rr_data::RawRRData,
     rrsig_rr_data::RRSigRRData,
     smimea_rr_data::SmimeaRRData,
     soa_rr_data::SoaRRData,
@@ -24,7 +32,8 @@ use crate::rr_data::{
     sshfp_rr_data::SshFpRRData,
     svcb_rr_data::SvcbRRData,
     txt_rr_data::TxtRRData,
-    uri_rr_data::UriRRData
+    uri_rr_data::UriRRData,
+    zonemd_rr_data::ZoneMdRRData
 };
 
 use std::any::Any;
@@ -32,7 +41,7 @@ use std::fmt;
 use std::fmt::{Debug, Display, Formatter};
 use crate::messages::inter::rr_classes::RRClasses;
 use crate::messages::inter::rr_types::RRTypes;
-use crate::messages::wire::{FromWireContext, FromWireLen, ToWire, WireError};
+use crate::messages::wire::{FromWireContext, FromWireLen, ToWire, ToWireContext, WireError};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct RRDataError(pub String);
@@ -59,6 +68,12 @@ pub trait RRData: Display + Debug + Send + Sync + FromWireLen + ToWire {
     fn clone_box(&self) -> Box<dyn RRData>;
 
     fn eq_box(&self, other: &dyn RRData) -> bool;
+
+    fn to_canonical_bytes(&self) -> Result<Vec<u8>, WireError> {
+        let mut context = ToWireContext::canonical_with_capacity(u16::MAX as usize);
+        self.to_wire(&mut context)?;
+        Ok(context.into_bytes())
+    }
 }
 
 impl Clone for Box<dyn RRData> {
@@ -90,6 +105,7 @@ impl dyn RRData {
             RRTypes::Aaaa   => AaaaRRData::default().upcast(),
             RRTypes::Ns     => NsRRData::default().upcast(),
             RRTypes::CName  => CNameRRData::default().upcast(),
+            RRTypes::DName  => DNameRRData::default().upcast(),
             RRTypes::Soa    => SoaRRData::default().upcast(),
             RRTypes::Ptr    => PtrRRData::default().upcast(),
             RRTypes::HInfo  => HInfoRRData::default().upcast(),
@@ -115,13 +131,14 @@ impl dyn RRData {
             RRTypes::TKey   => TKeyRRData::default().upcast(),
             RRTypes::TSig   => TSigRRData::default().upcast(),
             RRTypes::Uri    => UriRRData::default().upcast(),
-            /*RRTypes::Caa => {
-                todo!()
-            }
-            _ => {
-                todo!()
-            }
-            */
+            RRTypes::Caa    => CaaRRData::default().upcast(),
+            RRTypes::Cert   => CertRRData::default().upcast(),
+            RRTypes::Tlsa   => TlsaRRData::default().upcast(),
+            RRTypes::Cds    => CdsRRData::default().upcast(),
+            RRTypes::CdnsKey=> CdnsKeyRRData::default().upcast(),
+            RRTypes::OpenPGPKey =>  OpenPgpKeyRRData::default().upcast(),
+            RRTypes::ZoneMd => ZoneMdRRData::default().upcast(),
+            RRTypes::Unknown(_) | RRTypes::Hip | RRTypes::Spf => RawRRData::default().upcast(),
             // pseudo/unsupported types:
             _ => return None
         })
@@ -138,6 +155,7 @@ impl dyn RRData {
             RRTypes::Aaaa   => AaaaRRData::from_bytes(buf)?.upcast(),
             RRTypes::Ns     => NsRRData::from_bytes(buf)?.upcast(),
             RRTypes::CName  => CNameRRData::from_bytes(buf)?.upcast(),
+            RRTypes::DName  => DNameRRData::from_bytes(buf)?.upcast(),
             RRTypes::Soa    => SoaRRData::from_bytes(buf)?.upcast(),
             RRTypes::Ptr    => PtrRRData::from_bytes(buf)?.upcast(),
             RRTypes::HInfo  => HInfoRRData::from_bytes(buf)?.upcast(),
@@ -163,13 +181,14 @@ impl dyn RRData {
             RRTypes::TKey   => TKeyRRData::from_bytes(buf)?.upcast(),
             RRTypes::TSig   => TSigRRData::from_bytes(buf)?.upcast(),
             RRTypes::Uri    => UriRRData::from_bytes(buf)?.upcast(),
-            /*RRTypes::Caa => {
-                todo!()
-            }
-            _ => {
-                todo!()
-            }
-            */
+            RRTypes::Caa    => CaaRRData::from_bytes(buf)?.upcast(),
+            RRTypes::Cert   => CertRRData::from_bytes(buf)?.upcast(),
+            RRTypes::Tlsa   => TlsaRRData::from_bytes(buf)?.upcast(),
+            RRTypes::Cds    => CdsRRData::from_bytes(buf)?.upcast(),
+            RRTypes::CdnsKey=> CdnsKeyRRData::from_bytes(buf)?.upcast(),
+            RRTypes::OpenPGPKey =>  OpenPgpKeyRRData::from_bytes(buf)?.upcast(),
+            RRTypes::ZoneMd => ZoneMdRRData::from_bytes(buf)?.upcast(),
+            RRTypes::Unknown(_) | RRTypes::Hip | RRTypes::Spf => RawRRData::from_bytes(buf)?.upcast(),
             // pseudo/unsupported types:
             _ => return Err(RRDataError("invalid rr_type".to_string()))
         })
@@ -186,6 +205,7 @@ impl dyn RRData {
             RRTypes::Aaaa   => AaaaRRData::from_wire_len(context, len)?.upcast(),
             RRTypes::Ns     => NsRRData::from_wire_len(context, len)?.upcast(),
             RRTypes::CName  => CNameRRData::from_wire_len(context, len)?.upcast(),
+            RRTypes::DName  => DNameRRData::from_wire_len(context, len)?.upcast(),
             RRTypes::Soa    => SoaRRData::from_wire_len(context, len)?.upcast(),
             RRTypes::Ptr    => PtrRRData::from_wire_len(context, len)?.upcast(),
             RRTypes::HInfo  => HInfoRRData::from_wire_len(context, len)?.upcast(),
@@ -211,13 +231,14 @@ impl dyn RRData {
             RRTypes::TKey   => TKeyRRData::from_wire_len(context, len)?.upcast(),
             RRTypes::TSig   => TSigRRData::from_wire_len(context, len)?.upcast(),
             RRTypes::Uri    => UriRRData::from_wire_len(context, len)?.upcast(),
-            /*RRTypes::Caa => {
-                todo!()
-            }
-            _ => {
-                todo!()
-            }
-            */
+            RRTypes::Caa    => CaaRRData::from_wire_len(context, len)?.upcast(),
+            RRTypes::Cert   => CertRRData::from_wire_len(context, len)?.upcast(),
+            RRTypes::Tlsa   => TlsaRRData::from_wire_len(context, len)?.upcast(),
+            RRTypes::Cds    => CdsRRData::from_wire_len(context, len)?.upcast(),
+            RRTypes::CdnsKey=> CdnsKeyRRData::from_wire_len(context, len)?.upcast(),
+            RRTypes::OpenPGPKey =>  OpenPgpKeyRRData::from_wire_len(context, len)?.upcast(),
+            RRTypes::ZoneMd => ZoneMdRRData::from_wire_len(context, len)?.upcast(),
+            RRTypes::Unknown(_) | RRTypes::Hip | RRTypes::Spf => RawRRData::from_wire_len(context, len)?.upcast(),
             // pseudo/unsupported types:
             _ => return Err(WireError::Format("invalid rr_type".to_string()))
         })
@@ -3,6 +3,7 @@ pub mod in_a_rr_data;
 pub mod ch_a_rr_data;
 pub mod aaaa_rr_data;
 pub mod cname_rr_data;
+pub mod dname_rr_data;
 pub mod dnskey_rr_data;
 pub mod ds_rr_data;
 pub mod svcb_rr_data;
@@ -26,3 +27,11 @@ pub mod uri_rr_data;
 pub mod loc_rr_data;
 pub mod sshfp_rr_data;
 pub mod smimea_rr_data;
+pub mod caa_rr_data;
+pub mod tlsa_rr_data;
+pub mod openpgpkey_rr_data;
+pub mod cds_rr_data;
+pub mod cdnskey_rr_data;
+pub mod cert_rr_data;
+pub mod zonemd_rr_data;
+pub mod raw_rr_data;
@@ -5,10 +5,13 @@ use std::str::FromStr;
 use crate::messages::inter::rr_types::RRTypes;
 use crate::messages::wire::{FromWire, FromWireContext, FromWireLen, ToWire, ToWireContext, WireError};
 use crate::rr_data::inter::rr_data::{RRData, RRDataError};
+use crate::rr_data::nsec_rr_data::{decode_type_bitmap, encode_type_bitmap};
 use crate::utils::{base32, hex};
 use crate::zone::inter::zone_rr_data::ZoneRRData;
 use crate::zone::zone_reader::{ErrorKind, ZoneReaderError};
 
+pub const NSEC3_OPT_OUT: u8 = 0x01;
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct NSec3RRData {
     algorithm: u8,
@@ -36,50 +39,28 @@ impl Default for NSec3RRData {
 impl RRData for NSec3RRData {
 
     fn from_bytes(buf: &[u8]) -> Result<Self, RRDataError> {
+        if buf.len() < 5 {
+            return Err(RRDataError("truncated NSEC3 record".to_string()));
+        }
+
         let algorithm = buf[0];
         let flags = buf[1];
         let iterations = u16::from_be_bytes([buf[2], buf[3]]);
 
         let salt_length = buf[4] as usize;
-        let salt = buf[5..5 + salt_length].to_vec();
-
-        let mut i = 5+salt_length;
-        let next_hash_length = buf[i+1] as usize;
-        let next_hash = buf[i+1..i+1+next_hash_length].to_vec();
-        i += 1+next_hash_length;
-
-
-        let mut types = Vec::new();
-
-        while i < buf.len() {
-            if i+2 > buf.len() {
-                return Err(RRDataError("truncated NSEC window header".to_string()));
-            }
-
-            let window = buf[i];
-            let data_length = buf[i+1] as usize;
-            i += 2;
-
-            if data_length == 0 || data_length > 32 {
-                return Err(RRDataError("invalid NSEC window length".to_string()));
-            }
-
-            if i+data_length > buf.len() {
-                return Err(RRDataError("truncated NSEC bitmap".to_string()));
-            }
-
-            for (i, &byte) in buf[i..i+data_length].iter().enumerate() {
-                for bit in 0..8 {
-                    if (byte & (1 << (7 - bit))) != 0 {
-                        let _type = RRTypes::try_from((window as u16) * 256 + (i as u16 * 8 + bit as u16))
-                            .map_err(|e| RRDataError(e.to_string()))?;
-                        types.push(_type);
-                    }
-                }
-            }
+        if buf.len() < 6+salt_length {
+            return Err(RRDataError("truncated NSEC3 salt".to_string()));
+        }
+        let salt = buf[5..5+salt_length].to_vec();
 
-            i += data_length;
+        let next_hash_length = buf[5+salt_length] as usize;
+        let i = 6+salt_length;
+        if buf.len() < i+next_hash_length {
+            return Err(RRDataError("truncated NSEC3 next hash".to_string()));
         }
+        let next_hash = buf[i..i+next_hash_length].to_vec();
+
+        let types = decode_type_bitmap(&buf[i+next_hash_length..]).map_err(RRDataError)?;
 
         Ok(Self {
             algorithm,
@@ -94,6 +75,18 @@ impl RRData for NSec3RRData {
     fn to_bytes(&self) -> Result<Vec<u8>, RRDataError> {
         let mut buf = Vec::with_capacity(126);
 
+        buf.push(self.algorithm);
+        buf.push(self.flags);
+        buf.extend_from_slice(&self.iterations.to_be_bytes());
+
+        buf.push(self.salt.len() as u8);
+        buf.extend_from_slice(&self.salt);
+
+        buf.push(self.next_hash.len() as u8);
+        buf.extend_from_slice(&self.next_hash);
+
+        buf.extend_from_slice(&encode_type_bitmap(&self.types));
+
         Ok(buf)
     }
 
@@ -147,6 +140,17 @@ impl NSec3RRData {
         self.flags
     }
 
+    pub fn set_opt_out(&mut self, opt_out: bool) {
+        match opt_out {
+            true => self.flags |= NSEC3_OPT_OUT,
+            false => self.flags &= !NSEC3_OPT_OUT
+        }
+    }
+
+    pub fn is_opt_out(&self) -> bool {
+        self.flags & NSEC3_OPT_OUT != 0
+    }
+
     pub fn set_iterations(&mut self, iterations: u16) {
         self.iterations = iterations;
     }
@@ -197,40 +201,9 @@ impl FromWireLen for NSec3RRData {
         let next_hash_length = u8::from_wire(context)? as usize;
         let next_hash = context.take(next_hash_length)?.to_vec();
 
-        let mut types = Vec::new();
-
-        /*
-        let mut i = len-6-salt_length as u16-next_hash_length as u16;
-        while i < len {
-            if i+2 > len {
-                return Err(RRDataError("truncated NSEC window header".to_string()));
-            }
-
-            let window = buf[off];
-            let data_length = buf[off + 1] as usize;
-            off += 2;
-
-            if data_length == 0 || data_length > 32 {
-                return Err(RRDataError("invalid NSEC window length".to_string()));
-            }
-
-            if off + data_length > len {
-                return Err(RRDataError("truncated NSEC bitmap".to_string()));
-            }
-
-            for (i, &byte) in buf[off..off + data_length].iter().enumerate() {
-                for bit in 0..8 {
-                    if (byte & (1 << (7 - bit))) != 0 {
-                        let _type = RRTypes::try_from((window as u16) * 256 + (i as u16 * 8 + bit as u16))
-                            .map_err(|e| RRDataError(e.to_string()))?;
-                        types.push(_type);
-                    }
-                }
-            }
-
-            off += data_length;
-        }
-        */
+        let types = decode_type_bitmap(context.take((len as usize).checked_sub(6+salt_length+next_hash_length)
+            .ok_or_else(|| WireError::Format("truncated NSEC3 record".to_string()))?)?)
+            .map_err(WireError::Format)?;
 
         Ok(Self {
             algorithm,
@@ -246,7 +219,17 @@ impl FromWireLen for NSec3RRData {
 impl ToWire for NSec3RRData {
 
     fn to_wire(&self, context: &mut ToWireContext) -> Result<(), WireError> {
-        todo!()
+        self.algorithm.to_wire(context)?;
+        self.flags.to_wire(context)?;
+        self.iterations.to_wire(context)?;
+
+        (self.salt.len() as u8).to_wire(context)?;
+        context.write(&self.salt)?;
+
+        (self.next_hash.len() as u8).to_wire(context)?;
+        context.write(&self.next_hash)?;
+
+        context.write(&encode_type_bitmap(&self.types))
     }
 }
 
@@ -276,10 +259,11 @@ impl ZoneRRData for NSec3RRData {
 impl fmt::Display for NSec3RRData {
 
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {} {} {} {}", self.algorithm,
+        write!(f, "{} {} {} {} {} {}", self.algorithm,
                self.flags,
                self.iterations,
-               base32::hex_encode_nopad(&self.salt),
+               if self.salt.is_empty() { "-".to_string() } else { hex::encode(&self.salt) },
+               base32::hex_encode_nopad(&self.next_hash),
                self.types.iter()
                    .map(|t| t.to_string())
                    .collect::<Vec<_>>()
@@ -289,7 +273,14 @@ impl fmt::Display for NSec3RRData {
 
 #[test]
 fn test() {
-    let buf = vec![ ];
+    let buf = vec![ 0x1, 0x1, 0x0, 0x0, 0x4, 0xaa, 0xbb, 0xcc, 0xdd, 0x14, 0x2d, 0x6d, 0x96, 0x4d, 0x29, 0xb6, 0x59, 0x34, 0xc4, 0xe0, 0x8a, 0x21, 0x2d, 0xb2, 0xc4, 0x65, 0x7a, 0x0f, 0x86, 0xb6, 0x0, 0x7, 0x62, 0x0, 0x0, 0x0, 0x0, 0x2, 0x90 ];
     let record = NSec3RRData::from_bytes(&buf).unwrap();
     assert_eq!(buf, record.to_bytes().unwrap());
+    assert!(record.is_opt_out());
+    assert_eq!(record.types(), &vec![RRTypes::A, RRTypes::Ns, RRTypes::Soa, RRTypes::RRSig, RRTypes::DnsKey, RRTypes::NSec3Param]);
+
+    let mut context = ToWireContext::with_capacity(512);
+    record.to_wire(&mut context).unwrap();
+    assert_eq!(buf, context.to_bytes());
+    assert_eq!(record, NSec3RRData::from_wire_len(&mut FromWireContext::new(&buf), buf.len() as u16).unwrap());
 }
@@ -161,7 +161,11 @@ impl ZoneRRData for NSec3ParamRRData {
             0 => self.algorithm = value.parse().map_err(|_| ZoneReaderError::new(ErrorKind::Format, "unable to parse algorithm param for record type NSEC3PARAM"))?,
             1 => self.flags = value.parse().map_err(|_| ZoneReaderError::new(ErrorKind::Format, "unable to parse flags param for record type NSEC3PARAM"))?,
             2 => self.iterations = value.parse().map_err(|_| ZoneReaderError::new(ErrorKind::Format, "unable to parse iterations param for record type NSEC3PARAM"))?,
-            3 => self.salt = hex::decode(value).map_err(|_| ZoneReaderError::new(ErrorKind::Format, "unable to parse salt param for record type NSEC3PARAM"))?,
+            3 => {
+                if !value.eq("-") {
+                    self.salt = hex::decode(value).map_err(|_| ZoneReaderError::new(ErrorKind::Format, "unable to parse salt param for record type NSEC3PARAM"))?
+                }
+            }
             _ => return Err(ZoneReaderError::new(ErrorKind::ExtraRRData, "extra record data found for record type NSEC3PARAM"))
         })
     }
@@ -177,7 +181,7 @@ impl fmt::Display for NSec3ParamRRData {
         write!(f, "{} {} {} {}", self.algorithm,
                self.flags,
                self.iterations,
-               hex::encode(&self.salt))
+               if self.salt.is_empty() { "-".to_string() } else { hex::encode(&self.salt) })
     }
 }
 
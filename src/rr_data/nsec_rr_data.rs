@@ -30,38 +30,7 @@ impl RRData for NSecRRData {
     fn from_bytes(buf: &[u8]) -> Result<Self, RRDataError> {
         let (next_domain, next_domain_length) = unpack_fqdn(buf, 0);
 
-        let mut i = next_domain_length;
-        let mut types = Vec::new();
-
-        while i < buf.len() {
-            if i+2 > buf.len() {
-                return Err(RRDataError("truncated NSEC window header".to_string()));
-            }
-
-            let window = buf[i];
-            let data_length = buf[i+1] as usize;
-            i += 2;
-
-            if data_length == 0 || data_length > 32 {
-                return Err(RRDataError("invalid NSEC window length".to_string()));
-            }
-
-            if i+data_length > buf.len() {
-                return Err(RRDataError("truncated NSEC bitmap".to_string()));
-            }
-
-            for (i, &byte) in buf[i..i+data_length].iter().enumerate() {
-                for bit in 0..8 {
-                    if (byte & (1 << (7 - bit))) != 0 {
-                        let _type = RRTypes::try_from((window as u16) * 256 + (i as u16 * 8 + bit as u16))
-                            .map_err(|e| RRDataError(e.to_string()))?;
-                        types.push(_type);
-                    }
-                }
-            }
-
-            i += data_length;
-        }
+        let types = decode_type_bitmap(&buf[next_domain_length..]).map_err(RRDataError)?;
 
         Ok(Self {
             next_domain: Some(next_domain),
@@ -73,37 +42,9 @@ impl RRData for NSecRRData {
         let mut buf = Vec::with_capacity(94);
 
         buf.extend_from_slice(&pack_fqdn(self.next_domain.as_ref()
-            .ok_or_else(|| RRDataError("mailbox param was not set".to_string()))?));
+            .ok_or_else(|| RRDataError("next_domain param was not set".to_string()))?));
 
-        let mut windows: Vec<Vec<u8>> = vec![Vec::new(); 256];
-
-        for _type in self.types.iter() {
-            let code = _type.code();
-            let w = (code >> 8) as usize;
-            let low = (code & 0xFF) as u8;
-            let byte_i = (low >> 3) as usize;
-            let bit_in_byte = 7 - (low & 0x07);
-
-            let bm = &mut windows[w];
-            if bm.len() <= byte_i {
-                bm.resize(byte_i + 1, 0);
-            }
-            bm[byte_i] |= 1 << bit_in_byte;
-        }
-
-        for (win, bm) in windows.into_iter().enumerate() {
-            let mut used = bm.len();
-            while used > 0 && bm[used - 1] == 0 {
-                used -= 1;
-            }
-            if used == 0 {
-                continue;
-            }
-
-            buf.push(win as u8);
-            buf.push(used as u8);
-            buf.extend_from_slice(&bm[..used]);
-        }
+        buf.extend_from_slice(&encode_type_bitmap(&self.types));
 
         Ok(buf)
     }
@@ -159,17 +100,102 @@ impl NSecRRData {
     }
 }
 
+pub(crate) fn decode_type_bitmap(buf: &[u8]) -> Result<Vec<RRTypes>, String> {
+    let mut i = 0;
+    let mut types = Vec::new();
+
+    while i < buf.len() {
+        if i+2 > buf.len() {
+            return Err("truncated type bitmap window header".to_string());
+        }
+
+        let window = buf[i];
+        let data_length = buf[i+1] as usize;
+        i += 2;
+
+        if data_length == 0 || data_length > 32 {
+            return Err("invalid type bitmap window length".to_string());
+        }
+
+        if i+data_length > buf.len() {
+            return Err("truncated type bitmap".to_string());
+        }
+
+        for (i, &byte) in buf[i..i+data_length].iter().enumerate() {
+            for bit in 0..8 {
+                if (byte & (1 << (7 - bit))) != 0 {
+                    let _type = RRTypes::try_from((window as u16) * 256 + (i as u16 * 8 + bit as u16))
+                        .map_err(|e| e.to_string())?;
+                    types.push(_type);
+                }
+            }
+        }
+
+        i += data_length;
+    }
+
+    Ok(types)
+}
+
+pub(crate) fn encode_type_bitmap(types: &[RRTypes]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    let mut windows: Vec<Vec<u8>> = vec![Vec::new(); 256];
+
+    for _type in types.iter() {
+        let code = _type.code();
+        let w = (code >> 8) as usize;
+        let low = (code & 0xFF) as u8;
+        let byte_i = (low >> 3) as usize;
+        let bit_in_byte = 7 - (low & 0x07);
+
+        let bm = &mut windows[w];
+        if bm.len() <= byte_i {
+            bm.resize(byte_i + 1, 0);
+        }
+        bm[byte_i] |= 1 << bit_in_byte;
+    }
+
+    for (win, bm) in windows.into_iter().enumerate() {
+        let mut used = bm.len();
+        while used > 0 && bm[used - 1] == 0 {
+            used -= 1;
+        }
+        if used == 0 {
+            continue;
+        }
+
+        buf.push(win as u8);
+        buf.push(used as u8);
+        buf.extend_from_slice(&bm[..used]);
+    }
+
+    buf
+}
+
 impl FromWireLen for NSecRRData {
 
     fn from_wire_len(context: &mut FromWireContext, len: u16) -> Result<Self, WireError> {
-        todo!()
+        let pos = context.pos();
+        let next_domain = context.name()?;
+
+        let types = decode_type_bitmap(context.take((len as usize).checked_sub(context.pos() - pos)
+            .ok_or_else(|| WireError::Format("truncated NSEC record".to_string()))?)?)
+            .map_err(WireError::Format)?;
+
+        Ok(Self {
+            next_domain: Some(next_domain),
+            types
+        })
     }
 }
 
 impl ToWire for NSecRRData {
 
     fn to_wire(&self, context: &mut ToWireContext) -> Result<(), WireError> {
-        todo!()
+        context.write_name_preserve_case(self.next_domain.as_ref()
+            .ok_or_else(|| WireError::Format("next_domain param was not set".to_string()))?, false)?;
+
+        context.write(&encode_type_bitmap(&self.types))
     }
 }
 
@@ -205,4 +231,9 @@ fn test() {
     let buf = vec![ 0x1, 0x0, 0x5, 0x66, 0x69, 0x6e, 0x64, 0x39, 0x3, 0x6e, 0x65, 0x74, 0x0, 0x0, 0x9, 0x62, 0x5, 0x80, 0xc, 0x54, 0xb, 0x8d, 0x1c, 0xc0, 0x1, 0x1, 0xc0 ];
     let record = NSecRRData::from_bytes(&buf).unwrap();
     assert_eq!(buf, record.to_bytes().unwrap());
+
+    let mut context = ToWireContext::with_capacity(512);
+    record.to_wire(&mut context).unwrap();
+    assert_eq!(buf, context.to_bytes());
+    assert_eq!(record, NSecRRData::from_wire_len(&mut FromWireContext::new(&buf), buf.len() as u16).unwrap());
 }
@@ -0,0 +1,118 @@
+use std::any::Any;
+use std::fmt;
+use std::fmt::Formatter;
+use crate::messages::wire::{FromWireContext, FromWireLen, ToWire, ToWireContext, WireError};
+use crate::rr_data::inter::rr_data::{RRData, RRDataError};
+use crate::utils::base64;
+use crate::zone::inter::zone_rr_data::ZoneRRData;
+use crate::zone::zone_reader::{ErrorKind, ZoneReaderError};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OpenPgpKeyRRData {
+    public_key: Vec<u8>
+}
+
+impl Default for OpenPgpKeyRRData {
+
+    fn default() -> Self {
+        Self {
+            public_key: Vec::new()
+        }
+    }
+}
+
+impl RRData for OpenPgpKeyRRData {
+
+    fn from_bytes(buf: &[u8]) -> Result<Self, RRDataError> {
+        Ok(Self {
+            public_key: buf.to_vec()
+        })
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, RRDataError> {
+        Ok(self.public_key.clone())
+    }
+
+    fn upcast(self) -> Box<dyn RRData> {
+        Box::new(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn RRData> {
+        Box::new(self.clone())
+    }
+
+    fn eq_box(&self, other: &dyn RRData) -> bool {
+        other.as_any().downcast_ref::<Self>().map_or(false, |o| self == o)
+    }
+}
+
+impl OpenPgpKeyRRData {
+
+    pub fn new(public_key: &[u8]) -> Self {
+        Self {
+            public_key: public_key.to_vec()
+        }
+    }
+
+    pub fn set_public_key(&mut self, public_key: &[u8]) {
+        self.public_key = public_key.to_vec();
+    }
+
+    pub fn public_key(&self) -> &[u8] {
+        self.public_key.as_ref()
+    }
+}
+
+impl FromWireLen for OpenPgpKeyRRData {
+
+    fn from_wire_len(context: &mut FromWireContext, len: u16) -> Result<Self, WireError> {
+        let public_key = context.take(len as usize)?.to_vec();
+
+        Ok(Self {
+            public_key
+        })
+    }
+}
+
+impl ToWire for OpenPgpKeyRRData {
+
+    fn to_wire(&self, context: &mut ToWireContext) -> Result<(), WireError> {
+        context.write(&self.public_key)
+    }
+}
+
+impl ZoneRRData for OpenPgpKeyRRData {
+
+    fn set_data(&mut self, index: usize, value: &str) -> Result<(), ZoneReaderError> {
+        Ok(match index {
+            0 => self.public_key = base64::decode(value).map_err(|_| ZoneReaderError::new(ErrorKind::Format, "unable to parse public_key param for record type OPENPGPKEY"))?,
+            _ => return Err(ZoneReaderError::new(ErrorKind::ExtraRRData, "extra record data found for record type OPENPGPKEY"))
+        })
+    }
+
+    fn upcast(self) -> Box<dyn ZoneRRData> {
+        Box::new(self)
+    }
+}
+
+impl fmt::Display for OpenPgpKeyRRData {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", base64::encode(&self.public_key))
+    }
+}
+
+#[test]
+fn test() {
+    let buf = vec![ 0x99, 0x1, 0xd, 0x4, 0x5c, 0x2f, 0x13, 0x8a, 0x1, 0x8, 0x0, 0xc3, 0x6a, 0x91, 0x2e, 0x7f, 0x44, 0x1b, 0xd0, 0x85, 0x3e, 0x7a, 0x26, 0xb, 0xf1 ];
+    let record = OpenPgpKeyRRData::from_bytes(&buf).unwrap();
+    assert_eq!(buf, record.to_bytes().unwrap());
+}
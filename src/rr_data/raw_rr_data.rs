@@ -0,0 +1,172 @@
+use std::any::Any;
+use std::fmt;
+use std::fmt::Formatter;
+use crate::messages::wire::{FromWireContext, FromWireLen, ToWire, ToWireContext, WireError};
+use crate::rr_data::inter::rr_data::{RRData, RRDataError};
+use crate::utils::hex;
+use crate::zone::inter::zone_rr_data::ZoneRRData;
+use crate::zone::zone_reader::{ErrorKind, ZoneReaderError};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RawRRData {
+    data: Vec<u8>,
+    length: usize
+}
+
+impl Default for RawRRData {
+
+    fn default() -> Self {
+        Self {
+            data: Vec::new(),
+            length: 0
+        }
+    }
+}
+
+impl RRData for RawRRData {
+
+    fn from_bytes(buf: &[u8]) -> Result<Self, RRDataError> {
+        Ok(Self {
+            data: buf.to_vec(),
+            length: buf.len()
+        })
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, RRDataError> {
+        if self.data.len() != self.length {
+            return Err(RRDataError(format!("expected {} bytes of rdata but found {}", self.length, self.data.len())));
+        }
+
+        Ok(self.data.clone())
+    }
+
+    fn upcast(self) -> Box<dyn RRData> {
+        Box::new(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn RRData> {
+        Box::new(self.clone())
+    }
+
+    fn eq_box(&self, other: &dyn RRData) -> bool {
+        other.as_any().downcast_ref::<Self>().map_or(false, |o| self == o)
+    }
+}
+
+impl RawRRData {
+
+    pub fn new(data: &[u8]) -> Self {
+        Self {
+            data: data.to_vec(),
+            length: data.len()
+        }
+    }
+
+    pub fn set_data(&mut self, data: &[u8]) {
+        self.data = data.to_vec();
+        self.length = data.len();
+    }
+
+    pub fn data(&self) -> &[u8] {
+        self.data.as_ref()
+    }
+}
+
+impl FromWireLen for RawRRData {
+
+    fn from_wire_len(context: &mut FromWireContext, len: u16) -> Result<Self, WireError> {
+        let data = context.take(len as usize)?.to_vec();
+
+        Ok(Self {
+            length: data.len(),
+            data
+        })
+    }
+}
+
+impl ToWire for RawRRData {
+
+    fn to_wire(&self, context: &mut ToWireContext) -> Result<(), WireError> {
+        if self.data.len() != self.length {
+            return Err(WireError::Format(format!("expected {} bytes of rdata but found {}", self.length, self.data.len())));
+        }
+
+        context.write(&self.data)
+    }
+}
+
+impl ZoneRRData for RawRRData {
+
+    fn set_data(&mut self, index: usize, value: &str) -> Result<(), ZoneReaderError> {
+        Ok(match index {
+            0 => {
+                if value != "\\#" {
+                    return Err(ZoneReaderError::new(ErrorKind::Format, "expected \\# for generic record data"));
+                }
+            }
+            1 => {
+                self.length = value.parse().map_err(|_| ZoneReaderError::new(ErrorKind::Format, "unable to parse length param for generic record data"))?;
+                self.data = Vec::with_capacity(self.length);
+            }
+            _ => {
+                if value.len() % 2 != 0 {
+                    return Err(ZoneReaderError::new(ErrorKind::Format, "odd number of hex digits in generic record data"));
+                }
+
+                let buf = hex::decode(value).map_err(|_| ZoneReaderError::new(ErrorKind::Format, "unable to parse data param for generic record data"))?;
+                if self.data.len() + buf.len() > self.length {
+                    return Err(ZoneReaderError::new(ErrorKind::ExtraRRData, "generic record data exceeds declared length"));
+                }
+
+                self.data.extend_from_slice(&buf);
+            }
+        })
+    }
+
+    fn upcast(self) -> Box<dyn ZoneRRData> {
+        Box::new(self)
+    }
+}
+
+impl fmt::Display for RawRRData {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.data.is_empty() {
+            true => write!(f, "\\# 0"),
+            false => write!(f, "\\# {} {}", self.data.len(), hex::encode(&self.data))
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use std::str::FromStr;
+    use crate::messages::inter::rr_classes::RRClasses;
+    use crate::messages::inter::rr_types::RRTypes;
+
+    let buf = vec![ 0xa, 0x0, 0x0, 0x1, 0xde, 0xad, 0xbe, 0xef ];
+    let record = RawRRData::from_bytes(&buf).unwrap();
+    assert_eq!(buf, record.to_bytes().unwrap());
+
+    let mut record = RawRRData::default();
+    ZoneRRData::set_data(&mut record, 0, "\\#").unwrap();
+    ZoneRRData::set_data(&mut record, 1, "8").unwrap();
+    ZoneRRData::set_data(&mut record, 2, "0a000001").unwrap();
+    assert!(record.to_bytes().is_err());
+    ZoneRRData::set_data(&mut record, 3, "deadbeef").unwrap();
+    assert_eq!(buf, record.to_bytes().unwrap());
+    assert_eq!(record.to_string(), "\\# 8 0a000001deadbeef");
+
+    assert_eq!(RRTypes::from_str("TYPE65534").unwrap(), RRTypes::Unknown(65534));
+    assert_eq!(RRTypes::from_str("TYPE1").unwrap(), RRTypes::A);
+    assert_eq!(RRTypes::Unknown(65534).to_string(), "TYPE65534");
+    assert_eq!(RRClasses::from_str("CLASS32").unwrap(), RRClasses::Unknown(32));
+}
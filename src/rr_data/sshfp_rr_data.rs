@@ -1,8 +1,7 @@
 use std::any::Any;
 use std::fmt;
 use std::fmt::Formatter;
-use crate::messages::inter::rr_classes::RRClasses;
-use crate::messages::wire::{FromWireContext, FromWireLen, ToWire, ToWireContext, WireError};
+use crate::messages::wire::{FromWire, FromWireContext, FromWireLen, ToWire, ToWireContext, WireError};
 use crate::rr_data::inter::rr_data::{RRData, RRDataError};
 use crate::utils::hex;
 use crate::zone::inter::zone_rr_data::ZoneRRData;
@@ -29,6 +28,10 @@ impl Default for SshFpRRData {
 impl RRData for SshFpRRData {
 
     fn from_bytes(buf: &[u8]) -> Result<Self, RRDataError> {
+        if buf.len() < 2 {
+            return Err(RRDataError("truncated SSHFP record".to_string()));
+        }
+
         let algorithm = buf[0];
         let fingerprint_type = buf[1];
 
@@ -109,14 +112,27 @@ impl SshFpRRData {
 impl FromWireLen for SshFpRRData {
 
     fn from_wire_len(context: &mut FromWireContext, len: u16) -> Result<Self, WireError> {
-        todo!()
+        let algorithm = u8::from_wire(context)?;
+        let fingerprint_type = u8::from_wire(context)?;
+
+        let fingerprint = context.take((len as usize).checked_sub(2)
+            .ok_or_else(|| WireError::Format("truncated SSHFP record".to_string()))?)?.to_vec();
+
+        Ok(Self {
+            algorithm,
+            fingerprint_type,
+            fingerprint
+        })
     }
 }
 
 impl ToWire for SshFpRRData {
 
     fn to_wire(&self, context: &mut ToWireContext) -> Result<(), WireError> {
-        todo!()
+        self.algorithm.to_wire(context)?;
+        self.fingerprint_type.to_wire(context)?;
+
+        context.write(&self.fingerprint)
     }
 }
 
@@ -144,3 +160,16 @@ impl fmt::Display for SshFpRRData {
                hex::encode(&self.fingerprint))
     }
 }
+
+#[test]
+fn test() {
+    let buf = vec![ 0x1, 0x2, 0x8b, 0x9f, 0x2f, 0x2b, 0x6b, 0x3b, 0x6, 0xe3, 0xd8, 0x9f, 0x54, 0xd0, 0x73, 0xd, 0xce, 0x5b, 0x4, 0x26, 0xf8, 0x8c ];
+    let record = SshFpRRData::from_bytes(&buf).unwrap();
+    assert_eq!(buf, record.to_bytes().unwrap());
+
+    let mut context = ToWireContext::with_capacity(512);
+    record.to_wire(&mut context).unwrap();
+    assert_eq!(buf, context.to_bytes());
+    assert_eq!(record, SshFpRRData::from_wire_len(&mut FromWireContext::new(&buf), buf.len() as u16).unwrap());
+    assert!(SshFpRRData::from_wire_len(&mut FromWireContext::new(&buf), 1).is_err());
+}
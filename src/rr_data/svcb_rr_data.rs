@@ -163,7 +163,7 @@ impl ToWire for SvcbRRData {
     fn to_wire(&self, context: &mut ToWireContext) -> Result<(), WireError> {
         self.priority.to_wire(context)?;
 
-        context.write_name(self.target.as_ref()
+        context.write_name_preserve_case(self.target.as_ref()
             .ok_or_else(|| WireError::Format("target param was not set".to_string()))?, true)?;
 
         for param in self.params.iter() {
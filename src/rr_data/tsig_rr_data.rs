@@ -3,6 +3,7 @@ use std::fmt;
 use std::fmt::Formatter;
 use std::str::FromStr;
 use crate::keyring::inter::algorithms::Algorithms;
+use crate::messages::inter::response_codes::ResponseCodes;
 use crate::messages::wire::{FromWire, FromWireContext, FromWireLen, ToWire, ToWireContext, WireError};
 use crate::rr_data::inter::rr_data::{RRData, RRDataError};
 use crate::utils::fqdn_utils::{pack_fqdn, unpack_fqdn};
@@ -15,7 +16,7 @@ pub struct TSigRRData {
     fudge: u16,
     mac: Option<Vec<u8>>,
     original_id: u16,
-    error: u16,
+    error: ResponseCodes,
     data: Vec<u8>
 }
 
@@ -28,7 +29,7 @@ impl Default for TSigRRData {
             fudge: 0,
             mac: None,
             original_id: 0,
-            error: 0,
+            error: ResponseCodes::NoError,
             data: Vec::new()
         }
     }
@@ -55,7 +56,8 @@ impl RRData for TSigRRData {
         i += mac_length;
 
         let original_id = u16::from_be_bytes([buf[i], buf[i+1]]);
-        let error = u16::from_be_bytes([buf[i+2], buf[i+3]]);
+        let error = ResponseCodes::from_tsig_error(u16::from_be_bytes([buf[i+2], buf[i+3]]))
+            .map_err(|e| RRDataError(e.to_string()))?;
 
         let data_length = i+6+u16::from_be_bytes([buf[i+4], buf[i+5]]) as usize;
         let data = buf[i+6..data_length].to_vec();
@@ -93,7 +95,7 @@ impl RRData for TSigRRData {
             .ok_or_else(|| RRDataError("mac param was not set".to_string()))?);
 
         buf.extend_from_slice(&self.original_id.to_be_bytes());
-        buf.extend_from_slice(&self.error.to_be_bytes());
+        buf.extend_from_slice(&self.error.code().to_be_bytes());
 
         buf.extend_from_slice(&(self.data.len() as u16).to_be_bytes());
         buf.extend_from_slice(&self.data);
@@ -124,7 +126,7 @@ impl RRData for TSigRRData {
 
 impl TSigRRData {
 
-    pub fn new(algorithm: Algorithms, time_signed: u64, fudge: u16, original_id: u16, error: u16, data: &[u8]) -> Self {
+    pub fn new(algorithm: Algorithms, time_signed: u64, fudge: u16, original_id: u16, error: ResponseCodes, data: &[u8]) -> Self {
         Self {
             algorithm: Some(algorithm),
             time_signed,
@@ -176,11 +178,11 @@ impl TSigRRData {
         self.original_id
     }
 
-    pub fn set_error(&mut self, error: u16) {
+    pub fn set_error(&mut self, error: ResponseCodes) {
         self.error = error;
     }
 
-    pub fn error(&self) -> u16 {
+    pub fn error(&self) -> ResponseCodes {
         self.error
     }
 
@@ -212,7 +214,8 @@ impl FromWireLen for TSigRRData {
         let mac = context.take(mac_length)?.to_vec();
 
         let original_id = u16::from_wire(context)?;
-        let error = u16::from_wire(context)?;
+        let error = ResponseCodes::from_tsig_error(u16::from_wire(context)?)
+            .map_err(|e| WireError::Format(e.to_string()))?;
 
         let data_length = u16::from_wire(context)? as usize;
         let data = context.take(data_length)?.to_vec();
@@ -251,7 +254,7 @@ impl ToWire for TSigRRData {
             .ok_or_else(|| WireError::Format("mac param was not set".to_string()))?)?;
 
         self.original_id.to_wire(context)?;
-        self.error.to_wire(context)?;
+        self.error.code().to_wire(context)?;
 
         (self.data.len() as u16).to_wire(context)?;
         context.write(&self.data)
@@ -274,6 +277,9 @@ impl fmt::Display for TSigRRData {
 #[test]
 fn test() {
     let buf = vec![ 0x8, 0x67, 0x73, 0x73, 0x2d, 0x74, 0x73, 0x69, 0x67, 0x0, 0x0, 0x0, 0x50, 0xf8, 0xcf, 0xbb, 0x8c, 0xa0, 0x0, 0x1c, 0x4, 0x4, 0x5, 0xff, 0xff, 0xff, 0xff, 0xff, 0x0, 0x0, 0x0, 0x0, 0x73, 0x28, 0x5d, 0xa, 0x2d, 0xf4, 0xa3, 0x34, 0x2f, 0xcf, 0x1, 0x6f, 0x3c, 0x9f, 0x76, 0x82, 0x2, 0x34, 0x0, 0x0, 0x0, 0x0 ];
-    let record = TSigRRData::from_bytes(&buf).unwrap();
+    let mut record = TSigRRData::from_bytes(&buf).unwrap();
     assert_eq!(buf, record.to_bytes().unwrap());
+
+    record.set_error(ResponseCodes::BadSig);
+    assert_eq!(TSigRRData::from_bytes(&record.to_bytes().unwrap()).unwrap().error(), ResponseCodes::BadSig);
 }
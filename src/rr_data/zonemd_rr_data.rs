@@ -0,0 +1,204 @@
+use std::any::Any;
+use std::fmt;
+use std::fmt::Formatter;
+use crate::messages::wire::{FromWire, FromWireContext, FromWireLen, ToWire, ToWireContext, WireError};
+use crate::rr_data::inter::rr_data::{RRData, RRDataError};
+use crate::utils::hex;
+use crate::zone::inter::zone_rr_data::ZoneRRData;
+use crate::zone::zone_reader::{ErrorKind, ZoneReaderError};
+
+pub const ZONEMD_SCHEME_SIMPLE: u8 = 1;
+
+pub const ZONEMD_HASH_SHA384: u8 = 1;
+pub const ZONEMD_HASH_SHA512: u8 = 2;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ZoneMdRRData {
+    serial: u32,
+    scheme: u8,
+    hash_algorithm: u8,
+    digest: Vec<u8>
+}
+
+impl Default for ZoneMdRRData {
+
+    fn default() -> Self {
+        Self {
+            serial: 0,
+            scheme: 0,
+            hash_algorithm: 0,
+            digest: Vec::new()
+        }
+    }
+}
+
+impl RRData for ZoneMdRRData {
+
+    fn from_bytes(buf: &[u8]) -> Result<Self, RRDataError> {
+        if buf.len() < 6 {
+            return Err(RRDataError("truncated ZONEMD record".to_string()));
+        }
+
+        let serial = u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]);
+        let scheme = buf[4];
+        let hash_algorithm = buf[5];
+
+        let digest = buf[6..buf.len()].to_vec();
+
+        Ok(Self {
+            serial,
+            scheme,
+            hash_algorithm,
+            digest
+        })
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, RRDataError> {
+        let mut buf = Vec::with_capacity(6 + self.digest.len());
+
+        buf.extend_from_slice(&self.serial.to_be_bytes());
+        buf.push(self.scheme);
+        buf.push(self.hash_algorithm);
+
+        buf.extend_from_slice(&self.digest);
+
+        Ok(buf)
+    }
+
+    fn upcast(self) -> Box<dyn RRData> {
+        Box::new(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn RRData> {
+        Box::new(self.clone())
+    }
+
+    fn eq_box(&self, other: &dyn RRData) -> bool {
+        other.as_any().downcast_ref::<Self>().map_or(false, |o| self == o)
+    }
+}
+
+impl ZoneMdRRData {
+
+    pub fn new(serial: u32, scheme: u8, hash_algorithm: u8, digest: &[u8]) -> Self {
+        Self {
+            serial,
+            scheme,
+            hash_algorithm,
+            digest: digest.to_vec()
+        }
+    }
+
+    pub fn set_serial(&mut self, serial: u32) {
+        self.serial = serial;
+    }
+
+    pub fn serial(&self) -> u32 {
+        self.serial
+    }
+
+    pub fn set_scheme(&mut self, scheme: u8) {
+        self.scheme = scheme;
+    }
+
+    pub fn scheme(&self) -> u8 {
+        self.scheme
+    }
+
+    pub fn set_hash_algorithm(&mut self, hash_algorithm: u8) {
+        self.hash_algorithm = hash_algorithm;
+    }
+
+    pub fn hash_algorithm(&self) -> u8 {
+        self.hash_algorithm
+    }
+
+    pub fn set_digest(&mut self, digest: &[u8]) {
+        self.digest = digest.to_vec();
+    }
+
+    pub fn digest(&self) -> &[u8] {
+        self.digest.as_ref()
+    }
+}
+
+impl FromWireLen for ZoneMdRRData {
+
+    fn from_wire_len(context: &mut FromWireContext, len: u16) -> Result<Self, WireError> {
+        if len < 6 {
+            return Err(WireError::Format(format!("invalid ZONEMD length: {}", len)));
+        }
+
+        let serial = u32::from_wire(context)?;
+        let scheme = u8::from_wire(context)?;
+        let hash_algorithm = u8::from_wire(context)?;
+
+        let digest = context.take(len as usize - 6)?.to_vec();
+
+        Ok(Self {
+            serial,
+            scheme,
+            hash_algorithm,
+            digest
+        })
+    }
+}
+
+impl ToWire for ZoneMdRRData {
+
+    fn to_wire(&self, context: &mut ToWireContext) -> Result<(), WireError> {
+        self.serial.to_wire(context)?;
+        self.scheme.to_wire(context)?;
+        self.hash_algorithm.to_wire(context)?;
+
+        context.write(&self.digest)
+    }
+}
+
+impl ZoneRRData for ZoneMdRRData {
+
+    fn set_data(&mut self, index: usize, value: &str) -> Result<(), ZoneReaderError> {
+        Ok(match index {
+            0 => self.serial = value.parse().map_err(|_| ZoneReaderError::new(ErrorKind::Format, "unable to parse serial param for record type ZONEMD"))?,
+            1 => self.scheme = value.parse().map_err(|_| ZoneReaderError::new(ErrorKind::Format, "unable to parse scheme param for record type ZONEMD"))?,
+            2 => self.hash_algorithm = value.parse().map_err(|_| ZoneReaderError::new(ErrorKind::Format, "unable to parse hash_algorithm param for record type ZONEMD"))?,
+            3 => self.digest = hex::decode(value).map_err(|_| ZoneReaderError::new(ErrorKind::Format, "unable to parse digest param for record type ZONEMD"))?,
+            _ => return Err(ZoneReaderError::new(ErrorKind::ExtraRRData, "extra record data found for record type ZONEMD"))
+        })
+    }
+
+    fn upcast(self) -> Box<dyn ZoneRRData> {
+        Box::new(self)
+    }
+}
+
+impl fmt::Display for ZoneMdRRData {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.serial,
+               self.scheme,
+               self.hash_algorithm,
+               hex::encode(&self.digest))
+    }
+}
+
+#[test]
+fn test() {
+    let buf = vec![ 0x78, 0x48, 0xb9, 0x1c, 0x1, 0x1, 0xc6, 0x80, 0x90, 0xd9, 0xa, 0x7a, 0xed, 0x71, 0x6b, 0xc4, 0x59, 0xf9, 0x34, 0xe, 0x3d, 0x7c, 0x13, 0x70, 0xd4, 0xd2, 0x4b, 0x7e, 0x2f, 0xc3, 0xa1, 0xdd, 0xc0, 0xb9, 0xa8, 0x71, 0x53, 0xb9, 0xa9, 0x71, 0x3b, 0x3c, 0x9a, 0xe5, 0xcc, 0x27, 0x77, 0x7f, 0x98, 0xb8, 0xe7, 0x30, 0x4, 0x4c ];
+    let record = ZoneMdRRData::from_bytes(&buf).unwrap();
+    assert_eq!(record.serial(), 2018031900);
+    assert_eq!(buf, record.to_bytes().unwrap());
+
+    let mut context = ToWireContext::with_capacity(512);
+    record.to_wire(&mut context).unwrap();
+    assert_eq!(buf, context.to_bytes());
+    assert_eq!(record, ZoneMdRRData::from_wire_len(&mut FromWireContext::new(&buf), buf.len() as u16).unwrap());
+}
@@ -0,0 +1,265 @@
+use std::cmp::Ordering;
+
+pub(crate) type U256 = [u64; 4];
+
+pub(crate) fn from_be_bytes(buf: &[u8]) -> U256 {
+    let mut padded = [0u8; 32];
+    let len = buf.len().min(32);
+    padded[32 - len..].copy_from_slice(&buf[buf.len() - len..]);
+
+    let mut r = [0u64; 4];
+    for i in 0..4 {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&padded[24 - i * 8..32 - i * 8]);
+        r[i] = u64::from_be_bytes(word);
+    }
+
+    r
+}
+
+pub(crate) fn to_be_bytes(a: &U256) -> [u8; 32] {
+    let mut buf = [0u8; 32];
+    for i in 0..4 {
+        buf[24 - i * 8..32 - i * 8].copy_from_slice(&a[i].to_be_bytes());
+    }
+
+    buf
+}
+
+pub(crate) fn from_le_bytes(buf: &[u8; 32]) -> U256 {
+    let mut r = [0u64; 4];
+    for i in 0..4 {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&buf[i * 8..i * 8 + 8]);
+        r[i] = u64::from_le_bytes(word);
+    }
+
+    r
+}
+
+pub(crate) fn to_le_bytes(a: &U256) -> [u8; 32] {
+    let mut buf = [0u8; 32];
+    for i in 0..4 {
+        buf[i * 8..i * 8 + 8].copy_from_slice(&a[i].to_le_bytes());
+    }
+
+    buf
+}
+
+pub(crate) fn is_zero(a: &U256) -> bool {
+    a.iter().fold(0, |acc, &w| acc | w) == 0
+}
+
+pub(crate) fn cmp(a: &U256, b: &U256) -> Ordering {
+    for i in (0..4).rev() {
+        match a[i].cmp(&b[i]) {
+            Ordering::Equal => continue,
+            o => return o
+        }
+    }
+
+    Ordering::Equal
+}
+
+pub(crate) fn add(a: &U256, b: &U256) -> (U256, bool) {
+    let mut r = [0u64; 4];
+    let mut carry = false;
+
+    for i in 0..4 {
+        let (s, c1) = a[i].overflowing_add(b[i]);
+        let (s, c2) = s.overflowing_add(carry as u64);
+        r[i] = s;
+        carry = c1 | c2;
+    }
+
+    (r, carry)
+}
+
+pub(crate) fn sub(a: &U256, b: &U256) -> (U256, bool) {
+    let mut r = [0u64; 4];
+    let mut borrow = false;
+
+    for i in 0..4 {
+        let (d, b1) = a[i].overflowing_sub(b[i]);
+        let (d, b2) = d.overflowing_sub(borrow as u64);
+        r[i] = d;
+        borrow = b1 | b2;
+    }
+
+    (r, borrow)
+}
+
+pub(crate) fn bit(a: &U256, i: usize) -> bool {
+    (a[i / 64] >> (i % 64)) & 1 == 1
+}
+
+pub(crate) fn mask(choice: bool) -> u64 {
+    0u64.wrapping_sub(choice as u64)
+}
+
+pub(crate) fn select(choice: bool, a: &U256, b: &U256) -> U256 {
+    let mask = mask(choice);
+
+    let mut r = [0u64; 4];
+    for i in 0..4 {
+        r[i] = (a[i] & mask) | (b[i] & !mask);
+    }
+
+    r
+}
+
+pub(crate) fn cswap(choice: bool, a: &mut U256, b: &mut U256) {
+    let mask = mask(choice);
+
+    for i in 0..4 {
+        let t = (a[i] ^ b[i]) & mask;
+        a[i] ^= t;
+        b[i] ^= t;
+    }
+}
+
+pub(crate) fn mul_wide(a: &U256, b: &U256) -> [u64; 8] {
+    let mut r = [0u64; 8];
+
+    for i in 0..4 {
+        let mut carry = 0u128;
+        for j in 0..4 {
+            let t = r[i + j] as u128 + (a[i] as u128) * (b[j] as u128) + carry;
+            r[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        r[i + 4] = carry as u64;
+    }
+
+    r
+}
+
+pub(crate) fn reduce_wide(x: &[u64; 8], m: &U256) -> U256 {
+    let mut r = [0u64; 4];
+
+    for i in (0..512).rev() {
+        let overflow = r[3] >> 63 == 1;
+        for j in (1..4).rev() {
+            r[j] = (r[j] << 1) | (r[j - 1] >> 63);
+        }
+        r[0] = (r[0] << 1) | ((x[i / 64] >> (i % 64)) & 1);
+
+        let (d, borrow) = sub(&r, m);
+        r = select(overflow | !borrow, &d, &r);
+    }
+
+    r
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct MontField {
+    m: U256,
+    m_inv: u64,
+    r2: U256
+}
+
+impl MontField {
+
+    pub(crate) fn new(m: U256) -> Self {
+        let mut inv = 1u64;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(m[0].wrapping_mul(inv)));
+        }
+
+        let mut r2 = [1u64, 0, 0, 0];
+        for _ in 0..512 {
+            r2 = add_mod(&r2, &r2, &m);
+        }
+
+        Self {
+            m,
+            m_inv: inv.wrapping_neg(),
+            r2
+        }
+    }
+
+    pub(crate) fn mont_mul(&self, a: &U256, b: &U256) -> U256 {
+        let mut t = [0u64; 6];
+
+        for i in 0..4 {
+            let mut carry = 0u128;
+            for j in 0..4 {
+                let s = t[j] as u128 + (a[j] as u128) * (b[i] as u128) + carry;
+                t[j] = s as u64;
+                carry = s >> 64;
+            }
+            let s = t[4] as u128 + carry;
+            t[4] = s as u64;
+            t[5] = (s >> 64) as u64;
+
+            let f = t[0].wrapping_mul(self.m_inv);
+            let mut carry = (t[0] as u128 + (f as u128) * (self.m[0] as u128)) >> 64;
+            for j in 1..4 {
+                let s = t[j] as u128 + (f as u128) * (self.m[j] as u128) + carry;
+                t[j - 1] = s as u64;
+                carry = s >> 64;
+            }
+            let s = t[4] as u128 + carry;
+            t[3] = s as u64;
+            t[4] = t[5] + (s >> 64) as u64;
+        }
+
+        let r = [t[0], t[1], t[2], t[3]];
+        let (d, borrow) = sub(&r, &self.m);
+        select((t[4] != 0) | !borrow, &d, &r)
+    }
+
+    pub(crate) fn to_mont(&self, a: &U256) -> U256 {
+        self.mont_mul(a, &self.r2)
+    }
+
+    pub(crate) fn from_mont(&self, a: &U256) -> U256 {
+        self.mont_mul(a, &[1, 0, 0, 0])
+    }
+
+    pub(crate) fn one(&self) -> U256 {
+        self.to_mont(&[1, 0, 0, 0])
+    }
+
+    pub(crate) fn add(&self, a: &U256, b: &U256) -> U256 {
+        add_mod(a, b, &self.m)
+    }
+
+    pub(crate) fn sub(&self, a: &U256, b: &U256) -> U256 {
+        let (d, borrow) = sub(a, b);
+        select(borrow, &add(&d, &self.m).0, &d)
+    }
+
+    pub(crate) fn mul(&self, a: &U256, b: &U256) -> U256 {
+        self.mont_mul(a, b)
+    }
+
+    // Branches on the bits of e, so e must be public (inv only uses m - 2).
+    pub(crate) fn pow(&self, a: &U256, e: &U256) -> U256 {
+        let mut r = self.one();
+
+        for i in (0..256).rev() {
+            r = self.mont_mul(&r, &r);
+            if bit(e, i) {
+                r = self.mont_mul(&r, a);
+            }
+        }
+
+        r
+    }
+
+    pub(crate) fn inv(&self, a: &U256) -> U256 {
+        self.pow(a, &sub(&self.m, &[2, 0, 0, 0]).0)
+    }
+
+    pub(crate) fn reduce(&self, a: &U256) -> U256 {
+        let (d, borrow) = sub(a, &self.m);
+        select(borrow, a, &d)
+    }
+}
+
+fn add_mod(a: &U256, b: &U256, m: &U256) -> U256 {
+    let (s, carry) = add(a, b);
+    let (d, borrow) = sub(&s, m);
+    select(carry | !borrow, &d, &s)
+}
@@ -0,0 +1,471 @@
+use crate::utils::crypto::big_uint::{self, U256};
+use crate::utils::hash::inter::hash::Hash;
+use crate::utils::hash::sha512::Sha512;
+
+const MASK: u64 = (1 << 51) - 1;
+
+const L: U256 = [0x5812631a5cf5d3ed, 0x14def9dea2f79cd6, 0x0000000000000000, 0x1000000000000000];
+
+const BASE_POINT: [u8; 32] = [
+    0x58, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+    0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+    0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+    0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66
+];
+
+#[derive(Clone, Copy)]
+struct Fe([u64; 5]);
+
+impl Fe {
+
+    const ZERO: Fe = Fe([0; 5]);
+    const ONE: Fe = Fe([1, 0, 0, 0, 0]);
+
+    fn from_u64(v: u64) -> Self {
+        Self([v, 0, 0, 0, 0]).carry()
+    }
+
+    fn from_bytes(buf: &[u8; 32]) -> Self {
+        let load = |i: usize| {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&buf[i..i + 8]);
+            u64::from_le_bytes(word)
+        };
+
+        Self([
+            load(0) & MASK,
+            (load(6) >> 3) & MASK,
+            (load(12) >> 6) & MASK,
+            (load(19) >> 1) & MASK,
+            (load(24) >> 12) & MASK
+        ])
+    }
+
+    fn to_bytes(&self) -> [u8; 32] {
+        let mut h = self.carry().carry().0;
+
+        let mut q = (h[0] + 19) >> 51;
+        q = (h[1] + q) >> 51;
+        q = (h[2] + q) >> 51;
+        q = (h[3] + q) >> 51;
+        q = (h[4] + q) >> 51;
+
+        h[0] += 19 * q;
+        for i in 0..4 {
+            h[i + 1] += h[i] >> 51;
+            h[i] &= MASK;
+        }
+        h[4] &= MASK;
+
+        let words = [
+            h[0] | (h[1] << 51),
+            (h[1] >> 13) | (h[2] << 38),
+            (h[2] >> 26) | (h[3] << 25),
+            (h[3] >> 39) | (h[4] << 12)
+        ];
+
+        let mut buf = [0u8; 32];
+        for i in 0..4 {
+            buf[i * 8..i * 8 + 8].copy_from_slice(&words[i].to_le_bytes());
+        }
+
+        buf
+    }
+
+    fn carry(&self) -> Self {
+        let mut h = self.0;
+
+        for i in 0..4 {
+            h[i + 1] += h[i] >> 51;
+            h[i] &= MASK;
+        }
+
+        h[0] += 19 * (h[4] >> 51);
+        h[4] &= MASK;
+
+        Self(h)
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        let mut h = [0u64; 5];
+        for i in 0..5 {
+            h[i] = self.0[i] + other.0[i];
+        }
+
+        Self(h).carry()
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        const TWO_P: [u64; 5] = [0xfffffffffffda, 0xffffffffffffe, 0xffffffffffffe, 0xffffffffffffe, 0xffffffffffffe];
+
+        let other = other.carry();
+        let mut h = [0u64; 5];
+        for i in 0..5 {
+            h[i] = self.0[i] + TWO_P[i] - other.0[i];
+        }
+
+        Self(h).carry()
+    }
+
+    fn neg(&self) -> Self {
+        Self::ZERO.sub(self)
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        let a = self.0.map(|v| v as u128);
+        let b = other.0.map(|v| v as u128);
+
+        let b1_19 = b[1] * 19;
+        let b2_19 = b[2] * 19;
+        let b3_19 = b[3] * 19;
+        let b4_19 = b[4] * 19;
+
+        let mut r = [
+            a[0] * b[0] + a[1] * b4_19 + a[2] * b3_19 + a[3] * b2_19 + a[4] * b1_19,
+            a[0] * b[1] + a[1] * b[0] + a[2] * b4_19 + a[3] * b3_19 + a[4] * b2_19,
+            a[0] * b[2] + a[1] * b[1] + a[2] * b[0] + a[3] * b4_19 + a[4] * b3_19,
+            a[0] * b[3] + a[1] * b[2] + a[2] * b[1] + a[3] * b[0] + a[4] * b4_19,
+            a[0] * b[4] + a[1] * b[3] + a[2] * b[2] + a[3] * b[1] + a[4] * b[0]
+        ];
+
+        for i in 0..4 {
+            r[i + 1] += r[i] >> 51;
+            r[i] &= MASK as u128;
+        }
+
+        r[0] += 19 * (r[4] >> 51);
+        r[4] &= MASK as u128;
+
+        r[1] += r[0] >> 51;
+        r[0] &= MASK as u128;
+
+        Self(r.map(|v| v as u64))
+    }
+
+    fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    // Branches on the bits of e, so e must be a public exponent.
+    fn pow(&self, e: &[u8; 32]) -> Self {
+        let mut r = Self::ONE;
+
+        for i in (0..256).rev() {
+            r = r.square();
+            if (e[i / 8] >> (i % 8)) & 1 == 1 {
+                r = r.mul(self);
+            }
+        }
+
+        r
+    }
+
+    fn invert(&self) -> Self {
+        let mut e = [0xffu8; 32];
+        e[0] = 0xeb;
+        e[31] = 0x7f;
+        self.pow(&e)
+    }
+
+    fn cswap(&mut self, other: &mut Self, choice: bool) {
+        let mask = big_uint::mask(choice);
+
+        for i in 0..5 {
+            let t = (self.0[i] ^ other.0[i]) & mask;
+            self.0[i] ^= t;
+            other.0[i] ^= t;
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.to_bytes() == [0u8; 32]
+    }
+
+    fn is_negative(&self) -> bool {
+        self.to_bytes()[0] & 1 == 1
+    }
+
+    fn equals(&self, other: &Self) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Point {
+    x: Fe,
+    y: Fe,
+    z: Fe,
+    t: Fe
+}
+
+impl Point {
+
+    fn cswap(&mut self, other: &mut Self, choice: bool) {
+        self.x.cswap(&mut other.x, choice);
+        self.y.cswap(&mut other.y, choice);
+        self.z.cswap(&mut other.z, choice);
+        self.t.cswap(&mut other.t, choice);
+    }
+}
+
+struct Curve {
+    d2: Fe,
+    d: Fe,
+    sqrt_m1: Fe
+}
+
+impl Curve {
+
+    fn new() -> Self {
+        let d = Fe::from_u64(121665).neg().mul(&Fe::from_u64(121666).invert());
+
+        let mut e = [0xffu8; 32];
+        e[0] = 0xfb;
+        e[31] = 0x1f;
+
+        Self {
+            d2: d.add(&d),
+            d,
+            sqrt_m1: Fe::from_u64(2).pow(&e)
+        }
+    }
+
+    fn identity(&self) -> Point {
+        Point {
+            x: Fe::ZERO,
+            y: Fe::ONE,
+            z: Fe::ONE,
+            t: Fe::ZERO
+        }
+    }
+
+    fn add(&self, p: &Point, q: &Point) -> Point {
+        let a = p.y.sub(&p.x).mul(&q.y.sub(&q.x));
+        let b = p.y.add(&p.x).mul(&q.y.add(&q.x));
+        let c = p.t.mul(&self.d2).mul(&q.t);
+        let zz = p.z.mul(&q.z);
+        let d = zz.add(&zz);
+
+        let e = b.sub(&a);
+        let f = d.sub(&c);
+        let g = d.add(&c);
+        let h = b.add(&a);
+
+        Point {
+            x: e.mul(&f),
+            y: g.mul(&h),
+            z: f.mul(&g),
+            t: e.mul(&h)
+        }
+    }
+
+    // Montgomery ladder over the complete twisted Edwards addition: the same additions run for every
+    // bit, and the bit only drives a masked swap.
+    fn mul(&self, p: &Point, k: &[u8; 32]) -> Point {
+        let mut r0 = self.identity();
+        let mut r1 = *p;
+
+        for i in (0..256).rev() {
+            let bit = (k[i / 8] >> (i % 8)) & 1 == 1;
+            r0.cswap(&mut r1, bit);
+            r1 = self.add(&r0, &r1);
+            r0 = self.add(&r0, &r0);
+            r0.cswap(&mut r1, bit);
+        }
+
+        r0
+    }
+
+    fn encode(&self, p: &Point) -> [u8; 32] {
+        let z_inv = p.z.invert();
+        let x = p.x.mul(&z_inv);
+        let y = p.y.mul(&z_inv);
+
+        let mut buf = y.to_bytes();
+        buf[31] |= (x.is_negative() as u8) << 7;
+        buf
+    }
+
+    fn decode(&self, buf: &[u8; 32]) -> Option<Point> {
+        let sign = buf[31] >> 7 == 1;
+
+        let mut y_bytes = *buf;
+        y_bytes[31] &= 0x7f;
+
+        let y = Fe::from_bytes(&y_bytes);
+        if y.to_bytes() != y_bytes {
+            return None;
+        }
+
+        let y2 = y.square();
+        let u = y2.sub(&Fe::ONE);
+        let v = self.d.mul(&y2).add(&Fe::ONE);
+
+        let v3 = v.square().mul(&v);
+        let v7 = v3.square().mul(&v);
+
+        let mut e = [0xffu8; 32];
+        e[0] = 0xfd;
+        e[31] = 0x0f;
+
+        let mut x = u.mul(&v3).mul(&u.mul(&v7).pow(&e));
+
+        let vx2 = v.mul(&x.square());
+        if !vx2.equals(&u) {
+            if vx2.equals(&u.neg()) {
+                x = x.mul(&self.sqrt_m1);
+
+            } else {
+                return None;
+            }
+        }
+
+        if x.is_zero() && sign {
+            return None;
+        }
+
+        if x.is_negative() != sign {
+            x = x.neg();
+        }
+
+        Some(Point {
+            x,
+            y,
+            z: Fe::ONE,
+            t: x.mul(&y)
+        })
+    }
+
+    fn base(&self) -> Point {
+        self.decode(&BASE_POINT).unwrap()
+    }
+}
+
+pub fn public_key(private_key: &[u8]) -> Option<[u8; 32]> {
+    if private_key.len() != 32 {
+        return None;
+    }
+
+    let (a, _) = expand(private_key);
+
+    let curve = Curve::new();
+    Some(curve.encode(&curve.mul(&curve.base(), &a)))
+}
+
+pub fn sign(private_key: &[u8], message: &[u8]) -> Option<[u8; 64]> {
+    if private_key.len() != 32 {
+        return None;
+    }
+
+    let (a, prefix) = expand(private_key);
+
+    let curve = Curve::new();
+    let public_key = curve.encode(&curve.mul(&curve.base(), &a));
+
+    let r = reduce_hash(&[&prefix, message]);
+    let r_point = curve.encode(&curve.mul(&curve.base(), &r));
+
+    let k = reduce_hash(&[&r_point, &public_key, message]);
+
+    let product = big_uint::mul_wide(&big_uint::from_le_bytes(&k), &big_uint::from_le_bytes(&a));
+    let ka = big_uint::reduce_wide(&product, &L);
+    let (sum, _) = big_uint::add(&ka, &big_uint::from_le_bytes(&r));
+    let s = big_uint::reduce_wide(&[sum[0], sum[1], sum[2], sum[3], 0, 0, 0, 0], &L);
+
+    let mut sig = [0u8; 64];
+    sig[..32].copy_from_slice(&r_point);
+    sig[32..].copy_from_slice(&big_uint::to_le_bytes(&s));
+    Some(sig)
+}
+
+pub fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    if public_key.len() != 32 || signature.len() != 64 {
+        return false;
+    }
+
+    let mut a_bytes = [0u8; 32];
+    a_bytes.copy_from_slice(public_key);
+
+    let mut r_bytes = [0u8; 32];
+    r_bytes.copy_from_slice(&signature[..32]);
+
+    let mut s_bytes = [0u8; 32];
+    s_bytes.copy_from_slice(&signature[32..]);
+
+    if big_uint::cmp(&big_uint::from_le_bytes(&s_bytes), &L).is_ge() {
+        return false;
+    }
+
+    let curve = Curve::new();
+
+    let a = match curve.decode(&a_bytes) {
+        Some(a) => a,
+        None => return false
+    };
+
+    if curve.decode(&r_bytes).is_none() {
+        return false;
+    }
+
+    let k = reduce_hash(&[&r_bytes, &a_bytes, message]);
+
+    let sb = curve.mul(&curve.base(), &s_bytes);
+    let ka = curve.mul(&a, &k);
+
+    let neg_ka = Point {
+        x: ka.x.neg(),
+        y: ka.y,
+        z: ka.z,
+        t: ka.t.neg()
+    };
+
+    curve.encode(&curve.add(&sb, &neg_ka)) == r_bytes
+}
+
+fn expand(private_key: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let mut hasher = Sha512::new();
+    hasher.update(private_key, 0, private_key.len());
+    let h = hasher.get_value();
+
+    let mut a = [0u8; 32];
+    a.copy_from_slice(&h[..32]);
+    a[0] &= 248;
+    a[31] &= 127;
+    a[31] |= 64;
+
+    let mut prefix = [0u8; 32];
+    prefix.copy_from_slice(&h[32..]);
+
+    (a, prefix)
+}
+
+fn reduce_hash(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part, 0, part.len());
+    }
+    let h = hasher.get_value();
+
+    let mut wide = [0u64; 8];
+    for i in 0..8 {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&h[i * 8..i * 8 + 8]);
+        wide[i] = u64::from_le_bytes(word);
+    }
+
+    big_uint::to_le_bytes(&big_uint::reduce_wide(&wide, &L))
+}
+
+#[test]
+fn test() {
+    use crate::utils::hex;
+
+    let private_key = hex::decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60").unwrap();
+    let public = public_key(&private_key).unwrap();
+    assert_eq!(hex::encode(&public), "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+
+    let signature = sign(&private_key, &[]).unwrap();
+    assert_eq!(hex::encode(&signature), "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b");
+    assert!(verify(&public, &[], &signature));
+    assert!(!verify(&public, b"x", &signature));
+}
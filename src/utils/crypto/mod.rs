@@ -0,0 +1,3 @@
+mod big_uint;
+pub mod ed25519;
+pub mod p256;
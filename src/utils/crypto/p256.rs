@@ -0,0 +1,289 @@
+use crate::utils::crypto::big_uint::{self, MontField, U256};
+use crate::utils::hash::hmac::hmac;
+use crate::utils::hash::inter::hash::Hash;
+use crate::utils::hash::sha256::Sha256;
+
+const P: U256 = [0xffffffffffffffff, 0x00000000ffffffff, 0x0000000000000000, 0xffffffff00000001];
+const N: U256 = [0xf3b9cac2fc632551, 0xbce6faada7179e84, 0xffffffffffffffff, 0xffffffff00000000];
+const B: U256 = [0x3bce3c3e27d2604b, 0x651d06b0cc53b0f6, 0xb3ebbd55769886bc, 0x5ac635d8aa3a93e7];
+const GX: U256 = [0xf4a13945d898c296, 0x77037d812deb33a0, 0xf8bce6e563a440f2, 0x6b17d1f2e12c4247];
+const GY: U256 = [0xcbb6406837bf51f5, 0x2bce33576b315ece, 0x8ee7eb4a7c0f9e16, 0x4fe342e2fe1a7f9b];
+
+#[derive(Clone, Copy)]
+struct Point {
+    x: U256,
+    y: U256,
+    z: U256
+}
+
+impl Point {
+
+    fn cswap(&mut self, other: &mut Self, choice: bool) {
+        big_uint::cswap(choice, &mut self.x, &mut other.x);
+        big_uint::cswap(choice, &mut self.y, &mut other.y);
+        big_uint::cswap(choice, &mut self.z, &mut other.z);
+    }
+}
+
+struct Curve {
+    fp: MontField,
+    fn_: MontField
+}
+
+impl Curve {
+
+    fn new() -> Self {
+        Self {
+            fp: MontField::new(P),
+            fn_: MontField::new(N)
+        }
+    }
+
+    fn infinity(&self) -> Point {
+        Point {
+            x: [0; 4],
+            y: self.fp.one(),
+            z: [0; 4]
+        }
+    }
+
+    fn generator(&self) -> Point {
+        Point {
+            x: self.fp.to_mont(&GX),
+            y: self.fp.to_mont(&GY),
+            z: self.fp.one()
+        }
+    }
+
+    fn from_affine(&self, x: &U256, y: &U256) -> Option<Point> {
+        if big_uint::cmp(x, &P).is_ge() || big_uint::cmp(y, &P).is_ge() {
+            return None;
+        }
+
+        let f = &self.fp;
+        let x = f.to_mont(x);
+        let y = f.to_mont(y);
+
+        let lhs = f.mul(&y, &y);
+        let x3 = f.mul(&f.mul(&x, &x), &x);
+        let three_x = f.add(&f.add(&x, &x), &x);
+        let rhs = f.add(&f.sub(&x3, &three_x), &f.to_mont(&B));
+
+        if lhs != rhs {
+            return None;
+        }
+
+        Some(Point {
+            x,
+            y,
+            z: f.one()
+        })
+    }
+
+    fn to_affine(&self, p: &Point) -> Option<(U256, U256)> {
+        if big_uint::is_zero(&p.z) {
+            return None;
+        }
+
+        let f = &self.fp;
+        let z_inv = f.inv(&p.z);
+
+        Some((f.from_mont(&f.mul(&p.x, &z_inv)), f.from_mont(&f.mul(&p.y, &z_inv))))
+    }
+
+    // Complete projective addition for a = -3 (Renes, Costello, Batina 2016, algorithm 4). It has no
+    // special cases for doubling or the point at infinity, so it never branches on the points.
+    fn add(&self, p: &Point, q: &Point) -> Point {
+        let f = &self.fp;
+        let b = f.to_mont(&B);
+
+        let t0 = f.mul(&p.x, &q.x);
+        let t1 = f.mul(&p.y, &q.y);
+        let t2 = f.mul(&p.z, &q.z);
+        let t3 = f.mul(&f.add(&p.x, &p.y), &f.add(&q.x, &q.y));
+        let t3 = f.sub(&t3, &f.add(&t0, &t1));
+        let t4 = f.mul(&f.add(&p.y, &p.z), &f.add(&q.y, &q.z));
+        let t4 = f.sub(&t4, &f.add(&t1, &t2));
+        let y3 = f.mul(&f.add(&p.x, &p.z), &f.add(&q.x, &q.z));
+        let y3 = f.sub(&y3, &f.add(&t0, &t2));
+        let z3 = f.mul(&b, &t2);
+        let x3 = f.sub(&y3, &z3);
+        let z3 = f.add(&x3, &x3);
+        let x3 = f.add(&x3, &z3);
+        let z3 = f.sub(&t1, &x3);
+        let x3 = f.add(&t1, &x3);
+        let y3 = f.mul(&b, &y3);
+        let t1 = f.add(&t2, &t2);
+        let t2 = f.add(&t1, &t2);
+        let y3 = f.sub(&f.sub(&y3, &t2), &t0);
+        let t1 = f.add(&y3, &y3);
+        let y3 = f.add(&t1, &y3);
+        let t1 = f.add(&t0, &t0);
+        let t0 = f.sub(&f.add(&t1, &t0), &t2);
+        let t1 = f.mul(&t4, &y3);
+        let t2 = f.mul(&t0, &y3);
+        let y3 = f.add(&f.mul(&x3, &z3), &t2);
+        let x3 = f.sub(&f.mul(&t3, &x3), &t1);
+        let z3 = f.add(&f.mul(&t4, &z3), &f.mul(&t3, &t0));
+
+        Point {
+            x: x3,
+            y: y3,
+            z: z3
+        }
+    }
+
+    // Montgomery ladder: the same additions run for every bit, and the bit only drives a masked swap.
+    fn mul(&self, p: &Point, k: &U256) -> Point {
+        let mut r0 = self.infinity();
+        let mut r1 = *p;
+
+        for i in (0..256).rev() {
+            let bit = big_uint::bit(k, i);
+            r0.cswap(&mut r1, bit);
+            r1 = self.add(&r0, &r1);
+            r0 = self.add(&r0, &r0);
+            r0.cswap(&mut r1, bit);
+        }
+
+        r0
+    }
+
+    fn scalar_mul(&self, a: &U256, b: &U256) -> U256 {
+        let f = &self.fn_;
+        f.from_mont(&f.mul(&f.to_mont(a), &f.to_mont(b)))
+    }
+
+    fn scalar_inv(&self, a: &U256) -> U256 {
+        let f = &self.fn_;
+        f.from_mont(&f.inv(&f.to_mont(a)))
+    }
+}
+
+pub fn public_key(private_key: &[u8]) -> Option<[u8; 64]> {
+    let d = private_scalar(private_key)?;
+
+    let curve = Curve::new();
+    let (x, y) = curve.to_affine(&curve.mul(&curve.generator(), &d))?;
+
+    let mut buf = [0u8; 64];
+    buf[..32].copy_from_slice(&big_uint::to_be_bytes(&x));
+    buf[32..].copy_from_slice(&big_uint::to_be_bytes(&y));
+    Some(buf)
+}
+
+pub fn sign(private_key: &[u8], message: &[u8]) -> Option<[u8; 64]> {
+    let d = private_scalar(private_key)?;
+
+    let curve = Curve::new();
+    let e = curve.fn_.reduce(&big_uint::from_be_bytes(&digest(message)));
+
+    let x = big_uint::to_be_bytes(&d);
+    let h = big_uint::to_be_bytes(&e);
+
+    let mut v = [0x01u8; 32];
+    let mut k = [0x00u8; 32];
+
+    for marker in [0x00u8, 0x01] {
+        let mut buf = Vec::with_capacity(97);
+        buf.extend_from_slice(&v);
+        buf.push(marker);
+        buf.extend_from_slice(&x);
+        buf.extend_from_slice(&h);
+        k = hmac::<Sha256>(&k, &buf);
+        v = hmac::<Sha256>(&k, &v);
+    }
+
+    loop {
+        v = hmac::<Sha256>(&k, &v);
+        let nonce = big_uint::from_be_bytes(&v);
+
+        if !big_uint::is_zero(&nonce) && big_uint::cmp(&nonce, &N).is_lt() {
+            if let Some((rx, _)) = curve.to_affine(&curve.mul(&curve.generator(), &nonce)) {
+                let r = curve.fn_.reduce(&rx);
+
+                if !big_uint::is_zero(&r) {
+                    let rd = curve.scalar_mul(&r, &d);
+                    let s = curve.scalar_mul(&curve.scalar_inv(&nonce), &curve.fn_.add(&e, &rd));
+
+                    if !big_uint::is_zero(&s) {
+                        let mut sig = [0u8; 64];
+                        sig[..32].copy_from_slice(&big_uint::to_be_bytes(&r));
+                        sig[32..].copy_from_slice(&big_uint::to_be_bytes(&s));
+                        return Some(sig);
+                    }
+                }
+            }
+        }
+
+        let mut buf = v.to_vec();
+        buf.push(0x00);
+        k = hmac::<Sha256>(&k, &buf);
+        v = hmac::<Sha256>(&k, &v);
+    }
+}
+
+pub fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    if public_key.len() != 64 || signature.len() != 64 {
+        return false;
+    }
+
+    let curve = Curve::new();
+
+    let q = match curve.from_affine(&big_uint::from_be_bytes(&public_key[..32]), &big_uint::from_be_bytes(&public_key[32..])) {
+        Some(q) => q,
+        None => return false
+    };
+
+    let r = big_uint::from_be_bytes(&signature[..32]);
+    let s = big_uint::from_be_bytes(&signature[32..]);
+
+    if big_uint::is_zero(&r) || big_uint::is_zero(&s) || big_uint::cmp(&r, &N).is_ge() || big_uint::cmp(&s, &N).is_ge() {
+        return false;
+    }
+
+    let e = curve.fn_.reduce(&big_uint::from_be_bytes(&digest(message)));
+    let w = curve.scalar_inv(&s);
+    let u1 = curve.scalar_mul(&e, &w);
+    let u2 = curve.scalar_mul(&r, &w);
+
+    let point = curve.add(&curve.mul(&curve.generator(), &u1), &curve.mul(&q, &u2));
+
+    match curve.to_affine(&point) {
+        Some((x, _)) => curve.fn_.reduce(&x) == r,
+        None => false
+    }
+}
+
+fn private_scalar(private_key: &[u8]) -> Option<U256> {
+    if private_key.len() != 32 {
+        return None;
+    }
+
+    let d = big_uint::from_be_bytes(private_key);
+    if big_uint::is_zero(&d) || big_uint::cmp(&d, &N).is_ge() {
+        return None;
+    }
+
+    Some(d)
+}
+
+fn digest(message: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(message, 0, message.len());
+    hasher.get_value()
+}
+
+#[test]
+fn test() {
+    use crate::utils::hex;
+
+    let private_key = hex::decode("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721").unwrap();
+    let public = public_key(&private_key).unwrap();
+    assert_eq!(hex::encode(&public), "60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb67903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299");
+
+    let signature = sign(&private_key, b"sample").unwrap();
+    assert_eq!(hex::encode(&signature), "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8");
+    assert!(verify(&public, b"sample", &signature));
+    assert!(!verify(&public, b"test", &signature));
+}
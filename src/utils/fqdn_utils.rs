@@ -74,8 +74,16 @@ pub fn encode_fqdn(fqdn: &str) -> Vec<u8> {
 
     let mut buf = Vec::new();
 
-    for part in fqdn.split('.').rev() {
-        buf.extend_from_slice(part.as_bytes());
+    for label in parse_labels(fqdn).iter().rev() {
+        for b in label {
+            // Keys are case-folded so lookups ignore ASCII case, and the owner name case is not kept.
+            // 0x00 separates labels in the key, so 0x00 and 0x01 are escaped behind 0x01 while keeping their order
+            match b.to_ascii_lowercase() {
+                0x00 => buf.extend_from_slice(&[0x01, 0x01]),
+                0x01 => buf.extend_from_slice(&[0x01, 0x02]),
+                b => buf.push(b)
+            }
+        }
         buf.push(0x00);
     }
 
@@ -87,22 +95,97 @@ pub fn decode_fqdn(buf: &[u8]) -> String {
         return String::new();
     }
 
-    let mut builder: Vec<&str> = Vec::new();
-    let mut original_pos = 0;
+    let mut builder: Vec<String> = Vec::new();
 
-    for i in 0..buf.len() {
-        if buf[i] == 0 {
-            if i > original_pos {
-                builder.push(std::str::from_utf8(&buf[original_pos..i]).unwrap());
+    for key in buf.split(|&b| b == 0x00) {
+        if key.is_empty() {
+            continue;
+        }
+
+        let mut label = String::with_capacity(key.len());
+        let mut i = 0;
+        while i < key.len() {
+            let b = match key[i] {
+                0x01 => {
+                    i += 1;
+                    key.get(i).map_or(0x01, |b| b - 1)
+                }
+                b => b
+            };
+
+            match b {
+                b'.' | b'\\' => {
+                    label.push('\\');
+                    label.push(b as char);
+                }
+                0x21..=0x7e => label.push(b as char),
+                _ => label.push_str(&format!("\\{:03}", b))
             }
-            original_pos = i + 1;
+            i += 1;
         }
+
+        builder.push(label);
     }
 
     builder.reverse();
     builder.join(".")
 }
 
+pub fn labels(fqdn: &str) -> impl Iterator<Item = &str> {
+    let mut rest = Some(fqdn);
+
+    std::iter::from_fn(move || {
+        let fqdn = rest?;
+        let buf = fqdn.as_bytes();
+
+        let mut i = 0;
+        while i < buf.len() {
+            match buf[i] {
+                // the byte after a backslash is never a separator, and \DDD digits are never dots
+                b'\\' => i += 2,
+                b'.' => {
+                    rest = Some(&fqdn[i + 1..]);
+                    return Some(&fqdn[..i]);
+                }
+                _ => i += 1
+            }
+        }
+
+        rest = None;
+        Some(fqdn)
+    })
+}
+
+fn parse_labels(fqdn: &str) -> Vec<Vec<u8>> {
+    labels(fqdn).map(unescape_label).collect()
+}
+
+fn unescape_label(label: &str) -> Vec<u8> {
+    let buf = label.as_bytes();
+    let mut unescaped = Vec::with_capacity(buf.len());
+
+    let mut i = 0;
+    while i < buf.len() {
+        match buf[i] {
+            b'\\' if i + 4 <= buf.len() && buf[i+1..i+4].iter().all(u8::is_ascii_digit) => {
+                match std::str::from_utf8(&buf[i+1..i+4]).unwrap().parse::<u8>() {
+                    Ok(b) => unescaped.push(b),
+                    Err(_) => unescaped.extend_from_slice(&buf[i+1..i+4])
+                }
+                i += 3;
+            }
+            b'\\' if i + 1 < buf.len() => {
+                unescaped.push(buf[i+1]);
+                i += 1;
+            }
+            b => unescaped.push(b)
+        }
+        i += 1;
+    }
+
+    unescaped
+}
+
 pub fn to_fqdn(apex: &str, child: &str) -> String {
     if child.is_empty() {
         return apex.to_string();
@@ -122,3 +205,13 @@ pub fn fqdn_to_relative(apex: &str, child: &str) -> Option<String> {
 
     None
 }
+
+#[test]
+fn test() {
+    assert_eq!(labels("www.a\\.b.find9\\\\.net").collect::<Vec<_>>(), vec!["www", "a\\.b", "find9\\\\", "net"]);
+    assert_eq!(labels("\\046.z").collect::<Vec<_>>(), vec!["\\046", "z"]);
+    assert_eq!(labels("").collect::<Vec<_>>(), vec![""]);
+
+    assert_eq!(parse_labels("a\\.b.\\046"), vec![b"a.b".to_vec(), b".".to_vec()]);
+    assert_eq!(decode_fqdn(&encode_fqdn("a\\.b.\\046")), "a\\.b.\\.");
+}
@@ -35,3 +35,21 @@ where
 
     outer.get_value()
 }
+
+#[test]
+fn test() {
+    use crate::utils::hash::md5::Md5;
+    use crate::utils::hash::sha1::Sha1;
+    use crate::utils::hash::sha256::Sha256;
+    use crate::utils::hash::sha384::Sha384;
+    use crate::utils::hash::sha512::Sha512;
+    use crate::utils::hex;
+
+    let key = [0xb; 20];
+    let message = b"Hi There";
+    assert_eq!(hex::encode(&hmac::<Md5>(&key[..16], message)), "9294727a3638bb1c13f48ef8158bfc9d");
+    assert_eq!(hex::encode(&hmac::<Sha1>(&key, message)), "b617318655057264e28bc0b6fb378c8ef146be00");
+    assert_eq!(hex::encode(&hmac::<Sha256>(&key, message)), "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7");
+    assert_eq!(hex::encode(&hmac::<Sha384>(&key, message)), "afd03944d84895626b0825f4ab46907f15f9dadbe4101ec682aa034c7cebc59cfaea9ea9076ede7f4af152e8b2fa9cb6");
+    assert_eq!(hex::encode(&hmac::<Sha512>(&key, message)), "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854");
+}
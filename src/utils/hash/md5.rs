@@ -0,0 +1,167 @@
+use crate::utils::hash::inter::hash::Hash;
+
+const S: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22,
+    5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20,
+    4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23,
+    6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+const K: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee,
+    0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be,
+    0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa,
+    0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed,
+    0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c,
+    0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05,
+    0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039,
+    0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1,
+    0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+pub struct Md5 {
+    h: [u32; 4],
+    buffer: [u8; 64],
+    buffer_len: usize,
+    message_len: u64
+}
+
+impl Md5 {
+
+    fn transform(&mut self, chunk: &[u8; 64]) {
+        let mut m = [0u32; 16];
+
+        for i in 0..16 {
+            m[i] = u32::from_le_bytes([
+                chunk[i * 4],
+                chunk[i * 4 + 1],
+                chunk[i * 4 + 2],
+                chunk[i * 4 + 3],
+            ]);
+        }
+
+        let mut a = self.h[0];
+        let mut b = self.h[1];
+        let mut c = self.h[2];
+        let mut d = self.h[3];
+
+        for i in 0..64 {
+            let (f, g) = match i {
+                0..=15 => ((b & c) | ((!b) & d), i),
+                16..=31 => ((d & b) | ((!d) & c), (5 * i + 1) % 16),
+                32..=47 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | (!d)), (7 * i) % 16)
+            };
+
+            let temp = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(a
+                .wrapping_add(f)
+                .wrapping_add(K[i])
+                .wrapping_add(m[g])
+                .rotate_left(S[i]));
+            a = temp;
+        }
+
+        self.h[0] = self.h[0].wrapping_add(a);
+        self.h[1] = self.h[1].wrapping_add(b);
+        self.h[2] = self.h[2].wrapping_add(c);
+        self.h[3] = self.h[3].wrapping_add(d);
+    }
+}
+
+impl Hash for Md5 {
+
+    type Output = [u8; 16];
+    const BLOCK_SIZE: usize = 64;
+
+    fn new() -> Self {
+        Self {
+            h: [
+                0x67452301,
+                0xefcdab89,
+                0x98badcfe,
+                0x10325476,
+            ],
+            buffer: [0u8; 64],
+            buffer_len: 0,
+            message_len: 0
+        }
+    }
+
+    fn get_value(&mut self) -> Self::Output {
+        let bit_len = self.message_len;
+        self.buffer[self.buffer_len] = 0x80;
+        self.buffer_len += 1;
+
+        if self.buffer_len > 56 {
+            for i in self.buffer_len..64 {
+                self.buffer[i] = 0;
+            }
+            let chunk = self.buffer;
+            self.transform(&chunk);
+            self.buffer_len = 0;
+        }
+
+        for i in self.buffer_len..56 {
+            self.buffer[i] = 0;
+        }
+
+        self.buffer[56..64].copy_from_slice(&bit_len.to_le_bytes());
+        let chunk = self.buffer;
+        self.transform(&chunk);
+
+        let mut result = [0u8; 16];
+        for (i, &word) in self.h.iter().enumerate() {
+            result[i * 4..i * 4 + 4].copy_from_slice(&word.to_le_bytes());
+        }
+
+        result
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn update(&mut self, data: &[u8], off: usize, len: usize) {
+        let mut input = &data[off..off + len];
+        self.message_len += (len as u64) * 8;
+
+        while !input.is_empty() {
+            let space = 64 - self.buffer_len;
+            let take = space.min(input.len());
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&input[..take]);
+            self.buffer_len += take;
+            input = &input[take..];
+
+            if self.buffer_len == 64 {
+                let chunk = self.buffer;
+                self.transform(&chunk);
+                self.buffer_len = 0;
+            }
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use crate::utils::hex;
+
+    let message = b"abc";
+    let mut hasher = Md5::new();
+    hasher.update(message, 0, message.len());
+    assert_eq!(hex::encode(hasher.get_value().as_ref()), "900150983cd24fb0d6963f7d28e17f72");
+
+    let message = b"12345678901234567890123456789012345678901234567890123456789012345678901234567890";
+    let mut hasher = Md5::new();
+    hasher.update(message, 0, message.len());
+    assert_eq!(hex::encode(hasher.get_value().as_ref()), "57edf4a22be3c955ac49da2e2107b67a");
+}
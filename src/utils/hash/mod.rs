@@ -1,3 +1,9 @@
 pub mod inter;
+pub mod md5;
+pub mod sha1;
+pub mod sha224;
 pub mod sha256;
+pub mod sha384;
+pub mod sha512;
 pub mod hmac;
+pub mod siphash;
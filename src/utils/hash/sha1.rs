@@ -0,0 +1,145 @@
+use crate::utils::hash::inter::hash::Hash;
+
+pub struct Sha1 {
+    h: [u32; 5],
+    buffer: [u8; 64],
+    buffer_len: usize,
+    message_len: u64
+}
+
+impl Sha1 {
+
+    fn transform(&mut self, chunk: &[u8; 64]) {
+        let mut w = [0u32; 80];
+
+        for i in 0..16 {
+            w[i] = u32::from_be_bytes([
+                chunk[i * 4],
+                chunk[i * 4 + 1],
+                chunk[i * 4 + 2],
+                chunk[i * 4 + 3],
+            ]);
+        }
+
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let mut a = self.h[0];
+        let mut b = self.h[1];
+        let mut c = self.h[2];
+        let mut d = self.h[3];
+        let mut e = self.h[4];
+
+        for i in 0..80 {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | ((!b) & d), 0x5a827999),
+                20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+                _ => (b ^ c ^ d, 0xca62c1d6)
+            };
+
+            let temp = a.rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(w[i]);
+
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        self.h[0] = self.h[0].wrapping_add(a);
+        self.h[1] = self.h[1].wrapping_add(b);
+        self.h[2] = self.h[2].wrapping_add(c);
+        self.h[3] = self.h[3].wrapping_add(d);
+        self.h[4] = self.h[4].wrapping_add(e);
+    }
+}
+
+impl Hash for Sha1 {
+
+    type Output = [u8; 20];
+    const BLOCK_SIZE: usize = 64;
+
+    fn new() -> Self {
+        Self {
+            h: [
+                0x67452301,
+                0xefcdab89,
+                0x98badcfe,
+                0x10325476,
+                0xc3d2e1f0,
+            ],
+            buffer: [0u8; 64],
+            buffer_len: 0,
+            message_len: 0
+        }
+    }
+
+    fn get_value(&mut self) -> Self::Output {
+        let bit_len = self.message_len;
+        self.buffer[self.buffer_len] = 0x80;
+        self.buffer_len += 1;
+
+        if self.buffer_len > 56 {
+            for i in self.buffer_len..64 {
+                self.buffer[i] = 0;
+            }
+            let chunk = self.buffer;
+            self.transform(&chunk);
+            self.buffer_len = 0;
+        }
+
+        for i in self.buffer_len..56 {
+            self.buffer[i] = 0;
+        }
+
+        self.buffer[56..64].copy_from_slice(&bit_len.to_be_bytes());
+        let chunk = self.buffer;
+        self.transform(&chunk);
+
+        let mut result = [0u8; 20];
+        for (i, &word) in self.h.iter().enumerate() {
+            result[i * 4..i * 4 + 4].copy_from_slice(&word.to_be_bytes());
+        }
+
+        result
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn update(&mut self, data: &[u8], off: usize, len: usize) {
+        let mut input = &data[off..off + len];
+        self.message_len += (len as u64) * 8;
+
+        while !input.is_empty() {
+            let space = 64 - self.buffer_len;
+            let take = space.min(input.len());
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&input[..take]);
+            self.buffer_len += take;
+            input = &input[take..];
+
+            if self.buffer_len == 64 {
+                let chunk = self.buffer;
+                self.transform(&chunk);
+                self.buffer_len = 0;
+            }
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use crate::utils::hex;
+
+    let message = b"abc";
+    let mut hasher = Sha1::new();
+    hasher.update(message, 0, message.len());
+    assert_eq!(hex::encode(hasher.get_value().as_ref()), "a9993e364706816aba3e25717850c26c9cd0d89d");
+}
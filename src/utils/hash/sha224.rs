@@ -0,0 +1,51 @@
+use crate::utils::hash::inter::hash::Hash;
+use crate::utils::hash::sha256::Sha256;
+
+pub struct Sha224 {
+    inner: Sha256
+}
+
+impl Hash for Sha224 {
+
+    type Output = [u8; 28];
+    const BLOCK_SIZE: usize = 64;
+
+    fn new() -> Self {
+        Self {
+            inner: Sha256::from_state([
+                0xc1059ed8,
+                0x367cd507,
+                0x3070dd17,
+                0xf70e5939,
+                0xffc00b31,
+                0x68581511,
+                0x64f98fa7,
+                0xbefa4fa4,
+            ])
+        }
+    }
+
+    fn get_value(&mut self) -> Self::Output {
+        let mut result = [0u8; 28];
+        result.copy_from_slice(&self.inner.get_value()[..28]);
+        result
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn update(&mut self, data: &[u8], off: usize, len: usize) {
+        self.inner.update(data, off, len);
+    }
+}
+
+#[test]
+fn test() {
+    use crate::utils::hex;
+
+    let message = b"abc";
+    let mut hasher = Sha224::new();
+    hasher.update(message, 0, message.len());
+    assert_eq!(hex::encode(hasher.get_value().as_ref()), "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7");
+}
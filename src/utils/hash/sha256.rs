@@ -28,6 +28,15 @@ pub struct Sha256 {
 
 impl Sha256 {
 
+    pub(crate) fn from_state(h: [u32; 8]) -> Self {
+        Self {
+            h,
+            buffer: [0u8; 64],
+            buffer_len: 0,
+            message_len: 0
+        }
+    }
+
     fn transform(&mut self, chunk: &[u8; 64]) {
         let mut w = [0u32; 64];
 
@@ -97,21 +106,16 @@ impl Hash for Sha256 {
     const BLOCK_SIZE: usize = 64;
 
     fn new() -> Self {
-        Self {
-            h: [
-                0x6a09e667,
-                0xbb67ae85,
-                0x3c6ef372,
-                0xa54ff53a,
-                0x510e527f,
-                0x9b05688c,
-                0x1f83d9ab,
-                0x5be0cd19,
-            ],
-            buffer: [0u8; 64],
-            buffer_len: 0,
-            message_len: 0
-        }
+        Self::from_state([
+            0x6a09e667,
+            0xbb67ae85,
+            0x3c6ef372,
+            0xa54ff53a,
+            0x510e527f,
+            0x9b05688c,
+            0x1f83d9ab,
+            0x5be0cd19,
+        ])
     }
 
     fn get_value(&mut self) -> Self::Output {
@@ -0,0 +1,51 @@
+use crate::utils::hash::inter::hash::Hash;
+use crate::utils::hash::sha512::Sha512;
+
+pub struct Sha384 {
+    inner: Sha512
+}
+
+impl Hash for Sha384 {
+
+    type Output = [u8; 48];
+    const BLOCK_SIZE: usize = 128;
+
+    fn new() -> Self {
+        Self {
+            inner: Sha512::from_state([
+                0xcbbb9d5dc1059ed8,
+                0x629a292a367cd507,
+                0x9159015a3070dd17,
+                0x152fecd8f70e5939,
+                0x67332667ffc00b31,
+                0x8eb44a8768581511,
+                0xdb0c2e0d64f98fa7,
+                0x47b5481dbefa4fa4,
+            ])
+        }
+    }
+
+    fn get_value(&mut self) -> Self::Output {
+        let mut result = [0u8; 48];
+        result.copy_from_slice(&self.inner.get_value()[..48]);
+        result
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn update(&mut self, data: &[u8], off: usize, len: usize) {
+        self.inner.update(data, off, len);
+    }
+}
+
+#[test]
+fn test() {
+    use crate::utils::hex;
+
+    let message = b"abc";
+    let mut hasher = Sha384::new();
+    hasher.update(message, 0, message.len());
+    assert_eq!(hex::encode(hasher.get_value().as_ref()), "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7");
+}
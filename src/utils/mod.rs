@@ -1,5 +1,6 @@
 pub mod trie;
 pub mod hash;
+pub mod crypto;
 pub mod fqdn_utils;
 pub mod random;
 pub mod base64;
@@ -8,3 +9,4 @@ pub mod hex;
 pub mod octal;
 pub mod coord_utils;
 pub mod time_utils;
+pub mod serial_utils;
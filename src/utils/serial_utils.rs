@@ -0,0 +1,56 @@
+use std::cmp::Ordering;
+
+const SERIAL_BITS_HALF: u32 = 1 << 31;
+
+pub trait SerialUtils: Sized {
+
+    fn serial_cmp(&self, other: Self) -> Option<Ordering>;
+
+    fn serial_lt(&self, other: Self) -> bool;
+
+    fn serial_gt(&self, other: Self) -> bool;
+
+    fn serial_add(&self, n: u32) -> Option<Self>;
+}
+
+impl SerialUtils for u32 {
+
+    fn serial_cmp(&self, other: Self) -> Option<Ordering> {
+        if *self == other {
+            return Some(Ordering::Equal);
+        }
+
+        match self.wrapping_sub(other) {
+            SERIAL_BITS_HALF => None,
+            d if d < SERIAL_BITS_HALF => Some(Ordering::Greater),
+            _ => Some(Ordering::Less)
+        }
+    }
+
+    fn serial_lt(&self, other: Self) -> bool {
+        self.serial_cmp(other) == Some(Ordering::Less)
+    }
+
+    fn serial_gt(&self, other: Self) -> bool {
+        self.serial_cmp(other) == Some(Ordering::Greater)
+    }
+
+    fn serial_add(&self, n: u32) -> Option<Self> {
+        if n >= SERIAL_BITS_HALF {
+            return None;
+        }
+
+        Some(self.wrapping_add(n))
+    }
+}
+
+#[test]
+fn test() {
+    assert_eq!(u32::MAX.serial_add(1), Some(0));
+    assert_eq!(5u32.serial_add(SERIAL_BITS_HALF - 1), Some(5 + SERIAL_BITS_HALF - 1));
+    assert_eq!(5u32.serial_add(SERIAL_BITS_HALF), None);
+
+    assert!(u32::MAX.serial_lt(0));
+    assert!(0u32.serial_gt(u32::MAX));
+    assert_eq!(0u32.serial_cmp(SERIAL_BITS_HALF), None);
+}
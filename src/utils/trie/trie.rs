@@ -204,6 +204,101 @@ impl<V> Trie<V> {
         self.get(key).is_some()
     }
 
+    pub fn contains_prefix(&self, prefix: &[u8]) -> bool {
+        let mut node = match self.root.as_ref() {
+            Some(node) => node,
+            None => return false
+        };
+
+        loop {
+            match node {
+                Node::Branch(br) => {
+                    if br.offset >= 2 * prefix.len() {
+                        return is_prefix(prefix, &Self::first_leaf_key(node));
+                    }
+
+                    let n = Self::nibble(prefix, br.offset);
+                    match br.get_child(n) {
+                        Some(child) => node = child,
+                        None => return false
+                    }
+                }
+                Node::Leaf(leaf) => return is_prefix(prefix, leaf.key.as_slice())
+            }
+        }
+    }
+
+    pub fn get_floor(&self, query: &[u8]) -> Option<(&[u8], &V)> {
+        self.get_floor_by(query, |_| true)
+    }
+
+    pub fn get_floor_by<F: Fn(&V) -> bool>(&self, query: &[u8], f: F) -> Option<(&[u8], &V)> {
+        let leaf = Self::floor_at(self.root.as_ref()?, query, &f)?;
+        Some((leaf.key.as_slice(), &leaf.val))
+    }
+
+    fn floor_at<'a, F: Fn(&V) -> bool>(node: &'a Node<Vec<u8>, V>, query: &[u8], f: &F) -> Option<&'a Leaf<Vec<u8>, V>> {
+        match node {
+            Node::Leaf(leaf) => {
+                if leaf.key.as_slice() <= query && f(&leaf.val) {
+                    return Some(leaf);
+                }
+                None
+            }
+            Node::Branch(br) => {
+                let rep_key = Self::first_leaf_key(node);
+
+                if rep_key.as_slice() != query {
+                    let split = Self::first_diff_nibble(&rep_key, query);
+                    if split < br.offset {
+                        if Self::nibble(query, split) > Self::nibble(&rep_key, split) {
+                            return Self::last_leaf_by(node, f);
+                        }
+                        return None;
+                    }
+                }
+
+                let n = Self::nibble(query, br.offset);
+                if let Some(child) = br.get_child(n) {
+                    if let Some(leaf) = Self::floor_at(child, query, f) {
+                        return Some(leaf);
+                    }
+                }
+
+                for i in (0..n).rev() {
+                    if let Some(child) = br.get_child(i) {
+                        if let Some(leaf) = Self::last_leaf_by(child, f) {
+                            return Some(leaf);
+                        }
+                    }
+                }
+
+                None
+            }
+        }
+    }
+
+    fn last_leaf_by<'a, F: Fn(&V) -> bool>(node: &'a Node<Vec<u8>, V>, f: &F) -> Option<&'a Leaf<Vec<u8>, V>> {
+        match node {
+            Node::Leaf(leaf) => {
+                if f(&leaf.val) {
+                    return Some(leaf);
+                }
+                None
+            }
+            Node::Branch(br) => {
+                for i in (0..=16).rev() {
+                    if let Some(child) = br.get_child(i) {
+                        if let Some(leaf) = Self::last_leaf_by(child, f) {
+                            return Some(leaf);
+                        }
+                    }
+                }
+                None
+            }
+        }
+    }
+
     pub fn iter(&self) -> Entries<'_, V> {
         let mut stack = Vec::new();
         if let Some(root) = self.root.as_ref() {
@@ -487,3 +582,35 @@ fn is_apex_key(k: &[u8]) -> bool {
 fn is_prefix(a: &[u8], b: &[u8]) -> bool {
     b.len() >= a.len() && &b[..a.len()] == a
 }
+
+#[test]
+fn test() {
+    use crate::utils::fqdn_utils::{decode_fqdn, encode_fqdn};
+
+    // RFC 4034 §6.1 canonical order, relative to "example", with "b" names interleaved
+    let names = ["", "a", "yljkjljk.a", "Z.a", "zABC.a", "b", "mail.b", "www.b", "z", "\\001.z", "*.z", "\\200.z"];
+
+    let mut trie = Trie::new();
+    for (i, name) in names.iter().enumerate().rev() {
+        trie.insert(encode_fqdn(name), i);
+    }
+
+    assert_eq!(trie.iter().map(|(_, v)| *v).collect::<Vec<_>>(), (0..names.len()).collect::<Vec<_>>());
+    assert_eq!(trie.iter().map(|(k, _)| decode_fqdn(k)).collect::<Vec<_>>(), names.iter().map(|n| n.to_lowercase()).collect::<Vec<_>>());
+
+    assert_eq!(trie.get_floor(&encode_fqdn("b")).map(|(_, v)| *v), Some(5));
+    assert_eq!(trie.get_floor(&encode_fqdn("ftp.b")).map(|(_, v)| *v), Some(5));
+    assert_eq!(trie.get_floor(&encode_fqdn("x.b")).map(|(_, v)| *v), Some(7));
+    assert_eq!(trie.get_floor(&encode_fqdn("c")).map(|(_, v)| *v), Some(7));
+    assert_eq!(trie.get_floor(&encode_fqdn("aa")).map(|(_, v)| *v), Some(4));
+    assert_eq!(trie.get_floor(&encode_fqdn("y.z")).map(|(_, v)| *v), Some(10));
+    assert_eq!(trie.get_floor(&encode_fqdn("\\000.z")).map(|(_, v)| *v), Some(8));
+    assert_eq!(trie.get_floor(&encode_fqdn("\\255.z")).map(|(_, v)| *v), Some(11));
+    assert_eq!(trie.get_floor(&[0x00]).map(|(_, v)| *v), Some(0));
+    assert_eq!(trie.get_floor_by(&encode_fqdn("ftp.b"), |v| *v != 5).map(|(_, v)| *v), Some(4));
+    assert!(trie.get_floor(&[]).is_none());
+
+    assert_eq!(encode_fqdn("\\065.z"), encode_fqdn("a.z"));
+    assert_eq!(encode_fqdn("WwW.B"), encode_fqdn("www.b"));
+    assert_eq!(decode_fqdn(&encode_fqdn("\\000\\..z")), "\\000\\..z");
+}
@@ -0,0 +1,292 @@
+use std::collections::HashSet;
+use crate::messages::inter::op_codes::OpCodes;
+use crate::messages::inter::response_codes::ResponseCodes;
+use crate::messages::inter::rr_classes::RRClasses;
+use crate::messages::inter::rr_types::RRTypes;
+use crate::messages::message::Message;
+use crate::messages::rr_query::RRQuery;
+use crate::rr_data::cname_rr_data::CNameRRData;
+use crate::rr_data::dname_rr_data::DNameRRData;
+use crate::rr_data::inter::rr_data::RRData;
+use crate::rr_data::ns_rr_data::NsRRData;
+use crate::utils::fqdn_utils::{fqdn_to_relative, to_fqdn};
+use crate::zone::rr_set::RRSet;
+use crate::zone::zone::Zone;
+use crate::zone::zone_store::ZoneStore;
+
+pub const MAX_CNAME_CHAIN: usize = 16;
+pub const MAX_NAME_LENGTH: usize = 255;
+
+#[derive(Debug, Clone)]
+pub struct Authority {
+    store: ZoneStore
+}
+
+impl Authority {
+
+    pub fn new(store: ZoneStore) -> Self {
+        Self {
+            store
+        }
+    }
+
+    pub fn store(&self) -> &ZoneStore {
+        &self.store
+    }
+
+    pub fn store_mut(&mut self) -> &mut ZoneStore {
+        &mut self.store
+    }
+
+    pub fn answer(&self, query: &Message) -> Message {
+        let mut response = Message::new(query.id());
+        response.set_qr(true);
+        response.set_op_code(query.op_code());
+        response.set_recursion_desired(query.is_recursion_desired());
+
+        for q in query.queries() {
+            response.add_query(q.clone());
+        }
+
+        if !query.op_code().eq(&OpCodes::Query) {
+            response.set_response_code(ResponseCodes::NotImp);
+            return response;
+        }
+
+        let q = match query.queries().first() {
+            Some(q) => q,
+            None => {
+                response.set_response_code(ResponseCodes::FormErr);
+                return response;
+            }
+        };
+
+        match self.store.deepest_zone(&q.fqdn().to_lowercase(), &q.class()) {
+            Some((apex, zone)) if zone.is_authority() => {
+                response.set_authoritative(true);
+                Self::answer_from_zone(&mut response, &apex, zone, q);
+            }
+            _ => response.set_response_code(ResponseCodes::Refused)
+        }
+
+        response
+    }
+
+    fn answer_from_zone(response: &mut Message, apex: &str, zone: &Zone, q: &RRQuery) {
+        let class = zone.class();
+
+        let mut name = match fqdn_to_relative(apex, &q.fqdn().to_lowercase()) {
+            Some(name) => name,
+            None => {
+                response.set_response_code(ResponseCodes::Refused);
+                return;
+            }
+        };
+
+        let mut visited = HashSet::new();
+
+        for _ in 0..MAX_CNAME_CHAIN {
+            if !visited.insert(name.clone()) {
+                return;
+            }
+
+            if let Some((cut, ns)) = zone.delegation_point(&name) {
+                if !(q.rtype().eq(&RRTypes::Ds) && cut.eq(&name)) {
+                    if !response.has_section(0) {
+                        response.set_authoritative(false);
+                    }
+
+                    Self::add_rr_set(response, 1, apex, &cut, class, ns);
+                    Self::add_glue(response, apex, zone, ns);
+                    return;
+                }
+            }
+
+            if let Some((owner, dname)) = zone.dname_point(&name) {
+                Self::add_rr_set(response, 0, apex, &owner, class, dname);
+
+                let target = dname.data()
+                    .first()
+                    .and_then(|d| d.as_any().downcast_ref::<DNameRRData>())
+                    .and_then(|d| d.target());
+
+                let target = match target {
+                    Some(target) => target,
+                    None => return
+                };
+
+                let prefix = match owner.is_empty() {
+                    true => name.clone(),
+                    false => name[..name.len() - owner.len() - 1].to_string()
+                };
+                let substituted = to_fqdn(target, &prefix);
+
+                if substituted.len() + 2 > MAX_NAME_LENGTH {
+                    response.set_response_code(ResponseCodes::YxDomain);
+                    return;
+                }
+
+                response.add_section(0, &to_fqdn(apex, &name), class, RRTypes::CName, dname.ttl(), Some(CNameRRData::new(&substituted).upcast()));
+
+                match fqdn_to_relative(apex, &substituted.to_lowercase()) {
+                    Some(target) => {
+                        name = target;
+                        continue;
+                    }
+                    None => return
+                }
+            }
+
+            match zone.find_rr_sets(&name) {
+                Some((sets, _synthesized)) => {
+                    if q.rtype().eq(&RRTypes::Any) {
+                        for set in sets {
+                            Self::add_rr_set(response, 0, apex, &name, class, set);
+                        }
+                        return;
+                    }
+
+                    if let Some(set) = sets.iter().find(|s| s.rtype().eq(&q.rtype())) {
+                        Self::add_rr_set(response, 0, apex, &name, class, set);
+                        return;
+                    }
+
+                    match sets.iter().find(|s| s.rtype().eq(&RRTypes::CName)) {
+                        Some(set) => {
+                            Self::add_rr_set(response, 0, apex, &name, class, set);
+
+                            let target = set.data()
+                                .first()
+                                .and_then(|d| d.as_any().downcast_ref::<CNameRRData>())
+                                .and_then(|d| d.target())
+                                .and_then(|t| fqdn_to_relative(apex, &t.to_lowercase()));
+
+                            match target {
+                                Some(target) => name = target,
+                                None => return
+                            }
+                        }
+                        None => {
+                            Self::add_soa(response, apex, zone);
+                            return;
+                        }
+                    }
+                }
+                None => {
+                    if !zone.name_exists(&name) {
+                        response.set_response_code(ResponseCodes::NxDomain);
+                    }
+
+                    Self::add_soa(response, apex, zone);
+                    return;
+                }
+            }
+        }
+
+        response.set_response_code(ResponseCodes::ServFail);
+    }
+
+    fn add_rr_set(response: &mut Message, index: usize, apex: &str, name: &str, class: RRClasses, set: &RRSet) {
+        let fqdn = to_fqdn(apex, name);
+
+        for data in set.data() {
+            response.add_section(index, &fqdn, class, set.rtype(), set.ttl(), Some(data.clone()));
+        }
+    }
+
+    fn add_glue(response: &mut Message, apex: &str, zone: &Zone, ns: &RRSet) {
+        for data in ns.data() {
+            let server = match data.as_any().downcast_ref::<NsRRData>().and_then(|d| d.server()) {
+                Some(server) => server,
+                None => continue
+            };
+
+            if let Some(name) = fqdn_to_relative(apex, &server.to_lowercase()) {
+                for rtype in [RRTypes::A, RRTypes::Aaaa] {
+                    if let Some(set) = zone.rr_set(&name, &rtype) {
+                        Self::add_rr_set(response, 2, apex, &name, zone.class(), set);
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_soa(response: &mut Message, apex: &str, zone: &Zone) {
+        if let (Some(set), Some(soa)) = (zone.rr_set("", &RRTypes::Soa), zone.soa()) {
+            response.add_section(1, apex, zone.class(), RRTypes::Soa, set.ttl().min(soa.minimum_ttl()), set.data().first().cloned());
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use std::net::Ipv4Addr;
+    use crate::rr_data::in_a_rr_data::InARRData;
+    use crate::rr_data::soa_rr_data::SoaRRData;
+    use crate::zone::inter::zone_types::ZoneTypes;
+
+    let mut zone = Zone::new(ZoneTypes::Master, RRClasses::In);
+    zone.add_record("", RRTypes::Soa, 3600, SoaRRData::new("ns1.find9.net", "admin.find9.net", 1, 3600, 600, 604800, 300).upcast());
+    zone.add_record("", RRTypes::Ns, 3600, NsRRData::new("ns1.find9.net").upcast());
+    zone.add_record("ns1", RRTypes::A, 300, InARRData::new(Ipv4Addr::new(127, 0, 0, 1)).upcast());
+    zone.add_record("www", RRTypes::A, 300, InARRData::new(Ipv4Addr::new(127, 0, 0, 2)).upcast());
+    zone.add_record("alias", RRTypes::CName, 300, CNameRRData::new("www.find9.net").upcast());
+    zone.add_record("a.b", RRTypes::A, 300, InARRData::new(Ipv4Addr::new(127, 0, 0, 3)).upcast());
+    zone.add_record("*.wild", RRTypes::A, 300, InARRData::new(Ipv4Addr::new(127, 0, 0, 5)).upcast());
+    zone.add_record("old", RRTypes::DName, 300, DNameRRData::new("find9.net").upcast());
+    zone.add_record("sub", RRTypes::Ns, 3600, NsRRData::new("ns.sub.find9.net").upcast());
+    zone.add_record("ns.sub", RRTypes::A, 3600, InARRData::new(Ipv4Addr::new(127, 0, 0, 4)).upcast());
+    zone.add_record("loop1", RRTypes::CName, 300, CNameRRData::new("loop2.find9.net").upcast());
+    zone.add_record("loop2", RRTypes::CName, 300, CNameRRData::new("loop1.find9.net").upcast());
+
+    let mut store = ZoneStore::new();
+    store.add_zone("find9.net", zone);
+    let authority = Authority::new(store);
+
+    let query = |fqdn: &str, rtype: RRTypes| {
+        let mut message = Message::new(1);
+        message.add_query(RRQuery::new(fqdn, rtype, RRClasses::In));
+        authority.answer(&message)
+    };
+
+    let response = query("www.find9.net", RRTypes::A);
+    assert!(response.is_authoritative());
+    assert_eq!(response.total_section(0), 1);
+
+    let response = query("alias.find9.net", RRTypes::A);
+    assert_eq!(response.total_section(0), 2);
+    assert_eq!(response.section(0)[1].fqdn(), "www.find9.net");
+
+    let response = query("nope.find9.net", RRTypes::A);
+    assert_eq!(response.response_code(), ResponseCodes::NxDomain);
+    assert_eq!(response.section(1)[0].ttl(), 300);
+
+    let response = query("b.find9.net", RRTypes::A);
+    assert_eq!(response.response_code(), ResponseCodes::NoError);
+    assert_eq!(response.total_section(0), 0);
+    assert_eq!(response.total_section(1), 1);
+
+    let response = query("x.sub.find9.net", RRTypes::A);
+    assert!(!response.is_authoritative());
+    assert_eq!(response.section(1)[0].rtype(), RRTypes::Ns);
+    assert_eq!(response.section(2)[0].fqdn(), "ns.sub.find9.net");
+
+    let response = query("a.wild.find9.net", RRTypes::A);
+    assert_eq!(response.total_section(0), 1);
+    assert_eq!(response.section(0)[0].fqdn(), "a.wild.find9.net");
+
+    let response = query("www.old.find9.net", RRTypes::A);
+    assert_eq!(response.total_section(0), 3);
+    assert_eq!(response.section(0)[0].rtype(), RRTypes::DName);
+    assert_eq!(response.section(0)[1].rtype(), RRTypes::CName);
+    assert_eq!(response.section(0)[2].fqdn(), "www.find9.net");
+
+    let response = query("loop1.find9.net", RRTypes::A);
+    assert_eq!(response.response_code(), ResponseCodes::NoError);
+    assert_eq!(response.total_section(0), 2);
+    assert_eq!(response.section(0)[0].fqdn(), "loop1.find9.net");
+    assert_eq!(response.section(0)[1].fqdn(), "loop2.find9.net");
+
+    let response = query("example.com", RRTypes::A);
+    assert_eq!(response.response_code(), ResponseCodes::Refused);
+}
@@ -0,0 +1,269 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::keyring::key::Key;
+use crate::messages::inter::op_codes::OpCodes;
+use crate::messages::inter::response_codes::ResponseCodes;
+use crate::messages::inter::rr_types::RRTypes;
+use crate::messages::message::Message;
+use crate::messages::record::Record;
+use crate::messages::rr_query::RRQuery;
+use crate::messages::tsig::{TSig, DEFAULT_TSIG_FUDGE};
+use crate::messages::wire::{ToWire, ToWireContext};
+use crate::rr_data::tsig_rr_data::TSigRRData;
+use crate::utils::fqdn_utils::{pack_fqdn, to_fqdn};
+use crate::zone::zone::Zone;
+
+pub const MAX_MESSAGE_LEN: usize = 65535;
+
+#[derive(Debug, Clone)]
+pub struct AxfrResponder {
+    id: u16,
+    query: Option<RRQuery>,
+    response_code: ResponseCodes,
+    records: Vec<Record>,
+    position: usize,
+    max_payload_len: usize,
+    tsig: Option<(TSig, Key)>,
+    prior_mac: Vec<u8>,
+    first: bool,
+    done: bool
+}
+
+impl AxfrResponder {
+
+    // The query TSIG is not verified here, its MAC is only copied to chain the first response.
+    // Callers must verify the query against the key before building the responder.
+    pub fn new(query: &Message, apex: &str, zone: &Zone) -> Self {
+        let q = query.queries().first();
+
+        let (response_code, records) = match q {
+            Some(q) if query.op_code().eq(&OpCodes::Query) && q.rtype().eq(&RRTypes::Axfr) && query.queries().len() == 1 => {
+                if !zone.is_authority() || !q.class().eq(&zone.class()) || !q.fqdn().eq_ignore_ascii_case(apex) {
+                    (ResponseCodes::NotAuth, Vec::new())
+
+                } else {
+                    match Self::collect_records(apex, zone) {
+                        Some(records) => (ResponseCodes::NoError, records),
+                        None => (ResponseCodes::ServFail, Vec::new())
+                    }
+                }
+            }
+            _ => (ResponseCodes::FormErr, Vec::new())
+        };
+
+        Self {
+            id: query.id(),
+            query: q.cloned(),
+            response_code,
+            records,
+            position: 0,
+            max_payload_len: MAX_MESSAGE_LEN,
+            tsig: None,
+            prior_mac: query.tsig().and_then(|tsig| tsig.data().mac().cloned()).unwrap_or_default(),
+            first: true,
+            done: false
+        }
+    }
+
+    pub fn set_max_payload_len(&mut self, max_payload_len: usize) {
+        self.max_payload_len = max_payload_len.min(MAX_MESSAGE_LEN);
+    }
+
+    pub fn max_payload_len(&self) -> usize {
+        self.max_payload_len
+    }
+
+    pub fn set_key(&mut self, key_name: &str, key: &Key) -> bool {
+        if self.prior_mac.is_empty() {
+            return false;
+        }
+
+        let data = TSigRRData::new(key.algorithm(), 0, DEFAULT_TSIG_FUDGE, self.id, ResponseCodes::NoError, &[]);
+        self.tsig = Some((TSig::new(key_name, data), key.clone()));
+        true
+    }
+
+    pub fn response_code(&self) -> ResponseCodes {
+        self.response_code
+    }
+
+    pub fn total_records(&self) -> usize {
+        self.records.len()
+    }
+
+    fn collect_records(apex: &str, zone: &Zone) -> Option<Vec<Record>> {
+        let class = zone.class();
+        let soa = zone.rr_set("", &RRTypes::Soa)?;
+        let soa = Record::new(apex, class, RRTypes::Soa, soa.ttl(), Some(soa.data().first()?.clone()));
+
+        let mut records = vec![soa.clone()];
+
+        for (name, sets) in zone.all_rr_sets_recursive() {
+            let fqdn = to_fqdn(apex, &name);
+
+            for set in sets {
+                if name.is_empty() && set.rtype().eq(&RRTypes::Soa) {
+                    continue;
+                }
+
+                for data in set.data() {
+                    records.push(Record::new(&fqdn, class, set.rtype(), set.ttl(), Some(data.clone())));
+                }
+            }
+        }
+
+        records.push(soa);
+        Some(records)
+    }
+
+    fn tsig_reserve(&self) -> usize {
+        match self.tsig.as_ref() {
+            Some((tsig, key)) => pack_fqdn(tsig.owner()).len() + 10
+                + pack_fqdn(&key.algorithm().to_string()).len() + 16 + 64,
+            None => 0
+        }
+    }
+}
+
+impl Iterator for AxfrResponder {
+
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let reserve = self.tsig_reserve();
+        let mut context = ToWireContext::with_capacity(self.max_payload_len.saturating_sub(reserve));
+
+        self.id.to_wire(&mut context).ok()?;
+        context.write(&[0; 10]).ok()?;
+
+        if self.first {
+            if let Some(query) = self.query.as_ref() {
+                let checkpoint = context.pos();
+                match query.to_wire(&mut context) {
+                    Ok(_) => context.patch(4..6, &1u16.to_be_bytes()).ok()?,
+                    Err(_) => context.rollback(checkpoint)
+                }
+            }
+        }
+
+        if self.response_code.eq(&ResponseCodes::NoError) {
+            let mut count: u16 = 0;
+            while self.position < self.records.len() {
+                let checkpoint = context.pos();
+                if self.records[self.position].to_wire(&mut context).is_err() {
+                    context.rollback(checkpoint);
+                    break;
+                }
+                self.position += 1;
+                count += 1;
+            }
+
+            if count == 0 {
+                self.response_code = ResponseCodes::ServFail;
+            }
+            context.patch(6..8, &count.to_be_bytes()).ok()?;
+        }
+
+        let flags = 0x8000 |  // QR bit
+            ((OpCodes::Query.code() as u16 & 0x0F) << 11) |  // Opcode
+            (if self.response_code.eq(&ResponseCodes::NoError) { 0x0400 } else { 0 }) |  // AA bit
+            (self.response_code.code() & 0x000F);  // RCODE
+        context.patch(2..4, &flags.to_be_bytes()).ok()?;
+
+        let mut buf = context.into_bytes();
+
+        if let Some((tsig, key)) = self.tsig.as_mut() {
+            tsig.data_mut().set_time_signed(SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0));
+            tsig.set_chained_payload(&self.prior_mac, &buf, !self.first);
+
+            let mut context = ToWireContext::with_capacity(reserve);
+            if !tsig.sign(key) || tsig.to_wire(&mut context).is_err() {
+                self.done = true;
+                return None;
+            }
+
+            buf.extend_from_slice(&context.into_bytes());
+            buf[10..12].copy_from_slice(&1u16.to_be_bytes());
+
+            self.prior_mac = tsig.data().mac().cloned().unwrap_or_default();
+        }
+
+        self.first = false;
+        self.done = self.position >= self.records.len() || !self.response_code.eq(&ResponseCodes::NoError);
+
+        Some(buf)
+    }
+}
+
+#[test]
+fn test() {
+    use std::net::Ipv4Addr;
+    use crate::keyring::inter::algorithms::Algorithms;
+    use crate::messages::inter::rr_classes::RRClasses;
+    use crate::rr_data::in_a_rr_data::InARRData;
+    use crate::rr_data::inter::rr_data::RRData;
+    use crate::rr_data::soa_rr_data::SoaRRData;
+    use crate::zone::inter::zone_types::ZoneTypes;
+
+    let mut zone = Zone::new(ZoneTypes::Master, RRClasses::In);
+    zone.add_record("", RRTypes::Soa, 3600, SoaRRData::new("ns1.find9.net", "admin.find9.net", 7, 3600, 600, 604800, 300).upcast());
+    for i in 0..40u8 {
+        zone.add_record(&format!("host{}", i), RRTypes::A, 300, InARRData::new(Ipv4Addr::new(10, 0, 0, i)).upcast());
+    }
+
+    let mut query = Message::new(0x1234);
+    query.add_query(RRQuery::new("find9.net", RRTypes::Axfr, RRClasses::In));
+
+    let key = Key::new(b"axfr-secret".to_vec(), Algorithms::HmacSha256);
+    let mut responder = AxfrResponder::new(&query, "find9.net", &zone);
+    assert!(!responder.set_key("xfr-key", &key));
+
+    query.set_tsig(TSig::new("xfr-key", TSigRRData::new(key.algorithm(), 0, DEFAULT_TSIG_FUDGE, 0x1234, ResponseCodes::NoError, &[])));
+    query.to_bytes_with_sig(512, &key);
+    let mut prior_mac = query.tsig().unwrap().data().mac().unwrap().clone();
+
+    let mut responder = AxfrResponder::new(&query, "find9.net", &zone);
+    responder.set_max_payload_len(512);
+    assert!(responder.set_key("xfr-key", &key));
+    assert_eq!(responder.total_records(), 42);
+
+    let mut answers = Vec::new();
+    for (i, buf) in responder.enumerate() {
+        assert!(buf.len() <= 512);
+
+        let message = Message::from_bytes(&buf).unwrap();
+        assert_eq!(message.id(), 0x1234);
+        assert!(message.is_authoritative());
+        assert_eq!(message.has_queries(), i == 0);
+
+        let mut tsig = message.tsig().unwrap().clone();
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        assert!(now.abs_diff(tsig.data().time_signed()) <= DEFAULT_TSIG_FUDGE as u64);
+        let mut context = ToWireContext::with_capacity(512);
+        tsig.to_wire(&mut context).unwrap();
+        let mut unsigned = buf[..buf.len() - context.pos()].to_vec();
+        unsigned[10..12].copy_from_slice(&0u16.to_be_bytes());
+
+        tsig.set_chained_payload(&prior_mac, &unsigned, i > 0);
+        assert!(tsig.verify(&key).is_ok());
+        tsig.set_chained_payload(&prior_mac, &unsigned, i == 0);
+        assert_eq!(tsig.verify(&key), Err(ResponseCodes::BadSig));
+        prior_mac = tsig.data().mac().unwrap().clone();
+
+        answers.extend(message.section(0).iter().map(|r| r.rtype()));
+    }
+
+    assert_eq!(answers.len(), 42);
+    assert_eq!(answers.first(), Some(&RRTypes::Soa));
+    assert_eq!(answers.last(), Some(&RRTypes::Soa));
+    assert_eq!(answers.iter().filter(|t| t.eq(&&RRTypes::A)).count(), 40);
+
+    let mut query = Message::new(0x1234);
+    query.add_query(RRQuery::new("other.net", RRTypes::Axfr, RRClasses::In));
+    let messages: Vec<Vec<u8>> = AxfrResponder::new(&query, "find9.net", &zone).collect();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(Message::from_bytes(&messages[0]).unwrap().response_code(), ResponseCodes::NotAuth);
+}
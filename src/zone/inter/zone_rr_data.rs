@@ -1,10 +1,18 @@
 use crate::messages::inter::rr_classes::RRClasses;
 use crate::messages::inter::rr_types::RRTypes;
+use crate::messages::wire::{FromWireContext, FromWireLen, WireError};
 use crate::rr_data::{
     in_a_rr_data::InARRData,
     ch_a_rr_data::ChARRData,
     aaaa_rr_data::AaaaRRData,
+    caa_rr_data::CaaRRData,
+    cert_rr_data::CertRRData,
+    tlsa_rr_data::TlsaRRData,
+    cds_rr_data::CdsRRData,
+    cdnskey_rr_data::CdnsKeyRRData,
+    openpgpkey_rr_data::OpenPgpKeyRRData,
     cname_rr_data::CNameRRData,
+    dname_rr_data::DNameRRData,
     dnskey_rr_data::DnsKeyRRData,
     ds_rr_data::DsRRData,
     hinfo_rr_data::HInfoRRData,
@@ -17,6 +25,7 @@ use crate::rr_data::{
     nsec3_rr_data::NSec3RRData,
     nsec3param_rr_data::NSec3ParamRRData,
     ptr_rr_data::PtrRRData,
+    raw_rr_data::RawRRData,
     rrsig_rr_data::RRSigRRData,
     smimea_rr_data::SmimeaRRData,
     soa_rr_data::SoaRRData,
@@ -26,7 +35,8 @@ use crate::rr_data::{
     sshfp_rr_data::SshFpRRData,
     svcb_rr_data::SvcbRRData,
     txt_rr_data::TxtRRData,
-    uri_rr_data::UriRRData
+    uri_rr_data::UriRRData,
+    zonemd_rr_data::ZoneMdRRData
 };
 
 use crate::rr_data::inter::rr_data::RRData;
@@ -52,6 +62,7 @@ impl dyn ZoneRRData {
             RRTypes::Aaaa   => <AaaaRRData as ZoneRRData>::upcast(AaaaRRData::default()),
             RRTypes::Ns     => <NsRRData as ZoneRRData>::upcast(NsRRData::default()),
             RRTypes::CName  => <CNameRRData as ZoneRRData>::upcast(CNameRRData::default()),
+            RRTypes::DName  => <DNameRRData as ZoneRRData>::upcast(DNameRRData::default()),
             RRTypes::Soa    => <SoaRRData as ZoneRRData>::upcast(SoaRRData::default()),
             RRTypes::Ptr    => <PtrRRData as ZoneRRData>::upcast(PtrRRData::default()),
             RRTypes::HInfo  => <HInfoRRData as ZoneRRData>::upcast(HInfoRRData::default()),
@@ -77,15 +88,66 @@ impl dyn ZoneRRData {
             //RRTypes::TKey   => <TKeyRRData as ZoneRRData>::upcast(TKeyRRData::default()),
             //RRTypes::TSig   => <TSigRRData as ZoneRRData>::upcast(TSigRRData::default()),
             RRTypes::Uri    => <UriRRData as ZoneRRData>::upcast(UriRRData::default()),
-            /*RRTypes::Caa => {
-                todo!()
+            RRTypes::Caa    => <CaaRRData as ZoneRRData>::upcast(CaaRRData::default()),
+            RRTypes::Cert   => <CertRRData as ZoneRRData>::upcast(CertRRData::default()),
+            RRTypes::Tlsa   => <TlsaRRData as ZoneRRData>::upcast(TlsaRRData::default()),
+            RRTypes::Cds    => <CdsRRData as ZoneRRData>::upcast(CdsRRData::default()),
+            RRTypes::CdnsKey=> <CdnsKeyRRData as ZoneRRData>::upcast(CdnsKeyRRData::default()),
+            RRTypes::OpenPGPKey =>  <OpenPgpKeyRRData as ZoneRRData>::upcast(OpenPgpKeyRRData::default()),
+            RRTypes::ZoneMd => <ZoneMdRRData as ZoneRRData>::upcast(ZoneMdRRData::default()),
+            RRTypes::Unknown(_) | RRTypes::Hip | RRTypes::Spf => <RawRRData as ZoneRRData>::upcast(RawRRData::default()),
+            // pseudo/unsupported types:
+            _ => return None
+        })
+    }
+
+    pub fn from_wire(context: &mut FromWireContext, len: u16, rtype: RRTypes, class: &RRClasses) -> Result<Box<dyn ZoneRRData>, WireError> {
+        Ok(match rtype {
+            RRTypes::A      => {
+                match class {
+                    RRClasses::Ch => <ChARRData as ZoneRRData>::upcast(ChARRData::from_wire_len(context, len)?),
+                    _ => <InARRData as ZoneRRData>::upcast(InARRData::from_wire_len(context, len)?)
+                }
             }
-            _ => {
+            RRTypes::Aaaa   => <AaaaRRData as ZoneRRData>::upcast(AaaaRRData::from_wire_len(context, len)?),
+            RRTypes::Ns     => <NsRRData as ZoneRRData>::upcast(NsRRData::from_wire_len(context, len)?),
+            RRTypes::CName  => <CNameRRData as ZoneRRData>::upcast(CNameRRData::from_wire_len(context, len)?),
+            RRTypes::DName  => <DNameRRData as ZoneRRData>::upcast(DNameRRData::from_wire_len(context, len)?),
+            RRTypes::Soa    => <SoaRRData as ZoneRRData>::upcast(SoaRRData::from_wire_len(context, len)?),
+            RRTypes::Ptr    => <PtrRRData as ZoneRRData>::upcast(PtrRRData::from_wire_len(context, len)?),
+            RRTypes::HInfo  => <HInfoRRData as ZoneRRData>::upcast(HInfoRRData::from_wire_len(context, len)?),
+            RRTypes::Mx     => <MxRRData as ZoneRRData>::upcast(MxRRData::from_wire_len(context, len)?),
+            RRTypes::Txt    => <TxtRRData as ZoneRRData>::upcast(TxtRRData::from_wire_len(context, len)?),
+            RRTypes::Loc    => <LocRRData as ZoneRRData>::upcast(LocRRData::from_wire_len(context, len)?),
+            RRTypes::Srv    => <SrvRRData as ZoneRRData>::upcast(SrvRRData::from_wire_len(context, len)?),
+            RRTypes::Naptr  => <NaptrRRData as ZoneRRData>::upcast(NaptrRRData::from_wire_len(context, len)?),
+            RRTypes::Ds     => <DsRRData as ZoneRRData>::upcast(DsRRData::from_wire_len(context, len)?),
+            RRTypes::SshFp  => <SshFpRRData as ZoneRRData>::upcast(SshFpRRData::from_wire_len(context, len)?),
+            RRTypes::RRSig  => <RRSigRRData as ZoneRRData>::upcast(RRSigRRData::from_wire_len(context, len)?),
+            RRTypes::NSec   => <NSecRRData as ZoneRRData>::upcast(NSecRRData::from_wire_len(context, len)?),
+            RRTypes::DnsKey => <DnsKeyRRData as ZoneRRData>::upcast(DnsKeyRRData::from_wire_len(context, len)?),
+            RRTypes::NSec3  => <NSec3RRData as ZoneRRData>::upcast(NSec3RRData::from_wire_len(context, len)?),
+            RRTypes::NSec3Param   => <NSec3ParamRRData as ZoneRRData>::upcast(NSec3ParamRRData::from_wire_len(context, len)?),
+            RRTypes::Smimea => <SmimeaRRData as ZoneRRData>::upcast(SmimeaRRData::from_wire_len(context, len)?),
+            RRTypes::Svcb   => <SvcbRRData as ZoneRRData>::upcast(SvcbRRData::from_wire_len(context, len)?),
+            RRTypes::Https  => <HttpsRRData as ZoneRRData>::upcast(HttpsRRData::from_wire_len(context, len)?),
+            /*
+            RRTypes::Spf => {
                 todo!()
-            }
-            */
+            }*/
+            //RRTypes::TKey   => <TKeyRRData as ZoneRRData>::upcast(TKeyRRData::from_wire_len(context, len)?),
+            //RRTypes::TSig   => <TSigRRData as ZoneRRData>::upcast(TSigRRData::from_wire_len(context, len)?),
+            RRTypes::Uri    => <UriRRData as ZoneRRData>::upcast(UriRRData::from_wire_len(context, len)?),
+            RRTypes::Caa    => <CaaRRData as ZoneRRData>::upcast(CaaRRData::from_wire_len(context, len)?),
+            RRTypes::Cert   => <CertRRData as ZoneRRData>::upcast(CertRRData::from_wire_len(context, len)?),
+            RRTypes::Tlsa   => <TlsaRRData as ZoneRRData>::upcast(TlsaRRData::from_wire_len(context, len)?),
+            RRTypes::Cds    => <CdsRRData as ZoneRRData>::upcast(CdsRRData::from_wire_len(context, len)?),
+            RRTypes::CdnsKey=> <CdnsKeyRRData as ZoneRRData>::upcast(CdnsKeyRRData::from_wire_len(context, len)?),
+            RRTypes::OpenPGPKey =>  <OpenPgpKeyRRData as ZoneRRData>::upcast(OpenPgpKeyRRData::from_wire_len(context, len)?),
+            RRTypes::ZoneMd => <ZoneMdRRData as ZoneRRData>::upcast(ZoneMdRRData::from_wire_len(context, len)?),
+            RRTypes::Unknown(_) | RRTypes::Hip | RRTypes::Spf => <RawRRData as ZoneRRData>::upcast(RawRRData::from_wire_len(context, len)?),
             // pseudo/unsupported types:
-            _ => return None
+            _ => return Err(WireError::Format("invalid rr_type".to_string()))
         })
     }
 }
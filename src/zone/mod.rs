@@ -2,4 +2,10 @@ pub mod inter;
 pub mod zone_store;
 pub mod zone;
 pub mod zone_reader;
+pub mod zone_writer;
+pub mod authority;
 pub mod rr_set;
+pub mod rr_set_v2;
+pub mod axfr_responder;
+pub mod transfer_reader;
+pub mod update;
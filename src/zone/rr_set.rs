@@ -1,5 +1,8 @@
+use crate::messages::inter::rr_classes::RRClasses;
 use crate::messages::inter::rr_types::RRTypes;
+use crate::messages::wire::WireError;
 use crate::rr_data::inter::rr_data::RRData;
+use crate::utils::fqdn_utils::pack_fqdn;
 
 #[derive(Debug, Clone)]
 pub struct RRSet {
@@ -62,4 +65,66 @@ impl RRSet {
     pub fn is_empty(&self) -> bool {
         self.data.len() == 0
     }
+
+    pub fn canonical_data(&self) -> Result<Vec<Vec<u8>>, WireError> {
+        let mut data = self.data.iter()
+            .map(|d| d.to_canonical_bytes())
+            .collect::<Result<Vec<_>, _>>()?;
+        data.sort();
+        data.dedup();
+        Ok(data)
+    }
+
+    pub fn sort_canonical(&mut self) -> Result<(), WireError> {
+        let mut keyed = self.data.drain(..)
+            .map(|d| d.to_canonical_bytes().map(|k| (k, d)))
+            .collect::<Result<Vec<_>, _>>()?;
+        keyed.sort_by(|a, b| a.0.cmp(&b.0));
+        keyed.dedup_by(|a, b| a.0.eq(&b.0));
+
+        self.data = keyed.into_iter().map(|(_, d)| d).collect();
+        Ok(())
+    }
+
+    pub fn to_canonical_bytes(&self, fqdn: &str, class: RRClasses, ttl: u32) -> Result<Vec<u8>, WireError> {
+        let owner = pack_fqdn(&fqdn.to_lowercase());
+        let mut buf = Vec::new();
+
+        for rdata in self.canonical_data()? {
+            buf.extend_from_slice(&owner);
+            buf.extend_from_slice(&self.rtype.code().to_be_bytes());
+            buf.extend_from_slice(&class.code().to_be_bytes());
+            buf.extend_from_slice(&ttl.to_be_bytes());
+            buf.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+            buf.extend_from_slice(&rdata);
+        }
+
+        Ok(buf)
+    }
+}
+
+#[test]
+fn test() {
+    use crate::rr_data::mx_rr_data::MxRRData;
+    use crate::rr_data::nsec_rr_data::NSecRRData;
+
+    let mut set = RRSet::new(RRTypes::Mx, 300);
+    set.add_data(300, MxRRData::new(20, "Mail.Find9.net").upcast());
+    set.add_data(300, MxRRData::new(10, "mail2.find9.net").upcast());
+    set.add_data(300, MxRRData::new(20, "mail.find9.net").upcast());
+
+    let data = set.canonical_data().unwrap();
+    assert_eq!(data.len(), 2);
+    assert_eq!(data[0], [ &[0x0, 0xa, 0x5][..], b"mail2", &[0x5], b"find9", &[0x3], b"net", &[0x0] ].concat());
+    assert_eq!(data[1], [ &[0x0, 0x14, 0x4][..], b"mail", &[0x5], b"find9", &[0x3], b"net", &[0x0] ].concat());
+
+    set.sort_canonical().unwrap();
+    assert_eq!(set.data().len(), 2);
+    assert_eq!(set.data()[0].as_any().downcast_ref::<MxRRData>().unwrap().priority(), 10);
+
+    let nsec = NSecRRData::new("Www.find9.net", vec![RRTypes::A]);
+    assert_eq!(&nsec.to_canonical_bytes().unwrap()[..4], &[0x3, b'W', b'w', b'w']);
+
+    let buf = set.to_canonical_bytes("FIND9.net", RRClasses::In, 300).unwrap();
+    assert_eq!(&buf[..11], &[ &[0x5][..], b"find9", &[0x3], b"net", &[0x0] ].concat()[..]);
 }
@@ -1,5 +1,6 @@
 use crate::messages::inter::rr_classes::RRClasses;
 use crate::messages::inter::rr_types::RRTypes;
+use crate::messages::wire::{FromWireContext, WireError};
 use crate::rr_data::inter::rr_data::RRData;
 
 #[derive(Debug, Clone)]
@@ -22,7 +23,7 @@ impl RRSet {
     }
 
     pub fn set_rtype(&mut self, rtype: RRTypes) {
-        self._type = rtype;
+        self.rtype = rtype;
     }
 
     pub fn rtype(&self) -> RRTypes {
@@ -37,30 +38,48 @@ impl RRSet {
         self.ttl
     }
 
-    pub fn add_data(&mut self, ttl: u32, data: Box<dyn RRData>) {
+    pub fn add_data(&mut self, ttl: u32, data: Box<dyn RRData>) -> Result<(), WireError> {
+        let buf = data.to_canonical_bytes()?;
+
         if self.ttl != ttl {
             self.ttl = self.ttl.min(ttl);
         }
 
-        self.data.extend_from_slice(&data.to_bytes().unwrap());
+        self.data.extend_from_slice(&(buf.len() as u16).to_be_bytes());
+        self.data.extend_from_slice(&buf);
+        Ok(())
     }
 
-    pub fn remove_data(&mut self, data: &Box<dyn RRData>, min_records: usize) -> bool {
-        if self.data.len() <= min_records {
+    pub fn remove_data(&mut self, data: &dyn RRData, min_records: usize) -> bool {
+        if self.total_data() <= min_records {
             return false;
         }
 
-        /*
-        if let Some(i) = self.data.iter().position(|b| b.eq(data)) {
-            self.data.remove(i);
-            return true;
+        let buf = match data.to_canonical_bytes() {
+            Ok(buf) => buf,
+            Err(_) => return false
+        };
+
+        let mut off = 0;
+        let mut found = None;
+        for entry in self.entries() {
+            if entry[2..].eq(&buf[..]) {
+                found = Some(off..off+entry.len());
+                break;
+            }
+            off += entry.len();
         }
-        */
 
-        false
+        match found {
+            Some(range) => {
+                self.data.drain(range);
+                true
+            }
+            None => false
+        }
     }
 
-    pub fn data(&self) -> RRSetIter {
+    pub fn data(&self) -> RRSetIter<'_> {
         RRSetIter {
             set: self,
             off: 0
@@ -68,7 +87,37 @@ impl RRSet {
     }
 
     pub fn total_data(&self) -> usize {
-        self.data.len()
+        self.entries().count()
+    }
+
+    pub fn canonical_data(&self) -> Vec<Vec<u8>> {
+        let mut data: Vec<Vec<u8>> = self.entries().map(|entry| entry[2..].to_vec()).collect();
+        data.sort();
+        data.dedup();
+        data
+    }
+
+    pub fn sort_canonical(&mut self) {
+        let mut entries: Vec<&[u8]> = self.entries().collect();
+        entries.sort_by(|a, b| a[2..].cmp(&b[2..]));
+        entries.dedup();
+
+        self.data = entries.concat();
+    }
+
+    fn entries(&self) -> impl Iterator<Item = &[u8]> {
+        let mut off = 0;
+
+        std::iter::from_fn(move || {
+            if off+2 > self.data.len() {
+                return None;
+            }
+
+            let len = u16::from_be_bytes([self.data[off], self.data[off+1]]) as usize;
+            let entry = self.data.get(off..off+2+len)?;
+            off += 2+len;
+            Some(entry)
+        })
     }
 }
 
@@ -82,13 +131,42 @@ impl<'a> Iterator for RRSetIter<'a> {
     type Item = Box<dyn RRData>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.off >= self.set.data.len() {
+        if self.off+2 > self.set.data.len() {
             return None;
         }
 
-        let data = <dyn RRData>::from_wire(self.set._type, &self.set.class, &self.set.data[self.off..], 0).unwrap();
-        self.off += 2+u16::from_be_bytes([self.set.data[self.off], self.set.data[self.off+1]]) as usize;
+        let len = u16::from_be_bytes([self.set.data[self.off], self.set.data[self.off+1]]);
+        let buf = self.set.data.get(self.off+2..self.off+2+len as usize)?;
+        self.off += 2+len as usize;
 
-        Some(data)
+        let mut context = FromWireContext::new(buf);
+        <dyn RRData>::from_wire(&mut context, len, &self.set.rtype, &self.set.class).ok()
     }
 }
+
+#[test]
+fn test() {
+    use crate::rr_data::mx_rr_data::MxRRData;
+
+    let mut set = RRSet::new(RRClasses::In, RRTypes::Mx, 300);
+    set.add_data(300, MxRRData::new(20, "Mail.Find9.net").upcast()).unwrap();
+    set.add_data(300, MxRRData::new(10, "mail2.find9.net").upcast()).unwrap();
+    set.add_data(300, MxRRData::new(20, "mail.find9.net").upcast()).unwrap();
+    assert_eq!(set.total_data(), 3);
+
+    let data = set.canonical_data();
+    assert_eq!(data.len(), 2);
+    assert_eq!(data[0], [ &[0x0, 0xa, 0x5][..], b"mail2", &[0x5], b"find9", &[0x3], b"net", &[0x0] ].concat());
+    assert_eq!(data[1], [ &[0x0, 0x14, 0x4][..], b"mail", &[0x5], b"find9", &[0x3], b"net", &[0x0] ].concat());
+
+    set.sort_canonical();
+    assert_eq!(set.total_data(), 2);
+    let records: Vec<Box<dyn RRData>> = set.data().collect();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].as_any().downcast_ref::<MxRRData>().unwrap().priority(), 10);
+    assert_eq!(records[1].as_any().downcast_ref::<MxRRData>().unwrap().server().unwrap(), "mail.find9.net");
+
+    assert!(set.remove_data(MxRRData::new(10, "MAIL2.find9.net").upcast().as_ref(), 1));
+    assert!(!set.remove_data(MxRRData::new(20, "mail.find9.net").upcast().as_ref(), 1));
+    assert_eq!(set.total_data(), 1);
+}
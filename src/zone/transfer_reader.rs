@@ -0,0 +1,505 @@
+use std::fmt;
+use std::fmt::Formatter;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::journal::inter::txn_op_codes::TxnOpCodes;
+use crate::journal::txn::Txn;
+use crate::keyring::key::Key;
+use crate::messages::inter::response_codes::ResponseCodes;
+use crate::messages::inter::rr_classes::RRClasses;
+use crate::messages::inter::rr_types::RRTypes;
+use crate::messages::message::Message;
+use crate::messages::record::Record;
+use crate::rr_data::soa_rr_data::SoaRRData;
+use crate::utils::fqdn_utils::fqdn_to_relative;
+use crate::zone::inter::zone_types::ZoneTypes;
+use crate::zone::zone::Zone;
+
+pub const MAX_UNSIGNED_MESSAGES: usize = 99;
+
+#[derive(Debug, Clone)]
+pub struct TransferReaderError {
+    _type: ErrorKind,
+    message: String
+}
+
+impl fmt::Display for TransferReaderError {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self._type, self.message)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ErrorKind {
+    BadQuery,
+    Format,
+    IdMismatch,
+    ResponseCode,
+    BadSoa,
+    SerialMismatch,
+    TSig,
+    Incomplete
+}
+
+impl TransferReaderError {
+
+    pub fn new(_type: ErrorKind, message: &str) -> Self {
+        Self {
+            _type,
+            message: message.to_string()
+        }
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self._type
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum State {
+    Start,
+    Opened,
+    Axfr,
+    Deleting,
+    Adding,
+    Done
+}
+
+#[derive(Debug, Clone)]
+pub struct TransferReader {
+    id: u16,
+    apex: String,
+    class: RRClasses,
+    rtype: RRTypes,
+    current_serial: Option<u32>,
+    key: Option<Key>,
+    prior_mac: Vec<u8>,
+    unsigned: Vec<u8>,
+    unsigned_count: usize,
+    total_messages: usize,
+    state: State,
+    soa: Option<Record>,
+    records: Vec<Record>,
+    txn: Option<Txn>,
+    txns: Vec<Txn>
+}
+
+impl TransferReader {
+
+    pub fn new(query: &Message) -> Result<Self, TransferReaderError> {
+        let q = match query.queries().as_slice() {
+            [q] if q.rtype().eq(&RRTypes::Axfr) || q.rtype().eq(&RRTypes::Ixfr) => q,
+            _ => return Err(TransferReaderError::new(ErrorKind::BadQuery, "query must contain a single AXFR or IXFR question"))
+        };
+
+        let current_serial = match q.rtype() {
+            RRTypes::Ixfr => Some(query.section(1).iter()
+                .filter(|r| r.rtype().eq(&RRTypes::Soa))
+                .find_map(Self::soa_serial)
+                .ok_or_else(|| TransferReaderError::new(ErrorKind::BadQuery, "IXFR query has no SOA in the authority section"))?),
+            _ => None
+        };
+
+        Ok(Self {
+            id: query.id(),
+            apex: q.fqdn().to_lowercase(),
+            class: q.class(),
+            rtype: q.rtype(),
+            current_serial,
+            key: None,
+            prior_mac: query.tsig().and_then(|tsig| tsig.data().mac().cloned()).unwrap_or_default(),
+            unsigned: Vec::new(),
+            unsigned_count: 0,
+            total_messages: 0,
+            state: State::Start,
+            soa: None,
+            records: Vec::new(),
+            txn: None,
+            txns: Vec::new()
+        })
+    }
+
+    pub fn set_key(&mut self, key: &Key) -> bool {
+        // The first response MAC chains off the query MAC, so an unsigned query can't be verified
+        if self.prior_mac.is_empty() {
+            return false;
+        }
+
+        self.key = Some(key.clone());
+        true
+    }
+
+    pub fn read(&mut self, buf: &[u8]) -> Result<bool, TransferReaderError> {
+        if self.is_complete() {
+            return Err(TransferReaderError::new(ErrorKind::Format,
+                &format!("message {} received after the transfer completed", self.total_messages + 1)));
+        }
+
+        let message = Message::from_bytes(buf)
+            .map_err(|e| TransferReaderError::new(ErrorKind::Format, &format!("message {}: {}", self.total_messages + 1, e)))?;
+        self.total_messages += 1;
+
+        if message.id() != self.id {
+            return Err(TransferReaderError::new(ErrorKind::IdMismatch,
+                &format!("message {} has id {} but query id was {}", self.total_messages, message.id(), self.id)));
+        }
+
+        if !message.is_qr() || message.is_truncated() {
+            return Err(TransferReaderError::new(ErrorKind::Format,
+                &format!("message {} is not a complete response", self.total_messages)));
+        }
+
+        self.verify_tsig(buf, &message)?;
+
+        if !message.response_code().eq(&ResponseCodes::NoError) {
+            return Err(TransferReaderError::new(ErrorKind::ResponseCode,
+                &format!("message {} returned {}", self.total_messages, message.response_code())));
+        }
+
+        for record in message.section(0) {
+            self.read_record(record)?;
+        }
+
+        if self.state.eq(&State::Opened) && self.rtype.eq(&RRTypes::Ixfr) && self.total_messages == 1 {
+            let serial = self.soa.as_ref().and_then(Self::soa_serial).unwrap_or(0);
+            if self.current_serial.is_some_and(|current| (serial.wrapping_sub(current) as i32) <= 0) {
+                self.state = State::Done;
+            }
+        }
+
+        if self.is_complete() && self.key.is_some() && self.unsigned_count > 0 {
+            return Err(TransferReaderError::new(ErrorKind::TSig,
+                &format!("final message {} is not signed", self.total_messages)));
+        }
+
+        Ok(self.is_complete())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.state.eq(&State::Done)
+    }
+
+    pub fn is_axfr(&self) -> bool {
+        self.rtype.eq(&RRTypes::Axfr) || !self.records.is_empty()
+    }
+
+    pub fn apex(&self) -> &str {
+        &self.apex
+    }
+
+    pub fn serial(&self) -> Option<u32> {
+        Self::soa_serial(self.soa.as_ref()?)
+    }
+
+    pub fn total_messages(&self) -> usize {
+        self.total_messages
+    }
+
+    pub fn txns(&self) -> &Vec<Txn> {
+        &self.txns
+    }
+
+    pub fn to_zone(&self, ztype: ZoneTypes) -> Result<Zone, TransferReaderError> {
+        if !self.is_complete() {
+            return Err(TransferReaderError::new(ErrorKind::Incomplete, "transfer has not completed"));
+        }
+
+        if !self.is_axfr() {
+            return Err(TransferReaderError::new(ErrorKind::Format, "transfer is incremental and holds no full zone"));
+        }
+
+        let mut zone = Zone::new(ztype, self.class);
+
+        for record in self.soa.iter().chain(self.records.iter()) {
+            let name = fqdn_to_relative(&self.apex, &record.fqdn().to_lowercase())
+                .ok_or_else(|| TransferReaderError::new(ErrorKind::Format, &format!("record {} is outside of zone {}", record.fqdn(), self.apex)))?;
+
+            if let Some(data) = record.data() {
+                zone.add_record(&name, record.rtype(), record.ttl(), data.clone());
+            }
+        }
+
+        Ok(zone)
+    }
+
+    pub fn apply(&self, zone: &mut Zone) -> Result<(), TransferReaderError> {
+        if self.is_axfr() {
+            let mut replacement = self.to_zone(zone.ztype())?;
+            if let Some(journal_path) = zone.journal_path() {
+                replacement.set_journal_path(journal_path.clone());
+            }
+
+            *zone = replacement;
+            return Ok(());
+        }
+
+        if !self.is_complete() {
+            return Err(TransferReaderError::new(ErrorKind::Incomplete, "transfer has not completed"));
+        }
+
+        let mut updated = zone.clone();
+        for txn in &self.txns {
+            updated.apply_txn(txn)
+                .map_err(|e| TransferReaderError::new(ErrorKind::SerialMismatch,
+                    &format!("unable to apply delta {} -> {}: {}", txn.serial_0(), txn.serial_1(), e)))?;
+        }
+
+        *zone = updated;
+        Ok(())
+    }
+
+    fn verify_tsig(&mut self, buf: &[u8], message: &Message) -> Result<(), TransferReaderError> {
+        let key = match self.key.as_ref() {
+            Some(key) => key,
+            None => return Ok(())
+        };
+
+        let first = self.total_messages == 1;
+
+        match message.tsig() {
+            Some(tsig) => {
+                let mut tsig = tsig.clone();
+
+                let mut signed = std::mem::take(&mut self.unsigned);
+                signed.extend_from_slice(tsig.unsigned_message());
+                tsig.set_chained_payload(&self.prior_mac, &signed, !first);
+
+                if !tsig.data().error().eq(&ResponseCodes::NoError) {
+                    return Err(TransferReaderError::new(ErrorKind::TSig,
+                        &format!("message {} carries TSIG error {}", self.total_messages, tsig.data().error())));
+                }
+
+                if let Err(code) = tsig.verify(key) {
+                    return Err(TransferReaderError::new(ErrorKind::TSig,
+                        &format!("message {} failed TSIG verification: {}", self.total_messages, code)));
+                }
+
+                let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+                if now.abs_diff(tsig.data().time_signed()) > tsig.data().fudge() as u64 {
+                    return Err(TransferReaderError::new(ErrorKind::TSig,
+                        &format!("message {} was signed outside of the allowed time window", self.total_messages)));
+                }
+
+                self.prior_mac = tsig.data().mac().cloned().unwrap_or_default();
+                self.unsigned_count = 0;
+            }
+            None => {
+                if first {
+                    return Err(TransferReaderError::new(ErrorKind::TSig, "first message is not signed"));
+                }
+
+                self.unsigned.extend_from_slice(buf);
+                self.unsigned_count += 1;
+
+                if self.unsigned_count > MAX_UNSIGNED_MESSAGES {
+                    return Err(TransferReaderError::new(ErrorKind::TSig,
+                        &format!("message {} exceeds {} consecutive unsigned messages", self.total_messages, MAX_UNSIGNED_MESSAGES)));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_record(&mut self, record: &Record) -> Result<(), TransferReaderError> {
+        let serial = match record.rtype() {
+            RRTypes::Soa => Some(Self::soa_serial(record)
+                .ok_or_else(|| TransferReaderError::new(ErrorKind::BadSoa, &format!("message {} has an empty SOA record", self.total_messages)))?),
+            _ => None
+        };
+        let final_serial = self.serial().unwrap_or(0);
+
+        match (self.state, serial) {
+            (State::Start, Some(_)) => {
+                if !record.fqdn().eq_ignore_ascii_case(&self.apex) || !record.class().eq(&self.class) {
+                    return Err(TransferReaderError::new(ErrorKind::BadSoa,
+                        &format!("opening SOA owner {} does not match zone {}", record.fqdn(), self.apex)));
+                }
+
+                self.soa = Some(record.clone());
+                self.state = State::Opened;
+            }
+            (State::Start, None) => {
+                return Err(TransferReaderError::new(ErrorKind::BadSoa,
+                    &format!("transfer starts with {} instead of SOA", record.rtype())));
+            }
+            (State::Opened, Some(serial)) if self.rtype.eq(&RRTypes::Ixfr) && serial != final_serial => {
+                if self.current_serial.is_some_and(|current| current != serial) {
+                    return Err(TransferReaderError::new(ErrorKind::SerialMismatch,
+                        &format!("incremental transfer starts at serial {} but zone is at serial {}", serial, self.current_serial.unwrap_or(0))));
+                }
+
+                self.open_txn(serial, record);
+            }
+            (State::Opened, Some(serial)) | (State::Axfr, Some(serial)) => {
+                if serial != final_serial {
+                    return Err(TransferReaderError::new(ErrorKind::BadSoa,
+                        &format!("closing SOA serial {} does not match opening serial {}", serial, final_serial)));
+                }
+
+                self.state = State::Done;
+            }
+            (State::Opened, None) | (State::Axfr, None) => {
+                self.records.push(record.clone());
+                self.state = State::Axfr;
+            }
+            (State::Deleting, Some(serial)) => {
+                if let Some(txn) = self.txn.as_mut() {
+                    txn.set_serial_1(serial);
+                    txn.add_record(TxnOpCodes::Add, record.fqdn(), record.class(), record.rtype(), record.ttl(), record.data().cloned());
+                }
+
+                self.state = State::Adding;
+            }
+            (State::Adding, Some(serial)) => {
+                let txn = self.txn.take()
+                    .ok_or_else(|| TransferReaderError::new(ErrorKind::Format, "incremental transfer lost its current delta"))?;
+                let end = txn.serial_1();
+                self.txns.push(txn);
+
+                if serial == final_serial && end == final_serial {
+                    self.state = State::Done;
+
+                } else if serial == end {
+                    self.open_txn(serial, record);
+
+                } else {
+                    return Err(TransferReaderError::new(ErrorKind::SerialMismatch,
+                        &format!("delta starts at serial {} but previous delta ended at serial {}", serial, end)));
+                }
+            }
+            (State::Deleting, None) | (State::Adding, None) => {
+                let op_code = match self.state {
+                    State::Deleting => TxnOpCodes::Delete,
+                    _ => TxnOpCodes::Add
+                };
+
+                if let Some(txn) = self.txn.as_mut() {
+                    txn.add_record(op_code, record.fqdn(), record.class(), record.rtype(), record.ttl(), record.data().cloned());
+                }
+            }
+            (State::Done, _) => {
+                return Err(TransferReaderError::new(ErrorKind::BadSoa,
+                    &format!("message {} has records after the closing SOA", self.total_messages)));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn open_txn(&mut self, serial: u32, record: &Record) {
+        let mut txn = Txn::new(serial, serial);
+        txn.add_record(TxnOpCodes::Delete, record.fqdn(), record.class(), record.rtype(), record.ttl(), record.data().cloned());
+        self.txn = Some(txn);
+        self.state = State::Deleting;
+    }
+
+    fn soa_serial(record: &Record) -> Option<u32> {
+        record.data()?.as_any().downcast_ref::<SoaRRData>().map(|soa| soa.serial())
+    }
+}
+
+#[test]
+fn test() {
+    use std::net::Ipv4Addr;
+    use crate::keyring::inter::algorithms::Algorithms;
+    use crate::messages::rr_query::RRQuery;
+    use crate::messages::tsig::{TSig, DEFAULT_TSIG_FUDGE};
+    use crate::rr_data::in_a_rr_data::InARRData;
+    use crate::rr_data::tsig_rr_data::TSigRRData;
+    use crate::rr_data::inter::rr_data::RRData;
+    use crate::zone::axfr_responder::AxfrResponder;
+
+    let soa = |serial: u32| SoaRRData::new("ns1.find9.net", "admin.find9.net", serial, 3600, 600, 604800, 300).upcast();
+    let a = |i: u8| InARRData::new(Ipv4Addr::new(10, 0, 0, i)).upcast();
+
+    let mut zone = Zone::new(ZoneTypes::Master, RRClasses::In);
+    zone.add_record("", RRTypes::Soa, 3600, soa(1));
+    for i in 0..40u8 {
+        zone.add_record(&format!("host{}", i), RRTypes::A, 300, a(i));
+    }
+
+    let mut query = Message::new(0x1234);
+    query.add_query(RRQuery::new("find9.net", RRTypes::Axfr, RRClasses::In));
+
+    let key = Key::new(b"axfr-secret".to_vec(), Algorithms::HmacSha256);
+    query.set_tsig(TSig::new("xfr-key", TSigRRData::new(key.algorithm(), 0, DEFAULT_TSIG_FUDGE, 0x1234, ResponseCodes::NoError, &[])));
+    query.to_bytes_with_sig(512, &key);
+
+    let mut responder = AxfrResponder::new(&query, "find9.net", &zone);
+    responder.set_max_payload_len(512);
+    assert!(responder.set_key("xfr-key", &key));
+    let messages: Vec<Vec<u8>> = responder.collect();
+    assert!(messages.len() > 2);
+
+    let mut reader = TransferReader::new(&query).unwrap();
+    assert!(reader.set_key(&key));
+    for (i, buf) in messages.iter().enumerate() {
+        assert_eq!(reader.read(buf).unwrap(), i == messages.len() - 1);
+    }
+    assert!(reader.read(&messages[0]).is_err());
+
+    let secondary = reader.to_zone(ZoneTypes::Slave).unwrap();
+    assert_eq!(secondary.soa().unwrap().serial(), 1);
+    assert_eq!(secondary.all_rr_sets_recursive().count(), 41);
+
+    let mut tampered = messages.clone();
+    let last = tampered[1].len() - 1;
+    tampered[1][last - 40] ^= 0xff;
+    let mut reader = TransferReader::new(&query).unwrap();
+    assert!(reader.set_key(&key));
+    reader.read(&tampered[0]).unwrap();
+    assert_eq!(reader.read(&tampered[1]).unwrap_err().kind(), &ErrorKind::TSig);
+
+    let mut unsigned = Message::new(0x1234);
+    unsigned.add_query(RRQuery::new("find9.net", RRTypes::Axfr, RRClasses::In));
+    assert!(!TransferReader::new(&unsigned).unwrap().set_key(&key));
+
+    let mut ixfr = Message::new(0x4321);
+    ixfr.add_query(RRQuery::new("find9.net", RRTypes::Ixfr, RRClasses::In));
+    ixfr.add_section(1, "find9.net", RRClasses::In, RRTypes::Soa, 3600, Some(soa(1)));
+
+    let mut response = Message::new(0x4321);
+    response.set_qr(true);
+    for (rtype, data) in [(RRTypes::Soa, soa(3)),
+            (RRTypes::Soa, soa(1)), (RRTypes::A, a(0)), (RRTypes::Soa, soa(2)), (RRTypes::A, a(100)),
+            (RRTypes::Soa, soa(2)), (RRTypes::Soa, soa(3)), (RRTypes::A, a(101)),
+            (RRTypes::Soa, soa(3))] {
+        let fqdn = match rtype {
+            RRTypes::Soa => "find9.net",
+            _ => "host0.find9.net"
+        };
+        response.add_section(0, fqdn, RRClasses::In, rtype, 300, Some(data));
+    }
+
+    let mut reader = TransferReader::new(&ixfr).unwrap();
+    assert!(reader.read(&response.to_bytes(65535)).unwrap());
+    assert!(!reader.is_axfr());
+    assert_eq!(reader.txns().len(), 2);
+
+    let mut updated = secondary.clone();
+    reader.apply(&mut updated).unwrap();
+    assert_eq!(updated.soa().unwrap().serial(), 3);
+    assert_eq!(updated.rr_set("host0", &RRTypes::A).unwrap().data().len(), 2);
+    assert!(reader.apply(&mut updated).is_err());
+    assert_eq!(updated.soa().unwrap().serial(), 3);
+
+    let mut up_to_date = Message::new(0x4321);
+    up_to_date.set_qr(true);
+    up_to_date.add_section(0, "find9.net", RRClasses::In, RRTypes::Soa, 300, Some(soa(1)));
+    let mut reader = TransferReader::new(&ixfr).unwrap();
+    assert!(reader.read(&up_to_date.to_bytes(65535)).unwrap());
+    assert!(reader.txns().is_empty());
+
+    let mut reader = TransferReader::new(&ixfr).unwrap();
+    let mut fallback = AxfrResponder::new(&query, "find9.net", &zone);
+    fallback.set_max_payload_len(512);
+    for buf in fallback {
+        let mut buf = buf;
+        buf[0..2].copy_from_slice(&0x4321u16.to_be_bytes());
+        reader.read(&buf).unwrap();
+    }
+    assert!(reader.is_complete());
+    assert!(reader.is_axfr());
+    assert_eq!(reader.to_zone(ZoneTypes::Slave).unwrap().all_rr_sets_recursive().count(), 41);
+}
@@ -0,0 +1,436 @@
+use std::fmt;
+use std::fmt::Formatter;
+use crate::journal::inter::txn_op_codes::TxnOpCodes;
+use crate::journal::txn::Txn;
+use crate::messages::inter::op_codes::OpCodes;
+use crate::messages::inter::response_codes::ResponseCodes;
+use crate::messages::inter::rr_classes::RRClasses;
+use crate::messages::inter::rr_types::RRTypes;
+use crate::messages::message::Message;
+use crate::messages::record::Record;
+use crate::messages::rr_query::RRQuery;
+use crate::rr_data::inter::rr_data::RRData;
+use crate::rr_data::soa_rr_data::SoaRRData;
+use crate::utils::fqdn_utils::{fqdn_to_relative, to_fqdn};
+use crate::utils::serial_utils::SerialUtils;
+use crate::zone::inter::zone_types::ZoneTypes;
+use crate::zone::rr_set::RRSet;
+use crate::zone::zone::Zone;
+
+#[derive(Debug, Clone)]
+pub struct UpdateError {
+    _type: ResponseCodes,
+    message: String
+}
+
+impl fmt::Display for UpdateError {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self._type, self.message)
+    }
+}
+
+impl UpdateError {
+
+    pub fn new(_type: ResponseCodes, message: &str) -> Self {
+        Self {
+            _type,
+            message: message.to_string()
+        }
+    }
+
+    pub fn response_code(&self) -> ResponseCodes {
+        self._type
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UpdateMessage {
+    zone: RRQuery,
+    prerequisites: Vec<Record>,
+    updates: Vec<Record>,
+    additional: Vec<Record>
+}
+
+impl UpdateMessage {
+
+    pub fn from_message(message: &Message) -> Result<Self, UpdateError> {
+        if !message.op_code().eq(&OpCodes::Update) {
+            return Err(UpdateError::new(ResponseCodes::FormErr, "message is not an UPDATE"));
+        }
+
+        let zone = match message.queries().as_slice() {
+            [zone] if zone.rtype().eq(&RRTypes::Soa) => zone.clone(),
+            _ => return Err(UpdateError::new(ResponseCodes::FormErr, "zone section must hold exactly one SOA entry"))
+        };
+
+        Ok(Self {
+            zone,
+            prerequisites: message.section(0).clone(),
+            updates: message.section(1).clone(),
+            additional: message.section(2).clone()
+        })
+    }
+
+    pub fn zone(&self) -> &RRQuery {
+        &self.zone
+    }
+
+    pub fn prerequisites(&self) -> &Vec<Record> {
+        &self.prerequisites
+    }
+
+    pub fn updates(&self) -> &Vec<Record> {
+        &self.updates
+    }
+
+    pub fn additional(&self) -> &Vec<Record> {
+        &self.additional
+    }
+
+    pub fn check_prerequisites(&self, apex: &str, zone: &Zone) -> Result<(), UpdateError> {
+        self.check_zone(apex, zone)?;
+
+        let mut expected: Vec<(String, RRSet)> = Vec::new();
+
+        for record in self.prerequisites.iter() {
+            if record.ttl() != 0 {
+                return Err(UpdateError::new(ResponseCodes::FormErr, &format!("prerequisite {} has a non zero TTL", record.fqdn())));
+            }
+
+            let name = Self::relative(apex, record)?;
+
+            match record.class() {
+                RRClasses::Any | RRClasses::None if record.data().is_some() => {
+                    return Err(UpdateError::new(ResponseCodes::FormErr, &format!("prerequisite {} must not carry data", record.fqdn())));
+                }
+                RRClasses::Any => {
+                    if record.rtype().eq(&RRTypes::Any) {
+                        if !Self::name_in_use(zone, &name) {
+                            return Err(UpdateError::new(ResponseCodes::NxDomain, &format!("name {} is not in use", record.fqdn())));
+                        }
+
+                    } else if zone.rr_set(&name, &record.rtype()).is_none() {
+                        return Err(UpdateError::new(ResponseCodes::NxRrSet, &format!("rrset {} {} does not exist", record.fqdn(), record.rtype())));
+                    }
+                }
+                RRClasses::None => {
+                    if record.rtype().eq(&RRTypes::Any) {
+                        if Self::name_in_use(zone, &name) {
+                            return Err(UpdateError::new(ResponseCodes::YxDomain, &format!("name {} is in use", record.fqdn())));
+                        }
+
+                    } else if zone.rr_set(&name, &record.rtype()).is_some() {
+                        return Err(UpdateError::new(ResponseCodes::YxRrSet, &format!("rrset {} {} exists", record.fqdn(), record.rtype())));
+                    }
+                }
+                class if class.eq(&zone.class()) => {
+                    let data = record.data()
+                        .ok_or_else(|| UpdateError::new(ResponseCodes::FormErr, &format!("prerequisite {} is missing data", record.fqdn())))?;
+
+                    match expected.iter_mut().find(|(n, set)| n.eq(&name) && set.rtype().eq(&record.rtype())) {
+                        Some((_, set)) => {
+                            if !set.data().iter().any(|d| d.eq(data)) {
+                                set.add_data(0, data.clone());
+                            }
+                        }
+                        None => {
+                            let mut set = RRSet::new(record.rtype(), 0);
+                            set.add_data(0, data.clone());
+                            expected.push((name, set));
+                        }
+                    }
+                }
+                _ => return Err(UpdateError::new(ResponseCodes::FormErr, &format!("prerequisite {} has an invalid class", record.fqdn())))
+            }
+        }
+
+        for (name, set) in expected.iter() {
+            let matches = match zone.rr_set(name, &set.rtype()) {
+                Some(existing) => existing.data().len() == set.data().len() &&
+                    existing.data().iter().all(|d| set.data().iter().any(|e| e.eq(d))),
+                None => false
+            };
+
+            if !matches {
+                return Err(UpdateError::new(ResponseCodes::NxRrSet, &format!("rrset {} {} does not match", to_fqdn(apex, name), set.rtype())));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn apply(&self, apex: &str, zone: &mut Zone) -> Result<Option<Txn>, UpdateError> {
+        self.check_prerequisites(apex, zone)?;
+        self.prescan(apex, zone)?;
+
+        let old_soa = zone.rr_set("", &RRTypes::Soa)
+            .and_then(|set| Some((set.ttl(), set.data().first()?.as_any().downcast_ref::<SoaRRData>()?.clone())))
+            .ok_or_else(|| UpdateError::new(ResponseCodes::ServFail, &format!("zone {} has no SOA", apex)))?;
+
+        let mut updated = zone.clone();
+        let mut touched: Vec<String> = Vec::new();
+
+        for record in self.updates.iter() {
+            let name = Self::relative(apex, record)?;
+            let rtype = record.rtype();
+
+            match record.class() {
+                RRClasses::Any => {
+                    if rtype.eq(&RRTypes::Any) {
+                        match name.is_empty() {
+                            true => updated.remove_all_records(&name, &[RRTypes::Soa, RRTypes::Ns]),
+                            false => updated.remove_all_records(&name, &[])
+                        }
+
+                    } else if !(name.is_empty() && (rtype.eq(&RRTypes::Soa) || rtype.eq(&RRTypes::Ns))) {
+                        updated.remove_rr_set(&name, &rtype);
+                    }
+                }
+                RRClasses::None => {
+                    if let Some(data) = record.data() {
+                        match rtype {
+                            RRTypes::Soa => {}
+                            RRTypes::Ns if name.is_empty() => {
+                                updated.remove_record(&name, &rtype, data, 1);
+                            }
+                            _ => {
+                                updated.remove_record(&name, &rtype, data, 0);
+                            }
+                        }
+                    }
+                }
+                _ => Self::add(&mut updated, &name, record)
+            }
+
+            if !touched.contains(&name) {
+                touched.push(name);
+            }
+        }
+
+        let mut deleted = Vec::new();
+        let mut added = Vec::new();
+
+        for name in touched.iter() {
+            let fqdn = to_fqdn(apex, name);
+            Self::diff(zone, &updated, name, &fqdn, &mut deleted);
+            Self::diff(&updated, zone, name, &fqdn, &mut added);
+        }
+
+        let (soa_ttl, mut new_soa) = updated.rr_set("", &RRTypes::Soa)
+            .and_then(|set| Some((set.ttl(), set.data().first()?.as_any().downcast_ref::<SoaRRData>()?.clone())))
+            .unwrap_or(old_soa.clone());
+
+        if deleted.is_empty() && added.is_empty() && new_soa.serial() == old_soa.1.serial() {
+            return Ok(None);
+        }
+
+        if !new_soa.serial().serial_gt(old_soa.1.serial()) {
+            new_soa.set_serial(old_soa.1.serial().serial_add(1)
+                .ok_or_else(|| UpdateError::new(ResponseCodes::ServFail, "unable to increment zone serial"))?);
+            updated.remove_rr_set("", &RRTypes::Soa);
+            updated.add_record("", RRTypes::Soa, soa_ttl, new_soa.clone().upcast());
+        }
+
+        let class = zone.class();
+        let mut txn = Txn::new(old_soa.1.serial(), new_soa.serial());
+
+        txn.add_record(TxnOpCodes::Delete, apex, class, RRTypes::Soa, old_soa.0, Some(old_soa.1.upcast()));
+        for record in deleted {
+            txn.add_record(TxnOpCodes::Delete, record.fqdn(), class, record.rtype(), record.ttl(), record.data().cloned());
+        }
+
+        txn.add_record(TxnOpCodes::Add, apex, class, RRTypes::Soa, soa_ttl, Some(new_soa.upcast()));
+        for record in added {
+            txn.add_record(TxnOpCodes::Add, record.fqdn(), class, record.rtype(), record.ttl(), record.data().cloned());
+        }
+
+        *zone = updated;
+
+        Ok(Some(txn))
+    }
+
+    fn check_zone(&self, apex: &str, zone: &Zone) -> Result<(), UpdateError> {
+        if !zone.ztype().eq(&ZoneTypes::Master) || !self.zone.class().eq(&zone.class()) || !self.zone.fqdn().eq_ignore_ascii_case(apex) {
+            return Err(UpdateError::new(ResponseCodes::NotAuth, &format!("not authoritative for zone {}", self.zone.fqdn())));
+        }
+
+        Ok(())
+    }
+
+    fn prescan(&self, apex: &str, zone: &Zone) -> Result<(), UpdateError> {
+        for record in self.updates.iter() {
+            Self::relative(apex, record)?;
+
+            let rtype = record.rtype();
+            let meta = matches!(rtype, RRTypes::Any | RRTypes::Axfr | RRTypes::Ixfr | RRTypes::Opt | RRTypes::TSig | RRTypes::TKey);
+
+            let valid = match record.class() {
+                RRClasses::Any => record.ttl() == 0 && record.data().is_none() && (!meta || rtype.eq(&RRTypes::Any)),
+                RRClasses::None => record.ttl() == 0 && record.data().is_some() && !meta,
+                class if class.eq(&zone.class()) => record.data().is_some() && !meta,
+                _ => false
+            };
+
+            if !valid {
+                return Err(UpdateError::new(ResponseCodes::FormErr, &format!("invalid update {} {} {}", record.fqdn(), record.class(), rtype)));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add(zone: &mut Zone, name: &str, record: &Record) {
+        let data = match record.data() {
+            Some(data) => data,
+            None => return
+        };
+
+        let rtype = record.rtype();
+        let sets = zone.all_rr_sets(name);
+        let has_cname = sets.is_some_and(|sets| sets.iter().any(|s| s.rtype().eq(&RRTypes::CName)));
+        let has_other = sets.is_some_and(|sets| sets.iter().any(|s| !matches!(s.rtype(), RRTypes::CName | RRTypes::RRSig | RRTypes::NSec | RRTypes::NSec3)));
+
+        match rtype {
+            RRTypes::Soa => {
+                let newer = match (data.as_any().downcast_ref::<SoaRRData>(), zone.soa()) {
+                    (Some(soa), Some(current)) => soa.serial().serial_gt(current.serial()),
+                    _ => false
+                };
+
+                if !name.is_empty() || !newer {
+                    return;
+                }
+
+                zone.remove_rr_set(name, &rtype);
+            }
+            RRTypes::CName => {
+                if has_other {
+                    return;
+                }
+
+                zone.remove_rr_set(name, &rtype);
+            }
+            _ => {
+                if has_cname {
+                    return;
+                }
+
+                if zone.rr_set(name, &rtype).is_some_and(|set| set.data().iter().any(|d| d.eq(data))) {
+                    return;
+                }
+            }
+        }
+
+        zone.add_record(name, rtype, record.ttl(), data.clone());
+    }
+
+    fn diff(zone: &Zone, other: &Zone, name: &str, fqdn: &str, out: &mut Vec<Record>) {
+        let sets = match zone.all_rr_sets(name) {
+            Some(sets) => sets,
+            None => return
+        };
+
+        for set in sets.iter() {
+            if name.is_empty() && set.rtype().eq(&RRTypes::Soa) {
+                continue;
+            }
+
+            let other = other.rr_set(name, &set.rtype()).filter(|o| o.ttl() == set.ttl());
+
+            for data in set.data() {
+                if !other.is_some_and(|o| o.data().iter().any(|d| d.eq(data))) {
+                    out.push(Record::new(fqdn, zone.class(), set.rtype(), set.ttl(), Some(data.clone())));
+                }
+            }
+        }
+    }
+
+    fn name_in_use(zone: &Zone, name: &str) -> bool {
+        zone.all_rr_sets(name).is_some_and(|sets| !sets.is_empty())
+    }
+
+    fn relative(apex: &str, record: &Record) -> Result<String, UpdateError> {
+        fqdn_to_relative(&apex.to_lowercase(), &record.fqdn().to_lowercase())
+            .ok_or_else(|| UpdateError::new(ResponseCodes::NotZone, &format!("{} is outside of zone {}", record.fqdn(), apex)))
+    }
+}
+
+#[test]
+fn test() {
+    use std::net::Ipv4Addr;
+    use crate::rr_data::in_a_rr_data::InARRData;
+    use crate::rr_data::ns_rr_data::NsRRData;
+
+    let a = |i| InARRData::new(Ipv4Addr::new(10, 0, 0, i)).upcast();
+
+    let mut zone = Zone::new(ZoneTypes::Master, RRClasses::In);
+    zone.add_record("", RRTypes::Soa, 3600, SoaRRData::new("ns1.find9.net", "admin.find9.net", 10, 3600, 600, 604800, 300).upcast());
+    zone.add_record("", RRTypes::Ns, 3600, NsRRData::new("ns1.find9.net").upcast());
+    zone.add_record("www", RRTypes::A, 300, a(1));
+    zone.add_record("www", RRTypes::A, 300, a(2));
+    zone.add_record("mail", RRTypes::A, 300, a(3));
+
+    type Prerequisite<'a> = (&'a str, RRClasses, RRTypes, Option<Box<dyn RRData>>);
+    type Update<'a> = (&'a str, RRClasses, RRTypes, u32, Option<Box<dyn RRData>>);
+
+    let update = |prerequisites: Vec<Prerequisite>, updates: Vec<Update>| {
+        let mut message = Message::new(1);
+        message.set_op_code(OpCodes::Update);
+        message.add_query(RRQuery::new("find9.net", RRTypes::Soa, RRClasses::In));
+        for (name, class, rtype, data) in prerequisites {
+            message.add_section(0, name, class, rtype, 0, data);
+        }
+        for (name, class, rtype, ttl, data) in updates {
+            message.add_section(1, name, class, rtype, ttl, data);
+        }
+        UpdateMessage::from_message(&Message::from_bytes(message.to_bytes(4096)).unwrap()).unwrap()
+    };
+
+    let code = |update: UpdateMessage, zone: &mut Zone| update.apply("find9.net", zone).unwrap_err().response_code();
+
+    assert_eq!(code(update(vec![("www.find9.net", RRClasses::None, RRTypes::Any, None)], vec![]), &mut zone), ResponseCodes::YxDomain);
+    assert_eq!(code(update(vec![("ftp.find9.net", RRClasses::Any, RRTypes::Any, None)], vec![]), &mut zone), ResponseCodes::NxDomain);
+    assert_eq!(code(update(vec![("www.find9.net", RRClasses::Any, RRTypes::Aaaa, None)], vec![]), &mut zone), ResponseCodes::NxRrSet);
+    assert_eq!(code(update(vec![("www.find9.net", RRClasses::None, RRTypes::A, None)], vec![]), &mut zone), ResponseCodes::YxRrSet);
+    assert_eq!(code(update(vec![("www.find9.net", RRClasses::In, RRTypes::A, Some(a(1)))], vec![]), &mut zone), ResponseCodes::NxRrSet);
+    assert_eq!(code(update(vec![], vec![("www.other.net", RRClasses::In, RRTypes::A, 300, Some(a(9)))]), &mut zone), ResponseCodes::NotZone);
+    assert_eq!(code(update(vec![], vec![("www.find9.net", RRClasses::Any, RRTypes::A, 300, None)]), &mut zone), ResponseCodes::FormErr);
+
+    assert_eq!(code(update(vec![("mail.find9.net", RRClasses::Any, RRTypes::Aaaa, None)], vec![
+        ("www.find9.net", RRClasses::Any, RRTypes::A, 0, None)
+    ]), &mut zone), ResponseCodes::NxRrSet);
+    assert_eq!(zone.rr_set("www", &RRTypes::A).unwrap().data().len(), 2);
+
+    let original = zone.clone();
+    let txn = update(vec![
+        ("www.find9.net", RRClasses::In, RRTypes::A, Some(a(2))),
+        ("www.find9.net", RRClasses::In, RRTypes::A, Some(a(1))),
+        ("ftp.find9.net", RRClasses::None, RRTypes::Any, None)
+    ], vec![
+        ("www.find9.net", RRClasses::None, RRTypes::A, 0, Some(a(1))),
+        ("ftp.find9.net", RRClasses::In, RRTypes::A, 300, Some(a(4))),
+        ("ftp.find9.net", RRClasses::In, RRTypes::A, 300, Some(a(4))),
+        ("mail.find9.net", RRClasses::Any, RRTypes::Any, 0, None),
+        ("find9.net", RRClasses::None, RRTypes::Ns, 0, Some(NsRRData::new("ns1.find9.net").upcast())),
+        ("find9.net", RRClasses::Any, RRTypes::Any, 0, None)
+    ]).apply("find9.net", &mut zone).unwrap().unwrap();
+
+    assert_eq!(zone.soa().unwrap().serial(), 11);
+    assert_eq!(zone.rr_set("www", &RRTypes::A).unwrap().data().len(), 1);
+    assert_eq!(zone.rr_set("ftp", &RRTypes::A).unwrap().data().len(), 1);
+    assert!(zone.all_rr_sets("mail").is_none());
+    assert!(zone.rr_set("", &RRTypes::Ns).is_some());
+
+    assert_eq!((txn.serial_0(), txn.serial_1()), (10, 11));
+    assert_eq!(txn.records(TxnOpCodes::Delete).len(), 3);
+    assert_eq!(txn.records(TxnOpCodes::Add).len(), 2);
+
+    let mut replayed = original.clone();
+    replayed.apply_txn(&txn).unwrap();
+    assert_eq!(replayed.rr_set("www", &RRTypes::A).unwrap().data().len(), 1);
+    assert_eq!(replayed.rr_set("ftp", &RRTypes::A).unwrap().data().len(), 1);
+    assert!(replayed.all_rr_sets("mail").is_none());
+
+    assert!(update(vec![], vec![("ftp.find9.net", RRClasses::In, RRTypes::A, 300, Some(a(4)))]).apply("find9.net", &mut zone).unwrap().is_none());
+}
@@ -1,13 +1,63 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fmt::Formatter;
 use std::path::PathBuf;
-use crate::journal::journal_reader::{JournalReader, JournalReaderError};
+use crate::dnssec::nsec3::{nsec3_hash, nsec3_label, NSEC3_HASH_SHA1};
+use crate::journal::inter::txn_op_codes::TxnOpCodes;
+use crate::journal::journal_reader::{ErrorKind as JournalErrorKind, JournalReader, JournalReaderError};
+use crate::journal::txn::Txn;
 use crate::messages::inter::rr_classes::RRClasses;
 use crate::messages::inter::rr_types::RRTypes;
 use crate::zone::rr_set::RRSet;
 use crate::rr_data::inter::rr_data::RRData;
-use crate::utils::fqdn_utils::{decode_fqdn, encode_fqdn};
+use crate::rr_data::nsec_rr_data::NSecRRData;
+use crate::rr_data::nsec3_rr_data::{NSec3RRData, NSEC3_OPT_OUT};
+use crate::rr_data::nsec3param_rr_data::NSec3ParamRRData;
+use crate::rr_data::rrsig_rr_data::RRSigRRData;
+use crate::rr_data::soa_rr_data::SoaRRData;
+use crate::rr_data::zonemd_rr_data::{ZoneMdRRData, ZONEMD_HASH_SHA384, ZONEMD_HASH_SHA512, ZONEMD_SCHEME_SIMPLE};
+use crate::utils::base32;
+use crate::utils::fqdn_utils::{decode_fqdn, encode_fqdn, fqdn_to_relative, labels, to_fqdn};
+use crate::utils::hash::inter::hash::Hash;
+use crate::utils::hash::sha384::Sha384;
+use crate::utils::hash::sha512::Sha512;
 use crate::utils::trie::trie::Trie;
 use crate::zone::inter::zone_types::ZoneTypes;
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ZoneError {
+    _type: ErrorKind,
+    message: String
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ErrorKind {
+    SerialMismatch,
+    OutOfZone,
+    Journal
+}
+
+impl ZoneError {
+
+    pub fn new(_type: ErrorKind, message: &str) -> Self {
+        Self {
+            _type,
+            message: message.to_string()
+        }
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self._type
+    }
+}
+
+impl fmt::Display for ZoneError {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self._type, self.message)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Zone {
     ztype: ZoneTypes,
@@ -181,6 +231,50 @@ impl Zone {
         self.sets.get(&encode_fqdn(query))
     }
 
+    pub fn find_rr_set(&self, query: &str, rtype: &RRTypes) -> Option<(&RRSet, bool)> {
+        let (sets, synthesized) = self.find_rr_sets(query)?;
+        Some((sets.iter().find(|s| s.rtype().eq(rtype))?, synthesized))
+    }
+
+    pub fn find_rr_sets(&self, query: &str) -> Option<(&Vec<RRSet>, bool)> {
+        if let Some(sets) = self.all_rr_sets(query) {
+            return Some((sets, false));
+        }
+
+        self.wildcard_rr_sets(query).map(|(_, sets)| (sets, true))
+    }
+
+    pub fn wildcard_rr_sets(&self, query: &str) -> Option<(String, &Vec<RRSet>)> {
+        if self.name_exists(query) {
+            return None;
+        }
+
+        let wildcard = match self.closest_encloser(query).as_str() {
+            "" => "*".to_string(),
+            encloser => format!("*.{}", encloser)
+        };
+        let sets = self.all_rr_sets(&wildcard)?;
+        Some((wildcard, sets))
+    }
+
+    pub fn closest_encloser(&self, query: &str) -> String {
+        if query.is_empty() {
+            return String::new();
+        }
+
+        let parts: Vec<&str> = labels(query).collect();
+
+        for i in 0..parts.len() {
+            let name = parts[i..].join(".");
+
+            if self.name_exists(&name) {
+                return name;
+            }
+        }
+
+        String::new()
+    }
+
     pub fn all_rr_sets_recursive(&self) -> impl Iterator<Item = (String, &Vec<RRSet>)> {
         self.sets.iter().map(|(key, records)| (decode_fqdn(key), records))
     }
@@ -204,19 +298,455 @@ impl Zone {
     */
 
     pub fn delegation_point(&self, query: &str) -> Option<(String, &RRSet)> {
-        match self.sets.get_shallowest(&encode_fqdn(query)) {
-            Some((name, sets)) => {
-                sets
-                    .iter()
-                    .find(|s| s.rtype().eq(&RRTypes::Ns))
-                    .map(|set| (decode_fqdn(name), set))
+        if query.is_empty() {
+            return None;
+        }
+
+        let parts: Vec<&str> = labels(query).collect();
+
+        for i in (0..parts.len()).rev() {
+            let name = parts[i..].join(".");
+
+            if let Some(set) = self.rr_set(&name, &RRTypes::Ns) {
+                return Some((name, set));
             }
-            None => None
         }
+
+        None
+    }
+
+    pub fn dname_point(&self, query: &str) -> Option<(String, &RRSet)> {
+        if query.is_empty() {
+            return None;
+        }
+
+        let parts: Vec<&str> = labels(query).collect();
+
+        for i in (1..parts.len()).rev() {
+            let name = parts[i..].join(".");
+
+            if let Some(set) = self.rr_set(&name, &RRTypes::DName) {
+                return Some((name, set));
+            }
+        }
+
+        self.rr_set("", &RRTypes::DName).map(|set| (String::new(), set))
+    }
+
+    pub fn name_exists(&self, query: &str) -> bool {
+        if query.is_empty() {
+            return true;
+        }
+
+        self.sets.contains_prefix(&encode_fqdn(query))
+    }
+
+    pub fn generate_nsec_chain(&mut self, apex: &str) -> bool {
+        let ttl = match (self.rr_set("", &RRTypes::Soa), self.soa()) {
+            (Some(set), Some(soa)) => set.ttl().min(soa.minimum_ttl()),
+            _ => return false
+        };
+
+        let existing: Vec<String> = self.all_rr_sets_recursive()
+            .filter(|(_, sets)| sets.iter().any(|s| s.rtype().eq(&RRTypes::NSec)))
+            .map(|(name, _)| name)
+            .collect();
+
+        for name in existing {
+            self.remove_rr_set(&name, &RRTypes::NSec);
+        }
+
+        let names: Vec<(String, Vec<RRTypes>)> = self.all_rr_sets_recursive()
+            .filter(|(name, _)| self.delegation_point(name).map_or(true, |(cut, _)| cut.eq(name)))
+            .map(|(name, sets)| {
+                let mut types: Vec<RRTypes> = sets.iter()
+                    .map(|s| s.rtype())
+                    .filter(|t| !t.eq(&RRTypes::RRSig))
+                    .chain([RRTypes::NSec, RRTypes::RRSig])
+                    .collect();
+                types.sort_by_key(|t| t.code());
+                types.dedup();
+                (name, types)
+            })
+            .collect();
+
+        for (i, (name, types)) in names.iter().enumerate() {
+            let (next, _) = &names[(i + 1) % names.len()];
+            self.add_record(name, RRTypes::NSec, ttl, NSecRRData::new(&to_fqdn(apex, next), types.clone()).upcast());
+        }
+
+        true
+    }
+
+    pub fn covering_nsec(&self, query: &str) -> Option<(String, &RRSet)> {
+        let (key, sets) = self.sets.get_floor_by(&encode_fqdn(query),
+            |sets| sets.iter().any(|s| s.rtype().eq(&RRTypes::NSec)))?;
+        Some((decode_fqdn(key), sets.iter().find(|s| s.rtype().eq(&RRTypes::NSec))?))
+    }
+
+    pub fn nsec_denial(&self, query: &str, rtype: &RRTypes) -> Vec<(String, &RRSet)> {
+        let mut proofs = Vec::new();
+
+        if let Some(sets) = self.all_rr_sets(query) {
+            if !sets.iter().any(|s| s.rtype().eq(rtype) || s.rtype().eq(&RRTypes::CName)) {
+                proofs.extend(self.rr_set(query, &RRTypes::NSec).map(|set| (query.to_string(), set)));
+            }
+            return proofs;
+        }
+
+        proofs.extend(self.covering_nsec(query));
+
+        if self.name_exists(query) {
+            return proofs;
+        }
+
+        let wildcard = match self.closest_encloser(query).as_str() {
+            "" => "*".to_string(),
+            encloser => format!("*.{}", encloser)
+        };
+
+        match self.all_rr_sets(&wildcard) {
+            Some(sets) => {
+                if !sets.iter().any(|s| s.rtype().eq(rtype) || s.rtype().eq(&RRTypes::CName)) {
+                    proofs.extend(self.rr_set(&wildcard, &RRTypes::NSec).map(|set| (wildcard, set)));
+                }
+            }
+            None => proofs.extend(self.covering_nsec(&wildcard))
+        }
+
+        proofs.dedup_by(|a, b| a.0.eq(&b.0));
+        proofs
+    }
+
+    pub fn generate_nsec3_chain(&mut self, apex: &str, salt: &[u8], iterations: u16, opt_out: bool) -> bool {
+        let ttl = match (self.rr_set("", &RRTypes::Soa), self.soa()) {
+            (Some(set), Some(soa)) => set.ttl().min(soa.minimum_ttl()),
+            _ => return false
+        };
+
+        let existing: Vec<String> = self.all_rr_sets_recursive()
+            .filter(|(_, sets)| sets.iter().any(|s| s.rtype().eq(&RRTypes::NSec3)))
+            .map(|(name, _)| name)
+            .collect();
+
+        for name in existing {
+            self.remove_all_records(&name, &[]);
+        }
+
+        self.remove_rr_set("", &RRTypes::NSec3Param);
+        self.add_record("", RRTypes::NSec3Param, ttl, NSec3ParamRRData::new(NSEC3_HASH_SHA1, 0, iterations, salt).upcast());
+
+        let mut names: HashMap<String, Vec<RRTypes>> = HashMap::new();
+
+        for (name, sets) in self.all_rr_sets_recursive() {
+            let at_cut = match self.delegation_point(&name) {
+                Some((cut, _)) if cut.eq(&name) => true,
+                Some(_) => continue,
+                None => false
+            };
+
+            let signed = !at_cut || sets.iter().any(|s| s.rtype().eq(&RRTypes::Ds));
+            if opt_out && !signed {
+                continue;
+            }
+
+            let mut types: Vec<RRTypes> = sets.iter()
+                .map(|s| s.rtype())
+                .filter(|t| !t.eq(&RRTypes::RRSig))
+                .collect();
+            if signed {
+                types.push(RRTypes::RRSig);
+            }
+            types.sort_by_key(|t| t.code());
+            types.dedup();
+
+            let parts: Vec<&str> = labels(&name).collect();
+            for i in 1..parts.len() {
+                names.entry(parts[i..].join(".")).or_default();
+            }
+
+            if !name.is_empty() {
+                names.entry(String::new()).or_default();
+            }
+
+            names.insert(name, types);
+        }
+
+        let mut hashes: Vec<(Vec<u8>, Vec<RRTypes>)> = names.into_iter()
+            .map(|(name, types)| (nsec3_hash(&to_fqdn(apex, &name), salt, iterations), types))
+            .collect();
+        hashes.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let flags = match opt_out {
+            true => NSEC3_OPT_OUT,
+            false => 0
+        };
+
+        for (i, (hash, types)) in hashes.iter().enumerate() {
+            let (next, _) = &hashes[(i + 1) % hashes.len()];
+            self.add_record(&base32::hex_encode_nopad(hash).to_lowercase(), RRTypes::NSec3, ttl,
+                NSec3RRData::new(NSEC3_HASH_SHA1, flags, iterations, salt, next, types.clone()).upcast());
+        }
+
+        true
+    }
+
+    pub fn nsec3_param(&self) -> Option<&NSec3ParamRRData> {
+        self.rr_set("", &RRTypes::NSec3Param)?.data().first()?.as_any().downcast_ref::<NSec3ParamRRData>()
+    }
+
+    pub fn matching_nsec3(&self, apex: &str, query: &str) -> Option<(String, &RRSet)> {
+        let param = self.nsec3_param()?;
+        let label = nsec3_label(&to_fqdn(apex, query), param.salt(), param.iterations());
+        let set = self.rr_set(&label, &RRTypes::NSec3)?;
+        Some((label, set))
+    }
+
+    pub fn covering_nsec3(&self, apex: &str, query: &str) -> Option<(String, &RRSet)> {
+        let param = self.nsec3_param()?;
+        let label = nsec3_label(&to_fqdn(apex, query), param.salt(), param.iterations());
+
+        let is_nsec3 = |sets: &Vec<RRSet>| sets.iter().any(|s| s.rtype().eq(&RRTypes::NSec3));
+        let (key, sets) = self.sets.get_floor_by(&encode_fqdn(&label), is_nsec3)
+            .or_else(|| self.sets.get_floor_by(&[0xff], is_nsec3))?;
+        Some((decode_fqdn(key), sets.iter().find(|s| s.rtype().eq(&RRTypes::NSec3))?))
+    }
+
+    pub fn closest_encloser_proof(&self, apex: &str, query: &str) -> Vec<(String, &RRSet)> {
+        let mut proofs = Vec::new();
+
+        if query.is_empty() {
+            proofs.extend(self.matching_nsec3(apex, query));
+            return proofs;
+        }
+
+        let parts: Vec<&str> = labels(query).collect();
+
+        for i in 1..=parts.len() {
+            if let Some(matching) = self.matching_nsec3(apex, &parts[i..].join(".")) {
+                proofs.push(matching);
+                proofs.extend(self.covering_nsec3(apex, &parts[i - 1..].join(".")));
+                break;
+            }
+        }
+
+        proofs
+    }
+
+    pub fn nsec3_denial(&self, apex: &str, query: &str, rtype: &RRTypes) -> Vec<(String, &RRSet)> {
+        let mut proofs = Vec::new();
+
+        if self.name_exists(query) {
+            if self.all_rr_sets(query).map_or(false, |sets| sets.iter().any(|s| s.rtype().eq(rtype) || s.rtype().eq(&RRTypes::CName))) {
+                return proofs;
+            }
+
+            match self.matching_nsec3(apex, query) {
+                Some(matching) => proofs.push(matching),
+                None => proofs.extend(self.closest_encloser_proof(apex, query))
+            }
+            return proofs;
+        }
+
+        let encloser = self.closest_encloser(query);
+        let wildcard = match encloser.as_str() {
+            "" => "*".to_string(),
+            encloser => format!("*.{}", encloser)
+        };
+
+        match self.all_rr_sets(&wildcard) {
+            Some(sets) if sets.iter().any(|s| s.rtype().eq(rtype) || s.rtype().eq(&RRTypes::CName)) => {
+                let parts: Vec<&str> = labels(query).collect();
+                let depth = match encloser.is_empty() {
+                    true => 0,
+                    false => labels(&encloser).count()
+                };
+                proofs.extend(self.covering_nsec3(apex, &parts[parts.len() - depth - 1..].join(".")));
+            }
+            Some(_) => {
+                proofs.extend(self.closest_encloser_proof(apex, query));
+                proofs.extend(self.matching_nsec3(apex, &wildcard));
+            }
+            None => {
+                proofs.extend(self.closest_encloser_proof(apex, query));
+                proofs.extend(self.covering_nsec3(apex, &wildcard));
+            }
+        }
+
+        let mut seen = HashSet::new();
+        proofs.retain(|(name, _)| seen.insert(name.clone()));
+        proofs
+    }
+
+    pub fn zone_digest(&self, apex: &str, hash_algorithm: u8) -> Option<Vec<u8>> {
+        match hash_algorithm {
+            ZONEMD_HASH_SHA384 => self.simple_digest::<Sha384>(apex),
+            ZONEMD_HASH_SHA512 => self.simple_digest::<Sha512>(apex),
+            _ => None
+        }
+    }
+
+    pub fn generate_zonemd(&mut self, apex: &str, hash_algorithm: u8) -> bool {
+        let (ttl, serial) = match (self.rr_set("", &RRTypes::Soa), self.soa()) {
+            (Some(set), Some(soa)) => (set.ttl(), soa.serial()),
+            _ => return false
+        };
+
+        let digest = match self.zone_digest(apex, hash_algorithm) {
+            Some(digest) => digest,
+            None => return false
+        };
+
+        let stale: Vec<Box<dyn RRData>> = self.rr_set("", &RRTypes::RRSig)
+            .map(|set| set.data().iter().filter(|d| Self::covers_zonemd(d.as_ref())).cloned().collect())
+            .unwrap_or_default();
+
+        for rrsig in stale {
+            self.remove_record("", &RRTypes::RRSig, &rrsig, 0);
+        }
+
+        self.remove_rr_set("", &RRTypes::ZoneMd);
+        self.add_record("", RRTypes::ZoneMd, ttl, ZoneMdRRData::new(serial, ZONEMD_SCHEME_SIMPLE, hash_algorithm, &digest).upcast());
+        true
+    }
+
+    pub fn verify_zonemd(&self, apex: &str) -> bool {
+        let serial = match self.soa() {
+            Some(soa) => soa.serial(),
+            None => return false
+        };
+
+        self.rr_set("", &RRTypes::ZoneMd).is_some_and(|set| set.data().iter()
+            .filter_map(|d| d.as_any().downcast_ref::<ZoneMdRRData>())
+            .filter(|zonemd| zonemd.serial() == serial && zonemd.scheme() == ZONEMD_SCHEME_SIMPLE)
+            .any(|zonemd| self.zone_digest(apex, zonemd.hash_algorithm()).is_some_and(|digest| digest.eq(zonemd.digest()))))
+    }
+
+    fn simple_digest<H: Hash>(&self, apex: &str) -> Option<Vec<u8>> {
+        let mut hasher = H::new();
+
+        for (name, sets) in self.all_rr_sets_recursive() {
+            let fqdn = to_fqdn(apex, &name);
+
+            let mut sets: Vec<&RRSet> = sets.iter().collect();
+            sets.sort_by_key(|s| s.rtype().code());
+
+            for set in sets {
+                let buf = match (name.is_empty(), set.rtype()) {
+                    (true, RRTypes::ZoneMd) => continue,
+                    (true, RRTypes::RRSig) => {
+                        let mut rrsigs = RRSet::new(RRTypes::RRSig, set.ttl());
+                        set.data().iter()
+                            .filter(|d| !Self::covers_zonemd(d.as_ref()))
+                            .for_each(|d| rrsigs.add_data(set.ttl(), d.clone()));
+                        rrsigs.to_canonical_bytes(&fqdn, self.class, set.ttl()).ok()?
+                    }
+                    _ => set.to_canonical_bytes(&fqdn, self.class, set.ttl()).ok()?
+                };
+
+                hasher.update(&buf, 0, buf.len());
+            }
+        }
+
+        Some(hasher.get_value().as_ref().to_vec())
+    }
+
+    fn covers_zonemd(data: &dyn RRData) -> bool {
+        data.as_any().downcast_ref::<RRSigRRData>().is_some_and(|rrsig| rrsig.type_covered().eq(&Some(&RRTypes::ZoneMd)))
+    }
+
+    pub fn soa(&self) -> Option<&SoaRRData> {
+        self.rr_set("", &RRTypes::Soa)?.data().first()?.as_any().downcast_ref::<SoaRRData>()
+    }
+
+    // Checks the whole transaction before touching the zone, so a rejected transaction leaves it unchanged.
+    // The apex is taken from the transaction's SOA records, which every journal and IXFR delta carries.
+    pub fn apply_txn(&mut self, txn: &Txn) -> Result<(), ZoneError> {
+        let soa = txn.records(TxnOpCodes::Add).iter()
+            .find(|record| record.rtype().eq(&RRTypes::Soa))
+            .ok_or_else(|| ZoneError::new(ErrorKind::SerialMismatch,
+                &format!("transaction ending at serial {} adds no SOA", txn.serial_1())))?;
+
+        let apex = soa.fqdn().to_lowercase();
+
+        match soa.data().and_then(|data| data.as_any().downcast_ref::<SoaRRData>()) {
+            Some(data) if data.serial() == txn.serial_1() => {}
+            _ => return Err(ZoneError::new(ErrorKind::SerialMismatch,
+                &format!("transaction ends at serial {} but adds a different SOA", txn.serial_1())))
+        }
+
+        if let Some(soa) = self.soa() {
+            if soa.serial() != txn.serial_0() {
+                return Err(ZoneError::new(ErrorKind::SerialMismatch,
+                    &format!("transaction starts at serial {} but zone is at serial {}", txn.serial_0(), soa.serial())));
+            }
+        }
+
+        let relative = |op_code| txn.records(op_code).iter()
+            .map(|record| fqdn_to_relative(&apex, &record.fqdn().to_lowercase())
+                .map(|name| (name, record))
+                .ok_or_else(|| ZoneError::new(ErrorKind::OutOfZone, &format!("record {} is outside of zone {}", record.fqdn(), apex))))
+            .collect::<Result<Vec<_>, _>>();
+
+        let deletes = relative(TxnOpCodes::Delete)?;
+        let adds = relative(TxnOpCodes::Add)?;
+
+        for (name, record) in deletes {
+            if record.rtype().eq(&RRTypes::Soa) {
+                self.remove_rr_set(&name, &RRTypes::Soa);
+                continue;
+            }
+
+            if let Some(data) = record.data() {
+                self.remove_record(&name, &record.rtype(), data, 0);
+            }
+        }
+
+        for (name, record) in adds {
+            if record.rtype().eq(&RRTypes::Soa) {
+                self.remove_rr_set(&name, &RRTypes::Soa);
+            }
+
+            if let Some(data) = record.data() {
+                self.add_record(&name, record.rtype(), record.ttl(), data.clone());
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn replay_journal(&mut self) -> Result<(), ZoneError> {
+        let journal_err = |e: JournalReaderError| ZoneError::new(ErrorKind::Journal, &e.to_string());
+
+        let mut reader = self.journal_reader().map_err(journal_err)?;
+        let (begin_serial, end_serial) = {
+            let headers = reader.headers().map_err(journal_err)?;
+            (headers.begin_serial(), headers.end_serial())
+        };
+
+        let serial = match self.soa() {
+            Some(soa) => soa.serial(),
+            None => begin_serial
+        };
+
+        if serial == end_serial {
+            return Ok(());
+        }
+
+        reader.seek(serial).map_err(journal_err)?;
+
+        let mut updated = self.clone();
+        for txn in reader.txns() {
+            updated.apply_txn(&txn.map_err(journal_err)?)?;
+        }
+
+        *self = updated;
+        Ok(())
     }
 
     pub fn journal_reader(&self) -> Result<JournalReader, JournalReaderError> {
-        JournalReader::open(self.journal_path.as_ref().unwrap())
+        JournalReader::open(self.journal_path.as_ref()
+            .ok_or_else(|| JournalReaderError::new(JournalErrorKind::PathNotFound, "journal path was not set"))?)
     }
 
     pub fn set_journal_path<P: Into<PathBuf>>(&mut self, journal_path: P) {
@@ -235,3 +765,236 @@ impl Zone {
         self
     }
 }
+
+#[test]
+fn test() {
+    use std::net::Ipv4Addr;
+    use crate::journal::journal_writer::JournalWriter;
+    use crate::rr_data::in_a_rr_data::InARRData;
+
+    let soa = |serial| SoaRRData::new("ns1.find9.net", "admin.find9.net", serial, 3600, 600, 604800, 300).upcast();
+    let a = InARRData::new(Ipv4Addr::new(127, 0, 0, 1)).upcast();
+
+    let mut txn_0 = Txn::new(1, 2);
+    txn_0.add_record(TxnOpCodes::Delete, "find9.net", RRClasses::In, RRTypes::Soa, 3600, Some(soa(1)));
+    txn_0.add_record(TxnOpCodes::Add, "find9.net", RRClasses::In, RRTypes::Soa, 3600, Some(soa(2)));
+    txn_0.add_record(TxnOpCodes::Add, "www.find9.net", RRClasses::In, RRTypes::A, 300, Some(a.clone()));
+
+    let mut txn_1 = Txn::new(2, 3);
+    txn_1.add_record(TxnOpCodes::Delete, "find9.net", RRClasses::In, RRTypes::Soa, 3600, Some(soa(2)));
+    txn_1.add_record(TxnOpCodes::Delete, "www.find9.net", RRClasses::In, RRTypes::A, 300, Some(a.clone()));
+    txn_1.add_record(TxnOpCodes::Add, "find9.net", RRClasses::In, RRTypes::Soa, 3600, Some(soa(3)));
+    txn_1.add_record(TxnOpCodes::Add, "mail.find9.net", RRClasses::In, RRTypes::A, 300, Some(a.clone()));
+
+    let path = std::env::temp_dir().join(format!("rlibdns_zone_replay_test_{}.jnl", std::process::id()));
+    let mut writer = JournalWriter::create(&path).unwrap();
+    writer.write_txns(&[txn_0.clone(), txn_1]).unwrap();
+
+    let mut zone = Zone::new_with_jnl(ZoneTypes::Master, RRClasses::In, &path);
+    zone.add_record("", RRTypes::Soa, 3600, soa(1));
+    zone.replay_journal().unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(zone.soa().unwrap().serial(), 3);
+    assert!(zone.rr_set("www", &RRTypes::A).is_none());
+    assert!(zone.rr_set("mail", &RRTypes::A).is_some());
+    assert!(zone.apply_txn(&txn_0).is_err());
+
+    let mut txn_2 = Txn::new(3, 4);
+    txn_2.add_record(TxnOpCodes::Delete, "find9.net", RRClasses::In, RRTypes::Soa, 3600, Some(soa(3)));
+    txn_2.add_record(TxnOpCodes::Delete, "mail.find9.net", RRClasses::In, RRTypes::A, 300, Some(a.clone()));
+    txn_2.add_record(TxnOpCodes::Add, "find9.net", RRClasses::In, RRTypes::Soa, 3600, Some(soa(4)));
+    txn_2.add_record(TxnOpCodes::Add, "www.example.com", RRClasses::In, RRTypes::A, 300, Some(a.clone()));
+    assert_eq!(zone.apply_txn(&txn_2).unwrap_err().kind(), &ErrorKind::OutOfZone);
+
+    let mut txn_3 = Txn::new(3, 5);
+    txn_3.add_record(TxnOpCodes::Delete, "find9.net", RRClasses::In, RRTypes::Soa, 3600, Some(soa(3)));
+    txn_3.add_record(TxnOpCodes::Delete, "mail.find9.net", RRClasses::In, RRTypes::A, 300, Some(a.clone()));
+    txn_3.add_record(TxnOpCodes::Add, "find9.net", RRClasses::In, RRTypes::Soa, 3600, Some(soa(4)));
+    assert_eq!(zone.apply_txn(&txn_3).unwrap_err().kind(), &ErrorKind::SerialMismatch);
+
+    assert_eq!(zone.soa().unwrap().serial(), 3);
+    assert!(zone.rr_set("mail", &RRTypes::A).is_some());
+}
+
+#[test]
+fn wildcard() {
+    use std::net::Ipv4Addr;
+    use crate::rr_data::in_a_rr_data::InARRData;
+    use crate::rr_data::txt_rr_data::TxtRRData;
+
+    let a = InARRData::new(Ipv4Addr::new(127, 0, 0, 1)).upcast();
+
+    let mut zone = Zone::new(ZoneTypes::Master, RRClasses::In);
+    zone.add_record("*", RRTypes::A, 300, a.clone());
+    zone.add_record("www", RRTypes::A, 300, a.clone());
+    zone.add_record("*.sub", RRTypes::Txt, 300, TxtRRData::new(vec!["wildcard".to_string()]).upcast());
+    zone.add_record("host.ent", RRTypes::A, 300, a.clone());
+
+    assert_eq!(zone.find_rr_set("www", &RRTypes::A).map(|(_, s)| s), Some(false));
+    assert_eq!(zone.find_rr_set("foo", &RRTypes::A).map(|(_, s)| s), Some(true));
+    assert_eq!(zone.find_rr_set("a.b.foo", &RRTypes::A).map(|(_, s)| s), Some(true));
+    assert_eq!(zone.wildcard_rr_sets("x.sub").map(|(n, _)| n), Some("*.sub".to_string()));
+    assert!(zone.find_rr_set("x.sub", &RRTypes::A).is_none());
+    assert_eq!(zone.find_rr_set("x.sub", &RRTypes::Txt).map(|(_, s)| s), Some(true));
+    assert!(zone.find_rr_sets("ent").is_none());
+    assert!(zone.find_rr_sets("x.www").is_none());
+}
+
+#[test]
+fn case_folding() {
+    use std::net::Ipv4Addr;
+    use crate::rr_data::in_a_rr_data::InARRData;
+
+    let mut zone = Zone::new(ZoneTypes::Master, RRClasses::In);
+    zone.add_record("MiXeD.Sub", RRTypes::A, 300, InARRData::new(Ipv4Addr::new(127, 0, 0, 1)).upcast());
+    zone.add_record("mixed.sub", RRTypes::A, 300, InARRData::new(Ipv4Addr::new(127, 0, 0, 2)).upcast());
+
+    assert_eq!(zone.rr_set("MIXED.SUB", &RRTypes::A).unwrap().data().len(), 2);
+    assert!(zone.name_exists("SUB"));
+    assert_eq!(zone.all_rr_sets_recursive().map(|(name, _)| name).collect::<Vec<_>>(), vec!["mixed.sub".to_string()]);
+}
+
+#[test]
+fn escaped_labels() {
+    use std::net::Ipv4Addr;
+    use crate::rr_data::in_a_rr_data::InARRData;
+    use crate::rr_data::ns_rr_data::NsRRData;
+
+    let mut zone = Zone::new(ZoneTypes::Master, RRClasses::In);
+    zone.add_record("a\\.b.sub", RRTypes::A, 300, InARRData::new(Ipv4Addr::new(127, 0, 0, 1)).upcast());
+    zone.add_record("b.sub", RRTypes::A, 300, InARRData::new(Ipv4Addr::new(127, 0, 0, 2)).upcast());
+    zone.add_record("ns\\.x.del", RRTypes::Ns, 300, NsRRData::new("ns1.find9.net").upcast());
+
+    assert_eq!(zone.closest_encloser("x.a\\.b.sub"), "a\\.b.sub");
+    assert_eq!(zone.delegation_point("www.ns\\.x.del").map(|(name, _)| name), Some("ns\\.x.del".to_string()));
+    assert!(zone.delegation_point("www.x.del").is_none());
+}
+
+#[test]
+fn nsec() {
+    use std::net::Ipv4Addr;
+    use crate::rr_data::in_a_rr_data::InARRData;
+    use crate::rr_data::ns_rr_data::NsRRData;
+
+    let a = InARRData::new(Ipv4Addr::new(127, 0, 0, 1)).upcast();
+
+    let mut zone = Zone::new(ZoneTypes::Master, RRClasses::In);
+    zone.add_record("", RRTypes::Soa, 3600, SoaRRData::new("ns1.find9.net", "admin.find9.net", 1, 3600, 600, 604800, 300).upcast());
+    zone.add_record("www", RRTypes::A, 300, a.clone());
+    zone.add_record("*.wild", RRTypes::A, 300, a.clone());
+    zone.add_record("host.ent", RRTypes::A, 300, a.clone());
+    zone.add_record("sub", RRTypes::Ns, 300, NsRRData::new("ns.sub.find9.net").upcast());
+    zone.add_record("ns.sub", RRTypes::A, 300, a.clone());
+    assert!(zone.generate_nsec_chain("find9.net"));
+
+    let next = |name: &str| {
+        let nsec = zone.rr_set(name, &RRTypes::NSec).unwrap();
+        assert_eq!(nsec.ttl(), 300);
+        nsec.data()[0].as_any().downcast_ref::<NSecRRData>().unwrap().clone()
+    };
+
+    assert_eq!(next("").next_domain().unwrap(), "host.ent.find9.net");
+    assert_eq!(next("host.ent").next_domain().unwrap(), "sub.find9.net");
+    assert_eq!(next("sub").next_domain().unwrap(), "*.wild.find9.net");
+    assert_eq!(next("sub").types(), &vec![RRTypes::Ns, RRTypes::RRSig, RRTypes::NSec]);
+    assert_eq!(next("*.wild").next_domain().unwrap(), "www.find9.net");
+    assert_eq!(next("www").next_domain().unwrap(), "find9.net");
+    assert!(zone.rr_set("ns.sub", &RRTypes::NSec).is_none());
+
+    let names = |proofs: Vec<(String, &RRSet)>| proofs.into_iter().map(|(n, _)| n).collect::<Vec<_>>();
+    assert_eq!(names(zone.nsec_denial("www", &RRTypes::Mx)), vec!["www"]);
+    assert_eq!(names(zone.nsec_denial("www", &RRTypes::A)), Vec::<String>::new());
+    assert_eq!(names(zone.nsec_denial("ent", &RRTypes::A)), vec![""]);
+    assert_eq!(names(zone.nsec_denial("mail", &RRTypes::A)), vec!["host.ent", ""]);
+    assert_eq!(names(zone.nsec_denial("x.wild", &RRTypes::A)), vec!["*.wild"]);
+    assert_eq!(names(zone.nsec_denial("x.wild", &RRTypes::Mx)), vec!["*.wild"]);
+    assert_eq!(names(zone.nsec_denial("zzz", &RRTypes::A)), vec!["www", ""]);
+}
+
+#[test]
+fn nsec3() {
+    use std::net::Ipv4Addr;
+    use crate::rr_data::ds_rr_data::DsRRData;
+    use crate::rr_data::in_a_rr_data::InARRData;
+    use crate::rr_data::ns_rr_data::NsRRData;
+
+    let a = InARRData::new(Ipv4Addr::new(127, 0, 0, 1)).upcast();
+    let salt = [0xaa, 0xbb, 0xcc, 0xdd];
+
+    let mut zone = Zone::new(ZoneTypes::Master, RRClasses::In);
+    zone.add_record("", RRTypes::Soa, 3600, SoaRRData::new("ns1.example", "admin.example", 1, 3600, 600, 604800, 300).upcast());
+    zone.add_record("a", RRTypes::A, 300, a.clone());
+    zone.add_record("host.ent", RRTypes::A, 300, a.clone());
+    zone.add_record("*.w", RRTypes::Mx, 300, a.clone());
+    zone.add_record("insecure", RRTypes::Ns, 300, NsRRData::new("ns.insecure.example").upcast());
+    zone.add_record("ns.insecure", RRTypes::A, 300, a.clone());
+    zone.add_record("secure", RRTypes::Ns, 300, NsRRData::new("ns.secure.example").upcast());
+    zone.add_record("secure", RRTypes::Ds, 300, DsRRData::new(1, 15, 2, &[0; 32]).upcast());
+
+    assert!(zone.generate_nsec3_chain("example", &salt, 12, true));
+    assert_eq!(zone.nsec3_param().unwrap().iterations(), 12);
+
+    let labels: Vec<String> = zone.all_rr_sets_recursive()
+        .filter(|(_, sets)| sets.iter().any(|s| s.rtype().eq(&RRTypes::NSec3)))
+        .map(|(name, _)| name)
+        .collect();
+    assert_eq!(labels.len(), 7);
+
+    let nsec3 = |label: &str| zone.rr_set(label, &RRTypes::NSec3).unwrap().data()[0].as_any().downcast_ref::<NSec3RRData>().unwrap().clone();
+    for (i, label) in labels.iter().enumerate() {
+        let next = base32::hex_encode_nopad(nsec3(label).next_hash()).to_lowercase();
+        assert_eq!(&next, &labels[(i + 1) % labels.len()]);
+        assert!(nsec3(label).is_opt_out());
+    }
+
+    assert_eq!(zone.matching_nsec3("example", "").unwrap().0, "0p9mhaveqvm6t7vbl5lop2u3t2rp3tom");
+    assert_eq!(nsec3("0p9mhaveqvm6t7vbl5lop2u3t2rp3tom").types(), &vec![RRTypes::Soa, RRTypes::RRSig, RRTypes::NSec3Param]);
+    assert!(zone.matching_nsec3("example", "ent").unwrap().1.data()[0].as_any().downcast_ref::<NSec3RRData>().unwrap().types().is_empty());
+    assert!(zone.matching_nsec3("example", "insecure").is_none());
+    assert!(zone.matching_nsec3("example", "secure").is_some());
+
+    let names = |proofs: Vec<(String, &RRSet)>| proofs.into_iter().map(|(n, _)| n).collect::<Vec<_>>();
+    assert_eq!(names(zone.nsec3_denial("example", "a", &RRTypes::A)), Vec::<String>::new());
+    assert_eq!(names(zone.nsec3_denial("example", "a", &RRTypes::Mx)), vec![zone.matching_nsec3("example", "a").unwrap().0]);
+
+    let proofs = names(zone.nsec3_denial("example", "b.a", &RRTypes::A));
+    assert_eq!(proofs[0], zone.matching_nsec3("example", "a").unwrap().0);
+    assert_eq!(proofs[1], zone.covering_nsec3("example", "b.a").unwrap().0);
+    assert_eq!(proofs[2], zone.covering_nsec3("example", "*.a").unwrap().0);
+
+    assert_eq!(names(zone.nsec3_denial("example", "x.w", &RRTypes::Mx)), vec![zone.covering_nsec3("example", "x.w").unwrap().0]);
+    assert!(names(zone.nsec3_denial("example", "x.w", &RRTypes::A)).contains(&zone.matching_nsec3("example", "*.w").unwrap().0));
+
+    let proofs = names(zone.nsec3_denial("example", "insecure", &RRTypes::Ds));
+    assert_eq!(proofs, vec![zone.matching_nsec3("example", "").unwrap().0, zone.covering_nsec3("example", "insecure").unwrap().0]);
+}
+
+#[test]
+fn zonemd() {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+    use crate::rr_data::aaaa_rr_data::AaaaRRData;
+    use crate::rr_data::in_a_rr_data::InARRData;
+    use crate::rr_data::ns_rr_data::NsRRData;
+    use crate::utils::hex;
+
+    let mut zone = Zone::new(ZoneTypes::Master, RRClasses::In);
+    zone.add_record("", RRTypes::Soa, 86400, SoaRRData::new("ns1.example", "admin.example", 2018031900, 1800, 900, 604800, 86400).upcast());
+    zone.add_record("", RRTypes::Ns, 86400, NsRRData::new("ns1.example").upcast());
+    zone.add_record("", RRTypes::Ns, 86400, NsRRData::new("ns2.example").upcast());
+    zone.add_record("ns1", RRTypes::A, 3600, InARRData::new(Ipv4Addr::new(203, 0, 113, 63)).upcast());
+    zone.add_record("ns2", RRTypes::Aaaa, 3600, AaaaRRData::new(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0x63)).upcast());
+    assert!(!zone.verify_zonemd("example"));
+
+    assert!(zone.generate_zonemd("example", ZONEMD_HASH_SHA384));
+    let zonemd = zone.rr_set("", &RRTypes::ZoneMd).unwrap().data()[0].as_any().downcast_ref::<ZoneMdRRData>().unwrap().clone();
+    assert_eq!(zonemd.serial(), 2018031900);
+    assert_eq!(hex::encode(zonemd.digest()), "c68090d90a7aed716bc459f9340e3d7c1370d4d24b7e2fc3a1ddc0b9a87153b9a9713b3c9ae5cc27777f98b8e730044c");
+    assert!(zone.verify_zonemd("example"));
+
+    zone.add_record("ns3", RRTypes::A, 3600, InARRData::new(Ipv4Addr::new(203, 0, 113, 64)).upcast());
+    assert!(!zone.verify_zonemd("example"));
+    assert!(zone.generate_zonemd("example", ZONEMD_HASH_SHA512));
+    assert_eq!(zone.rr_set("", &RRTypes::ZoneMd).unwrap().data().len(), 1);
+    assert!(zone.verify_zonemd("example"));
+}
@@ -6,9 +6,13 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use crate::messages::inter::rr_classes::RRClasses;
 use crate::messages::inter::rr_types::RRTypes;
+use crate::messages::wire::FromWireContext;
+use crate::rr_data::raw_rr_data::RawRRData;
 use crate::utils::fqdn_utils::fqdn_to_relative;
 use crate::zone::inter::zone_rr_data::ZoneRRData;
 
+type ZoneRecord = (String, RRTypes, u32, Box<dyn ZoneRRData>);
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum ParserState {
     Init,
@@ -73,7 +77,7 @@ impl ZoneReader {
         })
     }
 
-    pub fn read_record(&mut self) -> Result<Option<(String, RRTypes, u32, Box<dyn ZoneRRData>)>, ZoneReaderError> {
+    pub fn read_record(&mut self) -> Result<Option<ZoneRecord>, ZoneReaderError> {
         let mut state = ParserState::Init;
         let mut paren_count: u8 = 0;
 
@@ -83,8 +87,9 @@ impl ZoneReader {
 
         let mut directive_buf = String::new();
 
-        let mut record: Option<(String, RRTypes, u32, Box<dyn ZoneRRData>)> = None;
+        let mut record: Option<ZoneRecord> = None;
         let mut data_count = 0;
+        let mut generic = false;
 
         let mut line = String::new();
         loop {
@@ -201,6 +206,11 @@ impl ZoneReader {
                                     }
 
                                 } else {
+                                    if data_count == 0 && &part[0..word_len] == b"\\#" && !record.as_ref().unwrap().3.as_any().is::<RawRRData>() {
+                                        record.as_mut().unwrap().3 = <RawRRData as ZoneRRData>::upcast(RawRRData::default());
+                                        generic = true;
+                                    }
+
                                     record.as_mut().unwrap().3.set_data(data_count, &String::from_utf8(part[0..word_len].to_vec())
                                         .map_err(|_| ZoneReaderError::new(ErrorKind::Parsing, "unable to parse string"))?)?;
 
@@ -228,14 +238,34 @@ impl ZoneReader {
                     }
 
                     if record.is_some() && paren_count == 0 {
-                        return Ok(record);
+                        return self.decode_generic(record, generic);
                     }
                 }
                 Err(e) => return Err(ZoneReaderError::new(ErrorKind::UnexpectedEof, &e.to_string()))
             }
         }
 
-        Ok(record)
+        self.decode_generic(record, generic)
+    }
+
+    fn decode_generic(&self, record: Option<ZoneRecord>, generic: bool) -> Result<Option<ZoneRecord>, ZoneReaderError> {
+        if !generic {
+            return Ok(record);
+        }
+
+        let (name, _type, ttl, data) = record.unwrap();
+        let buf = data.to_bytes()
+            .map_err(|e| ZoneReaderError::new(ErrorKind::Format, &e.to_string()))?;
+
+        let mut context = FromWireContext::new(&buf);
+        let data = <dyn ZoneRRData>::from_wire(&mut context, buf.len() as u16, _type, &self.class)
+            .map_err(|e| ZoneReaderError::new(ErrorKind::Format, &format!("unable to decode generic record data for {}: {}", _type, e)))?;
+
+        if context.pos() != buf.len() {
+            return Err(ZoneReaderError::new(ErrorKind::ExtraRRData, &format!("generic record data for {} has trailing bytes", _type)));
+        }
+
+        Ok(Some((name, _type, ttl, data)))
     }
 
     pub fn origin(&self) -> &str {
@@ -267,7 +297,7 @@ pub struct ZoneReaderIter<'a> {
 
 impl<'a> Iterator for ZoneReaderIter<'a> {
 
-    type Item = Result<(String, RRTypes, u32, Box<dyn ZoneRRData>), ZoneReaderError>;
+    type Item = Result<ZoneRecord, ZoneReaderError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.reader.read_record() {
@@ -277,3 +307,36 @@ impl<'a> Iterator for ZoneReaderIter<'a> {
         }
     }
 }
+
+#[test]
+fn test() {
+    use std::net::Ipv4Addr;
+    use crate::rr_data::in_a_rr_data::InARRData;
+    use crate::rr_data::soa_rr_data::SoaRRData;
+
+    let path = std::env::temp_dir().join(format!("rlibdns_zone_reader_test_{}.zone", std::process::id()));
+    std::fs::write(&path, "@ 3600 IN SOA \\# 52 036e73310566696e6439036e6574000561646d696e0566696e6439036e657400 78a3f175 00000e10 00000258 00093a80 0000012c\n\
+        www 300 IN A \\# 4 7f000001\n\
+        raw 300 IN TYPE65534 \\# 2 dead\n\
+        bad 300 IN A \\# 5 7f00000100\n").unwrap();
+
+    let mut reader = ZoneReader::open(&path, "find9.net", RRClasses::In).unwrap();
+
+    let (_, rtype, _, data) = reader.read_record().unwrap().unwrap();
+    assert_eq!(rtype, RRTypes::Soa);
+    let soa = data.as_any().downcast_ref::<SoaRRData>().unwrap();
+    assert_eq!(soa.serial(), 2024010101);
+    assert_eq!(soa.to_string(), SoaRRData::new("ns1.find9.net", "admin.find9.net", 2024010101, 3600, 600, 604800, 300).to_string());
+
+    let (name, rtype, _, data) = reader.read_record().unwrap().unwrap();
+    assert_eq!((name.as_str(), rtype), ("www", RRTypes::A));
+    assert_eq!(data.as_any().downcast_ref::<InARRData>().unwrap().address(), Some(Ipv4Addr::new(127, 0, 0, 1)));
+
+    let (_, rtype, _, data) = reader.read_record().unwrap().unwrap();
+    assert_eq!(rtype, RRTypes::Unknown(65534));
+    assert_eq!(data.as_any().downcast_ref::<RawRRData>().unwrap().data(), &[ 0xde, 0xad ]);
+
+    assert!(reader.read_record().is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
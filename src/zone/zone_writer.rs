@@ -0,0 +1,228 @@
+use std::fmt;
+use std::fmt::Formatter;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use crate::messages::inter::rr_classes::RRClasses;
+use crate::messages::inter::rr_types::RRTypes;
+use crate::rr_data::dnskey_rr_data::DnsKeyRRData;
+use crate::rr_data::inter::rr_data::RRData;
+use crate::rr_data::rrsig_rr_data::RRSigRRData;
+use crate::rr_data::soa_rr_data::SoaRRData;
+use crate::utils::base64;
+use crate::utils::time_utils::TimeUtils;
+use crate::zone::rr_set::RRSet;
+use crate::zone::zone::Zone;
+
+pub struct ZoneWriter {
+    writer: BufWriter<File>,
+    origin: String,
+    class: RRClasses,
+    default_ttl: u32
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ZoneWriterError {
+    _type: ErrorKind,
+    message: String
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ErrorKind {
+    PathNotFound,
+    WriteErr
+}
+
+impl ZoneWriterError {
+
+    pub fn new(_type: ErrorKind, message: &str) -> Self {
+        Self {
+            _type,
+            message: message.to_string()
+        }
+    }
+}
+
+impl fmt::Display for ZoneWriterError {
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self._type, self.message)
+    }
+}
+
+impl ZoneWriter {
+
+    pub fn open<P: Into<PathBuf>>(file_path: P, origin: &str, class: RRClasses) -> Result<Self, ZoneWriterError> {
+        let file = File::create(file_path.into()).map_err(|e| ZoneWriterError::new(ErrorKind::PathNotFound, &e.to_string()))?;
+        let writer = BufWriter::new(file);
+
+        Ok(Self {
+            writer,
+            origin: origin.to_lowercase(),
+            class,
+            default_ttl: 300
+        })
+    }
+
+    pub fn set_default_ttl(&mut self, default_ttl: u32) {
+        self.default_ttl = default_ttl;
+    }
+
+    pub fn default_ttl(&self) -> u32 {
+        self.default_ttl
+    }
+
+    pub fn origin(&self) -> &str {
+        &self.origin
+    }
+
+    pub fn write_directives(&mut self) -> Result<(), ZoneWriterError> {
+        self.write_line(&format!("$ORIGIN {}.", self.origin))?;
+        self.write_line(&format!("$TTL {}", self.default_ttl))
+    }
+
+    pub fn write_record(&mut self, name: &str, rtype: RRTypes, ttl: u32, data: &dyn RRData) -> Result<(), ZoneWriterError> {
+        let line = format!("{}\t{}\t{}\t{}\t{}", self.relative_name(name), ttl, self.class, rtype, Self::format_data(rtype, data));
+        self.write_line(&line)
+    }
+
+    pub fn write_rr_set(&mut self, name: &str, set: &RRSet) -> Result<(), ZoneWriterError> {
+        for data in set.data() {
+            self.write_record(name, set.rtype(), set.ttl(), data.as_ref())?;
+        }
+
+        Ok(())
+    }
+
+    pub fn write_zone(&mut self, zone: &Zone) -> Result<(), ZoneWriterError> {
+        self.write_directives()?;
+
+        if let Some(sets) = zone.all_rr_sets("") {
+            if let Some(set) = sets.iter().find(|s| s.rtype().eq(&RRTypes::Soa)) {
+                self.write_rr_set("", set)?;
+            }
+
+            for set in sets.iter().filter(|s| !s.rtype().eq(&RRTypes::Soa)) {
+                self.write_rr_set("", set)?;
+            }
+        }
+
+        for (name, sets) in zone.all_rr_sets_recursive() {
+            if name.is_empty() {
+                continue;
+            }
+
+            for set in sets {
+                self.write_rr_set(&name, set)?;
+            }
+        }
+
+        self.flush()
+    }
+
+    pub fn flush(&mut self) -> Result<(), ZoneWriterError> {
+        self.writer.flush().map_err(|e| ZoneWriterError::new(ErrorKind::WriteErr, &e.to_string()))
+    }
+
+    pub fn relative_name(&self, name: &str) -> String {
+        if name.is_empty() {
+            return "@".to_string();
+        }
+
+        name.to_string()
+    }
+
+    fn write_line(&mut self, line: &str) -> Result<(), ZoneWriterError> {
+        writeln!(self.writer, "{}", line).map_err(|e| ZoneWriterError::new(ErrorKind::WriteErr, &e.to_string()))
+    }
+
+    fn format_data(rtype: RRTypes, data: &dyn RRData) -> String {
+        match rtype {
+            RRTypes::Soa => {
+                if let Some(soa) = data.as_any().downcast_ref::<SoaRRData>() {
+                    return format!("{}. {}. (\n\t\t\t\t{}\t; serial\n\t\t\t\t{}\t; refresh\n\t\t\t\t{}\t; retry\n\t\t\t\t{}\t; expire\n\t\t\t\t{}\t; minimum\n\t\t\t\t)",
+                                   soa.fqdn().map(|s| s.as_str()).unwrap_or(""),
+                                   soa.mailbox().map(|s| s.as_str()).unwrap_or(""),
+                                   soa.serial(),
+                                   soa.refresh(),
+                                   soa.retry(),
+                                   soa.expire(),
+                                   soa.minimum_ttl());
+                }
+            }
+            RRTypes::DnsKey => {
+                if let Some(key) = data.as_any().downcast_ref::<DnsKeyRRData>() {
+                    return format!("{} {} {} (\n\t\t\t\t{}\n\t\t\t\t)",
+                                   key.flags(),
+                                   key.protocol(),
+                                   key.algorithm(),
+                                   base64::encode(key.public_key()));
+                }
+            }
+            RRTypes::RRSig => {
+                if let Some(sig) = data.as_any().downcast_ref::<RRSigRRData>() {
+                    return format!("{} {} {} {} (\n\t\t\t\t{} {} {} {}.\n\t\t\t\t{}\n\t\t\t\t)",
+                                   sig.type_covered().map(|t| t.to_string()).unwrap_or_default(),
+                                   sig.algorithm(),
+                                   sig.labels(),
+                                   sig.original_ttl(),
+                                   sig.expiration().to_time_format(),
+                                   sig.inception().to_time_format(),
+                                   sig.key_tag(),
+                                   sig.signer_name().map(|s| s.as_str()).unwrap_or(""),
+                                   base64::encode(sig.signature()));
+                }
+            }
+            _ => {}
+        }
+
+        data.to_string()
+    }
+}
+
+#[test]
+fn test() {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+    use crate::rr_data::aaaa_rr_data::AaaaRRData;
+    use crate::rr_data::in_a_rr_data::InARRData;
+    use crate::rr_data::mx_rr_data::MxRRData;
+    use crate::rr_data::ns_rr_data::NsRRData;
+    use crate::rr_data::txt_rr_data::TxtRRData;
+    use crate::zone::inter::zone_types::ZoneTypes;
+    use crate::zone::zone_reader::ZoneReader;
+
+    let mut zone = Zone::new(ZoneTypes::Master, RRClasses::In);
+    zone.add_record("", RRTypes::Soa, 3600, SoaRRData::new("ns1.find9.net", "admin.find9.net", 2024010101, 3600, 600, 604800, 300).upcast());
+    zone.add_record("", RRTypes::Ns, 3600, NsRRData::new("ns1.find9.net").upcast());
+    zone.add_record("", RRTypes::Ns, 3600, NsRRData::new("ns2.find9.net").upcast());
+    zone.add_record("", RRTypes::Mx, 300, MxRRData::new(10, "mail.find9.net").upcast());
+    zone.add_record("", RRTypes::DnsKey, 3600, DnsKeyRRData::new(257, 3, 13, vec![ 0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x7, 0x8 ]).upcast());
+    zone.add_record("", RRTypes::RRSig, 3600, RRSigRRData::new(RRTypes::DnsKey, 13, 2, 3600, 1767225600, 1764547200, 12345, "find9.net", &[ 0x9, 0x8, 0x7, 0x6, 0x5, 0x4 ]).upcast());
+    zone.add_record("www", RRTypes::A, 300, InARRData::new(Ipv4Addr::new(127, 0, 0, 1)).upcast());
+    zone.add_record("www", RRTypes::Aaaa, 300, AaaaRRData::new(Ipv6Addr::LOCALHOST).upcast());
+    zone.add_record("txt", RRTypes::Txt, 300, TxtRRData::new(vec!["v=spf1 -all".to_string()]).upcast());
+
+    let path = std::env::temp_dir().join(format!("rlibdns_zone_writer_test_{}.zone", std::process::id()));
+    let mut writer = ZoneWriter::open(&path, "find9.net", RRClasses::In).unwrap();
+    writer.write_zone(&zone).unwrap();
+
+    let mut parsed = Zone::new(ZoneTypes::Master, RRClasses::In);
+    let mut reader = ZoneReader::open(&path, "find9.net", RRClasses::In).unwrap();
+    for record in reader.records() {
+        let (name, rtype, ttl, data) = record.unwrap();
+        parsed.add_record(&name, rtype, ttl, data);
+    }
+
+    for (name, sets) in zone.all_rr_sets_recursive() {
+        let parsed_sets = parsed.all_rr_sets(&name).unwrap();
+        assert_eq!(sets.len(), parsed_sets.len());
+
+        for set in sets {
+            let parsed_set = parsed.rr_set(&name, &set.rtype()).unwrap();
+            assert_eq!(set.ttl(), parsed_set.ttl());
+            assert_eq!(set.data(), parsed_set.data());
+        }
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}